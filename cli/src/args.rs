@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(name = "tollgate", version, about = "Operate Tollgate vaults")]
+pub struct Cli {
+    /// RPC URL or moniker (localnet, devnet, mainnet)
+    #[arg(short, long, global = true, default_value = "localnet")]
+    pub url: String,
+
+    /// Payer keypair file
+    #[arg(short, long, global = true, default_value = "~/.config/solana/id.json")]
+    pub keypair: String,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Initialize a vault from a TOML policy file
+    Init {
+        /// Vault config file
+        #[arg(short, long)]
+        config: PathBuf,
+    },
+    /// Run every remaining page of the current day
+    Crank {
+        /// Vault config file
+        #[arg(short, long)]
+        config: PathBuf,
+        /// Number of investors per crank instruction
+        #[arg(long, default_value_t = 10)]
+        page_size: u32,
+        /// Use crank_with_init to create missing investor ATAs
+        #[arg(long)]
+        with_init: bool,
+        /// Compute unit limit of each crank transaction
+        #[arg(long, default_value_t = 1_400_000)]
+        compute_unit_limit: u32,
+    },
+    /// Show the decoded policy and progress of a vault
+    Status {
+        /// Vault pubkey
+        #[arg(long)]
+        vault: String,
+    },
+    /// Create the day history of a vault initialized before it existed
+    InitDayHistory {
+        /// Vault pubkey
+        #[arg(long)]
+        vault: String,
+    },
+    /// Create the position registry of a vault initialized before it existed
+    InitPositionRegistry {
+        /// Vault config file
+        #[arg(short, long)]
+        config: PathBuf,
+    },
+    /// Add a honorary position on another pool of the vault pair, signed by the vault keypair
+    AddPosition {
+        /// Vault config file
+        #[arg(short, long)]
+        config: PathBuf,
+        /// DAMM v2 pool pubkey
+        #[arg(long)]
+        pool: String,
+        /// DAMM v2 pool config pubkey of the pool
+        #[arg(long)]
+        pool_config: String,
+    },
+    /// Add permanently locked liquidity to the primary position, paid by the payer keypair
+    AddLiquidity {
+        /// Vault config file
+        #[arg(short, long)]
+        config: PathBuf,
+        /// DAMM v2 liquidity to add and lock
+        #[arg(long)]
+        liquidity: u128,
+        /// Most base tokens spent, the unused part is refunded
+        #[arg(long)]
+        max_base: u64,
+        /// Most quote tokens spent, the unused part is refunded
+        #[arg(long)]
+        max_quote: u64,
+    },
+    /// Claim the pool reward of the day and distribute it over the day snapshot
+    CrankReward {
+        /// Vault config file
+        #[arg(short, long)]
+        config: PathBuf,
+        /// DAMM v2 reward index of the pool
+        #[arg(long, default_value_t = 0)]
+        reward_index: u8,
+        /// Number of investors per crank instruction
+        #[arg(long, default_value_t = 10)]
+        page_size: u32,
+        /// Compute unit limit of each crank transaction
+        #[arg(long, default_value_t = 1_400_000)]
+        compute_unit_limit: u32,
+    },
+    /// Show the closed days recorded in the day history of a vault
+    History {
+        /// Vault pubkey
+        #[arg(long)]
+        vault: String,
+    },
+    /// Show the lifetime payouts of an investor stream
+    InvestorStats {
+        /// Vault pubkey
+        #[arg(long)]
+        vault: String,
+        /// Stream pubkey
+        #[arg(long)]
+        stream: String,
+    },
+    /// Move the creator payouts to another wallet, signed by the vault keypair
+    SetCreator {
+        /// Vault config file
+        #[arg(short, long)]
+        config: PathBuf,
+        /// Wallet receiving the creator payouts
+        #[arg(long)]
+        creator: String,
+    },
+    /// Redirect the payouts of a stream, signed by its recipient keypair
+    SetPayoutDestination {
+        /// Vault pubkey
+        #[arg(long)]
+        vault: String,
+        /// Stream pubkey
+        #[arg(long)]
+        stream: String,
+        /// Quote token account receiving the payouts
+        #[arg(long)]
+        destination: String,
+    },
+    /// Show the remaining pages of the current day
+    PlanPages {
+        /// Vault config file
+        #[arg(short, long)]
+        config: PathBuf,
+        /// Number of investors per crank instruction
+        #[arg(long, default_value_t = 10)]
+        page_size: u32,
+    },
+}
@@ -0,0 +1,145 @@
+use anchor_client::solana_sdk::{compute_budget::ComputeBudgetInstruction, signer::Signer};
+use anyhow::{ensure, Result};
+use tollgate::state::{CrankPhase, DayState};
+use tollgate_client::{plan_pages, resume_cursor, CrankBuilder};
+
+use crate::{
+    config::VaultConfig,
+    rpc::{
+        fetch_clock, fetch_extra_positions, fetch_investors, fetch_mint_program, fetch_policy,
+        fetch_pool_base_mint, fetch_progress,
+    },
+    Context,
+};
+
+pub fn crank(
+    ctx: &Context,
+    config: &VaultConfig,
+    page_size: u32,
+    with_init: bool,
+    compute_unit_limit: u32,
+) -> Result<()> {
+    let payer = ctx.payer()?;
+    let vault = config.vault_pubkey()?;
+    let pool = config.pool()?;
+    let quote_mint = config.quote_mint()?;
+    let streams = config.streams()?;
+
+    let policy = fetch_policy(&ctx.rpc, &vault)?;
+    let progress = fetch_progress(&ctx.rpc, &vault)?;
+    let now_ts = fetch_clock(&ctx.rpc)?.unix_timestamp;
+    ensure!(
+        streams.len() >= policy.investor_count as usize,
+        "Config lists {} streams but the policy expects {} investors",
+        streams.len(),
+        policy.investor_count
+    );
+
+    let Some(cursor) = resume_cursor(&progress, now_ts) else {
+        println!("Day is closed, nothing to crank");
+        return Ok(());
+    };
+
+    let investors = fetch_investors(&ctx.rpc, &vault, &streams[..policy.investor_count as usize])?;
+    let base_mint = fetch_pool_base_mint(&ctx.rpc, &pool, &quote_mint)?;
+    let builder = CrankBuilder::new(
+        vault,
+        pool,
+        config.position_nft_mint_pubkey()?,
+        base_mint,
+        quote_mint,
+        policy.creator,
+        payer.pubkey(),
+    )
+    .base_program(fetch_mint_program(&ctx.rpc, &base_mint)?)
+    .quote_program(fetch_mint_program(&ctx.rpc, &quote_mint)?);
+
+    // A new day is opened by claiming the fees of every position
+    if progress.is_new_day(now_ts) {
+        let positions = fetch_extra_positions(&ctx.rpc, &vault)?;
+        let signature = ctx.send(
+            &payer,
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit),
+                builder.clone().positions(positions.clone()).claim_fees(),
+            ],
+            &[],
+        )?;
+        println!(
+            "Claimed fees of {} positions: {}",
+            positions.len() + 1,
+            signature
+        );
+        if fetch_progress(&ctx.rpc, &vault)?.day_state == DayState::Closed {
+            println!("Day closed");
+            return Ok(());
+        }
+    }
+
+    // The day runs a snapshot pass, the cap passes of a redistributed
+    // investor cap, then a payout pass over the same pages, a new day
+    // restarts with the snapshot pass
+    let mut cursor = cursor;
+    let mut pass = if progress.is_new_day(now_ts) && cursor == 0 {
+        CrankPhase::Snapshot
+    } else {
+        progress.phase
+    };
+    loop {
+        for page in plan_pages(policy.investor_count, cursor, page_size) {
+            let page_builder = builder
+                .clone()
+                .cursor(page.cursor)
+                .investors(investors[page.range()].to_vec());
+            let crank_instruction = if with_init {
+                page_builder.crank_with_init()
+            } else {
+                page_builder.crank()
+            };
+
+            let signature = ctx.send(
+                &payer,
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit),
+                    crank_instruction,
+                ],
+                &[],
+            )?;
+            println!(
+                "Cranked page {} to {}: {}",
+                page.cursor, page.end, signature
+            );
+
+            let progress = fetch_progress(&ctx.rpc, &vault)?;
+            if progress.day_state == DayState::Closed {
+                println!("Day closed");
+                return Ok(());
+            }
+            // A completed pass rewinds the cursor, a cap pass may repeat
+            if progress.phase != pass || progress.cursor == 0 {
+                println!("{:?} pass completed", pass);
+                break;
+            }
+            // The crank returns early without advancing the cursor, so the
+            // remaining pages must wait.
+            if progress.cursor != page.end {
+                println!(
+                    "Crank stopped at cursor {} ({:?}, {:?} pass)",
+                    progress.cursor, progress.day_state, progress.phase
+                );
+                return Ok(());
+            }
+        }
+
+        let progress = fetch_progress(&ctx.rpc, &vault)?;
+        if progress.phase == pass && progress.cursor != 0 {
+            println!(
+                "Crank stopped at cursor {} ({:?}, {:?} pass)",
+                progress.cursor, progress.day_state, progress.phase
+            );
+            return Ok(());
+        }
+        cursor = progress.cursor;
+        pass = progress.phase;
+    }
+}
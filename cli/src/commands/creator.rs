@@ -0,0 +1,24 @@
+use anchor_client::solana_sdk::{pubkey::Pubkey, signer::Signer};
+use anyhow::Result;
+use tollgate_client::SetCreatorBuilder;
+
+use crate::{config::VaultConfig, rpc::fetch_policy, Context};
+
+/// Moves the creator payouts to another wallet, signed by the vault keypair.
+pub fn set_creator(ctx: &Context, config: &VaultConfig, creator: &Pubkey) -> Result<()> {
+    let payer = ctx.payer()?;
+    let vault = config.vault()?;
+    let previous_creator = fetch_policy(&ctx.rpc, &vault.pubkey())?.creator;
+
+    let instruction = SetCreatorBuilder::new(vault.pubkey(), *creator).instruction();
+    let signature = ctx.send(&payer, &[instruction], &[&vault])?;
+
+    println!(
+        "Moved creator payouts of vault {} from {} to {}",
+        vault.pubkey(),
+        previous_creator,
+        creator
+    );
+    println!("Signature: {}", signature);
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_client::solana_sdk::{pubkey::Pubkey, signer::Signer};
+use anyhow::Result;
+use tollgate_client::{pda::get_day_history_pda, InitializeDayHistoryBuilder};
+
+use crate::{rpc::fetch_day_history, Context};
+
+pub fn init_day_history(ctx: &Context, vault: &Pubkey) -> Result<()> {
+    let payer = ctx.payer()?;
+    let instruction = InitializeDayHistoryBuilder::new(*vault, payer.pubkey()).instruction();
+
+    let signature = ctx.send(&payer, &[instruction], &[])?;
+
+    println!("Initialized day history of vault {}", vault);
+    println!("Day history: {}", get_day_history_pda(*vault).0);
+    println!("Signature:   {}", signature);
+    Ok(())
+}
+
+pub fn history(ctx: &Context, vault: &Pubkey) -> Result<()> {
+    let day_history = fetch_day_history(&ctx.rpc, vault)?;
+    let days = day_history.closed_days();
+
+    println!(
+        "{} day(s) closed, showing the last {}",
+        day_history.days_closed,
+        days.len()
+    );
+    println!(
+        "{:>6} {:>12} {:>14} {:>14} {:>14} {:>14} {:>14} {:>18} {:>6}",
+        "Day",
+        "Start",
+        "Fees",
+        "Distributable",
+        "Investors",
+        "Creator",
+        "Carry",
+        "Locked total",
+        "Paid"
+    );
+    for day in days {
+        println!(
+            "{:>6} {:>12} {:>14} {:>14} {:>14} {:>14} {:>14} {:>18} {:>6}",
+            day.index,
+            day.day_ts,
+            day.fees_claimed,
+            day.distributable,
+            day.investor_payouts,
+            day.creator_payout,
+            day.carry,
+            day.locked_total,
+            day.investors_paid
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_client::solana_sdk::signer::Signer;
+use anyhow::Result;
+use tollgate_client::{pda::get_policy_pda, InitializeBuilder};
+
+use crate::{config::VaultConfig, Context};
+
+pub fn init(ctx: &Context, config: &VaultConfig) -> Result<()> {
+    let payer = ctx.payer()?;
+    let vault = config.vault()?;
+    let position_nft_mint = config.position_nft_mint()?;
+
+    let instruction = InitializeBuilder::new(
+        vault.pubkey(),
+        config.pool()?,
+        config.pool_config()?,
+        position_nft_mint.pubkey(),
+        config.quote_mint()?,
+        payer.pubkey(),
+    )
+    .params(config.initialize_params()?)
+    .instruction();
+
+    let signature = ctx.send(&payer, &[instruction], &[&vault, &position_nft_mint])?;
+
+    println!("Initialized vault {}", vault.pubkey());
+    println!("Policy:    {}", get_policy_pda(vault.pubkey()).0);
+    println!("Investors: {}", config.streams.len());
+    println!("Signature: {}", signature);
+    Ok(())
+}
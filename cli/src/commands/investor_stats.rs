@@ -0,0 +1,30 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use tollgate_client::pda::get_investor_stats_pda;
+
+use crate::{rpc::fetch_investor_stats, Context};
+
+pub fn investor_stats(ctx: &Context, vault: &Pubkey, stream: &Pubkey) -> Result<()> {
+    let stats = fetch_investor_stats(&ctx.rpc, vault, stream)?;
+
+    println!(
+        "Investor stats: {}",
+        get_investor_stats_pda(*vault, *stream).0
+    );
+    println!("Stream:         {}", stats.stream);
+    println!("Recipient:      {}", stats.recipient);
+    println!("Total paid:     {}", stats.total_paid);
+    match stats.last_paid_day {
+        Some(day) => println!("Last paid day:  {}", day),
+        None => println!("Last paid day:  - (never paid)"),
+    }
+    match stats.last_seen_day {
+        Some(day) => println!("Last seen day:  {}", day),
+        None => println!("Last seen day:  - (never cranked)"),
+    }
+    println!("Skipped days:   {}", stats.skipped_days);
+    println!("Dust:           {}", stats.dust);
+    println!("Skipped:        {}", stats.skipped);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+mod crank;
+mod creator;
+mod history;
+mod init;
+mod investor_stats;
+mod payout_destination;
+mod plan_pages;
+mod positions;
+mod rewards;
+mod status;
+
+pub use crank::*;
+pub use creator::*;
+pub use history::*;
+pub use init::*;
+pub use investor_stats::*;
+pub use payout_destination::*;
+pub use plan_pages::*;
+pub use positions::*;
+pub use rewards::*;
+pub use status::*;
@@ -0,0 +1,28 @@
+use anchor_client::solana_sdk::{pubkey::Pubkey, signer::Signer};
+use anyhow::Result;
+use tollgate_client::{pda::get_payout_redirect_pda, SetPayoutDestinationBuilder};
+
+use crate::Context;
+
+/// Redirects the payouts of a stream, signed by its recipient as the payer.
+pub fn set_payout_destination(
+    ctx: &Context,
+    vault: &Pubkey,
+    stream: &Pubkey,
+    destination: &Pubkey,
+) -> Result<()> {
+    let recipient = ctx.payer()?;
+    let instruction =
+        SetPayoutDestinationBuilder::new(*vault, *stream, *destination, recipient.pubkey())
+            .instruction();
+
+    let signature = ctx.send(&recipient, &[instruction], &[])?;
+
+    println!("Redirected payouts of stream {} to {}", stream, destination);
+    println!(
+        "Payout redirect: {}",
+        get_payout_redirect_pda(*vault, *stream).0
+    );
+    println!("Signature:       {}", signature);
+    Ok(())
+}
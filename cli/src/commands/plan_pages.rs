@@ -0,0 +1,75 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use tollgate::state::{CrankPhase, Progress};
+use tollgate_client::{plan_pages as plan, resume_cursor};
+
+use crate::{
+    config::VaultConfig,
+    rpc::{fetch_clock, fetch_policy, fetch_progress},
+    Context,
+};
+
+pub fn plan_pages(ctx: &Context, config: &VaultConfig, page_size: u32) -> Result<()> {
+    let vault = config.vault_pubkey()?;
+    let streams = config.streams()?;
+    let policy = fetch_policy(&ctx.rpc, &vault)?;
+    let progress = fetch_progress(&ctx.rpc, &vault)?;
+    let now_ts = fetch_clock(&ctx.rpc)?.unix_timestamp;
+
+    for line in plan_lines(
+        &progress,
+        policy.investor_count,
+        &streams,
+        now_ts,
+        page_size,
+    ) {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+/// Returns the `plan-pages` output for the progress of a vault at `now_ts`.
+pub fn plan_lines(
+    progress: &Progress,
+    investor_count: u32,
+    streams: &[Pubkey],
+    now_ts: i64,
+    page_size: u32,
+) -> Vec<String> {
+    let Some(cursor) = resume_cursor(progress, now_ts) else {
+        return vec!["Day is closed, nothing to crank".to_string()];
+    };
+
+    // A new day restarts with the snapshot pass, which is followed by the
+    // payout pass over the same pages
+    let phase = if progress.is_new_day(now_ts) && cursor == 0 {
+        CrankPhase::Snapshot
+    } else {
+        progress.phase.clone()
+    };
+    let pages = plan(investor_count, cursor, page_size);
+    let mut lines = vec![format!(
+        "{} page(s) of the {:?} pass from cursor {} of {} investors",
+        pages.len(),
+        phase,
+        cursor,
+        investor_count
+    )];
+    if phase == CrankPhase::Snapshot || phase == CrankPhase::Cap {
+        lines.push("The next pass repeats the pages from cursor 0".to_string());
+    }
+    for (idx, page) in pages.iter().enumerate() {
+        lines.push(format!(
+            "Page {}: cursor={} investors={}",
+            idx,
+            page.cursor,
+            page.len()
+        ));
+        for stream in streams.get(page.range()).unwrap_or_default() {
+            lines.push(format!("  {}", stream));
+        }
+    }
+
+    lines
+}
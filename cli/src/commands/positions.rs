@@ -0,0 +1,104 @@
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use anyhow::Result;
+use tollgate_client::{
+    pda::{get_position_pda, get_position_registry_pda},
+    AddLockedLiquidityBuilder, AddPositionBuilder, InitializePositionRegistryBuilder,
+};
+
+use crate::{
+    config::VaultConfig,
+    rpc::{fetch_mint_program, fetch_pool_base_mint, fetch_position_registry},
+    Context,
+};
+
+pub fn init_position_registry(ctx: &Context, config: &VaultConfig) -> Result<()> {
+    let payer = ctx.payer()?;
+    let vault = config.vault_pubkey()?;
+    let instruction = InitializePositionRegistryBuilder::new(
+        vault,
+        config.pool()?,
+        config.position_nft_mint_pubkey()?,
+        payer.pubkey(),
+    )
+    .instruction();
+
+    let signature = ctx.send(&payer, &[instruction], &[])?;
+
+    println!("Initialized position registry of vault {}", vault);
+    println!("Position registry: {}", get_position_registry_pda(vault).0);
+    println!("Signature:         {}", signature);
+    Ok(())
+}
+
+pub fn add_position(
+    ctx: &Context,
+    config: &VaultConfig,
+    pool: &Pubkey,
+    pool_config: &Pubkey,
+) -> Result<()> {
+    let payer = ctx.payer()?;
+    let vault = config.vault()?;
+    let position_nft_mint = Keypair::new();
+
+    let instruction = AddPositionBuilder::new(
+        vault.pubkey(),
+        *pool,
+        *pool_config,
+        position_nft_mint.pubkey(),
+        payer.pubkey(),
+    )
+    .instruction();
+
+    let signature = ctx.send(&payer, &[instruction], &[&vault, &position_nft_mint])?;
+    let registry = fetch_position_registry(&ctx.rpc, &vault.pubkey())?;
+
+    println!("Added position on pool {}", pool);
+    println!(
+        "Position:          {}",
+        get_position_pda(position_nft_mint.pubkey()).0
+    );
+    println!("Position NFT mint: {}", position_nft_mint.pubkey());
+    println!("Positions:         {}", registry.position_count);
+    println!("Signature:         {}", signature);
+    Ok(())
+}
+
+pub fn add_locked_liquidity(
+    ctx: &Context,
+    config: &VaultConfig,
+    liquidity_delta: u128,
+    max_base_amount: u64,
+    max_quote_amount: u64,
+) -> Result<()> {
+    let payer = ctx.payer()?;
+    let vault = config.vault_pubkey()?;
+    let pool = config.pool()?;
+    let quote_mint = config.quote_mint()?;
+    let base_mint = fetch_pool_base_mint(&ctx.rpc, &pool, &quote_mint)?;
+
+    let instruction = AddLockedLiquidityBuilder::new(
+        vault,
+        pool,
+        config.position_nft_mint_pubkey()?,
+        base_mint,
+        quote_mint,
+        payer.pubkey(),
+    )
+    .base_program(fetch_mint_program(&ctx.rpc, &base_mint)?)
+    .quote_program(fetch_mint_program(&ctx.rpc, &quote_mint)?)
+    .liquidity(liquidity_delta, max_base_amount, max_quote_amount)
+    .instruction();
+
+    let signature = ctx.send(&payer, &[instruction], &[])?;
+
+    println!(
+        "Locked liquidity {} in the primary position",
+        liquidity_delta
+    );
+    println!(
+        "Position:          {}",
+        get_position_pda(config.position_nft_mint_pubkey()?).0
+    );
+    println!("Signature:         {}", signature);
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anchor_client::solana_sdk::{compute_budget::ComputeBudgetInstruction, signer::Signer};
+use anyhow::{ensure, Result};
+use tollgate_client::{plan_pages, RewardCrankBuilder};
+
+use crate::{
+    config::VaultConfig,
+    rpc::{
+        fetch_investors, fetch_mint_program, fetch_policy, fetch_pool_reward_mint, fetch_progress,
+        fetch_reward_progress,
+    },
+    Context,
+};
+
+pub fn crank_reward(
+    ctx: &Context,
+    config: &VaultConfig,
+    reward_index: u8,
+    page_size: u32,
+    compute_unit_limit: u32,
+) -> Result<()> {
+    let payer = ctx.payer()?;
+    let vault = config.vault_pubkey()?;
+    let pool = config.pool()?;
+    let streams = config.streams()?;
+
+    let policy = fetch_policy(&ctx.rpc, &vault)?;
+    let progress = fetch_progress(&ctx.rpc, &vault)?;
+    ensure!(
+        streams.len() >= policy.investor_count as usize,
+        "Config lists {} streams but the policy expects {} investors",
+        streams.len(),
+        policy.investor_count
+    );
+
+    let reward_mint = fetch_pool_reward_mint(&ctx.rpc, &pool, reward_index)?;
+    let builder = RewardCrankBuilder::new(
+        vault,
+        pool,
+        config.position_nft_mint_pubkey()?,
+        reward_index,
+        reward_mint,
+        policy.creator,
+        payer.pubkey(),
+    )
+    .reward_program(fetch_mint_program(&ctx.rpc, &reward_mint)?);
+
+    // The round of the day is opened by claiming the reward once the
+    // snapshot of the day is completed
+    let claimed = fetch_reward_progress(&ctx.rpc, &vault, reward_index)
+        .is_ok_and(|reward_progress| reward_progress.day_ts == progress.last_distribution_ts);
+    if !claimed {
+        let signature = ctx.send(&payer, &[builder.claim_reward()], &[])?;
+        println!("Claimed reward {}: {}", reward_index, signature);
+    }
+
+    let reward_progress = fetch_reward_progress(&ctx.rpc, &vault, reward_index)?;
+    if reward_progress.closed {
+        println!("Reward round is closed, nothing to crank");
+        return Ok(());
+    }
+
+    let investors = fetch_investors(&ctx.rpc, &vault, &streams[..policy.investor_count as usize])?;
+    for page in plan_pages(policy.investor_count, reward_progress.cursor, page_size) {
+        let instruction = builder
+            .clone()
+            .cursor(page.cursor)
+            .investors(investors[page.range()].to_vec())
+            .crank_reward();
+        let signature = ctx.send(
+            &payer,
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit),
+                instruction,
+            ],
+            &[],
+        )?;
+        println!(
+            "Cranked reward page {} to {}: {}",
+            page.cursor, page.end, signature
+        );
+    }
+
+    let reward_progress = fetch_reward_progress(&ctx.rpc, &vault, reward_index)?;
+    println!(
+        "Reward round {}: pool {}, investor payouts {}",
+        if reward_progress.closed {
+            "closed"
+        } else {
+            "open"
+        },
+        reward_progress.reward_pool,
+        reward_progress.investor_payouts
+    );
+    Ok(())
+}
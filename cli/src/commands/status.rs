@@ -0,0 +1,86 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use tollgate::state::DayState;
+use tollgate_client::{
+    next_day_ts,
+    pda::{get_day_history_pda, get_owner_pda, get_policy_pda, get_progress_pda},
+    resume_cursor,
+};
+
+use crate::{
+    rpc::{fetch_clock, fetch_policy, fetch_progress},
+    Context,
+};
+
+pub fn status(ctx: &Context, vault: &Pubkey) -> Result<()> {
+    let policy = fetch_policy(&ctx.rpc, vault)?;
+    let progress = fetch_progress(&ctx.rpc, vault)?;
+    let now_ts = fetch_clock(&ctx.rpc)?.unix_timestamp;
+
+    println!("Vault:                  {}", vault);
+    println!("Policy:                 {}", get_policy_pda(*vault).0);
+    println!("Progress:               {}", get_progress_pda(*vault).0);
+    println!("Day history:            {}", get_day_history_pda(*vault).0);
+    println!("Owner:                  {}", get_owner_pda(*vault).0);
+    println!(
+        "Version:                {}/{}",
+        policy.version, progress.version
+    );
+    println!("Creator:                {}", policy.creator);
+    println!("Quote mint:             {}", policy.quote_mint);
+    println!("Investors:              {}", policy.investor_count);
+    println!("Investor fee share bps: {}", policy.investor_fee_share_bps);
+    println!("Min payout:             {}", policy.min_payout_lamports);
+    println!("Daily cap:              {:?}", policy.daily_cap);
+    println!("Carry policy:           {:?}", policy.carry_policy);
+    println!("Investor cap:           {:?}", policy.investor_cap);
+    println!("Y0:                     {}", policy.y0);
+    println!();
+    println!("Day state:              {:?}", progress.day_state);
+    println!(
+        "Cursor:                 {}/{}",
+        progress.cursor, policy.investor_count
+    );
+    println!("Phase:                  {:?}", progress.phase);
+    if let Some(investor_cap) = progress.investor_cap {
+        println!(
+            "Day investor cap:       {} ({} capped over {} passes)",
+            investor_cap, progress.capped_count, progress.cap_passes
+        );
+    }
+    println!("Distributable:          {}", progress.distributable);
+    println!("Locked total:           {}", progress.locked_total);
+    println!("Investor pool:          {}", progress.investor_pool);
+    println!(
+        "Allocated:              {} ({} locked)",
+        progress.allocated, progress.allocated_locked
+    );
+    println!("Daily spent:            {}", progress.daily_spent);
+    println!("Carry:                  {}", progress.carry);
+    println!(
+        "Reserve:                {} ({} released per day)",
+        progress.reserve, progress.reserve_release
+    );
+    println!("Last distribution:      {}", progress.last_distribution_ts);
+
+    let next_day = next_day_ts(&progress);
+    if progress.is_new_day(now_ts) {
+        println!("Next day:               {} (can start now)", next_day);
+    } else {
+        println!(
+            "Next day:               {} (in {}s)",
+            next_day,
+            next_day - now_ts
+        );
+    }
+
+    match resume_cursor(&progress, now_ts) {
+        Some(cursor) => println!("Next crank cursor:      {}", cursor),
+        None if progress.day_state == DayState::Closed => {
+            println!("Next crank cursor:      - (day closed)")
+        }
+        None => println!("Next crank cursor:      -"),
+    }
+
+    Ok(())
+}
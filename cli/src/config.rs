@@ -0,0 +1,176 @@
+use std::{fs, path::Path, str::FromStr};
+
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+};
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use tollgate::{
+    instructions::InitializeParams,
+    state::{CapExcess, CapLimit, CarryPolicy, InvestorCap},
+};
+
+/// Vault configuration read from a TOML policy file.
+///
+/// ```toml
+/// vault_keypair = "./vault.json"
+/// position_nft_mint_keypair = "./position-nft-mint.json"
+/// pool = "<pool pubkey>"
+/// pool_config = "<pool config pubkey>"
+/// quote_mint = "<quote mint pubkey>"
+/// streams = ["<stream pubkey>", "..."]
+///
+/// [policy]
+/// init_investor_ata = true
+/// investor_fee_share_bps = 7000
+/// min_payout_lamports = 1000
+/// daily_cap = 10000000
+/// y0 = 100000
+/// creator = "<creator pubkey>" # optional, defaults to the pool creator
+/// carry_policy = "reserve"      # optional: "sweep" (default), "roll-over" or "reserve"
+/// release_days = 7              # days the reserve is released over
+/// investor_cap_bps = 2000       # optional, or investor_cap for an absolute amount
+/// cap_excess = "redistribute"   # "redistribute" (default) or "carry"
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct VaultConfig {
+    pub vault_keypair: String,
+    pub position_nft_mint_keypair: String,
+    pub pool: String,
+    pub pool_config: String,
+    pub quote_mint: String,
+    pub streams: Vec<String>,
+    pub policy: PolicyConfig,
+}
+
+/// Policy parameters of the vault.
+#[derive(Debug, Deserialize)]
+pub struct PolicyConfig {
+    pub init_investor_ata: bool,
+    pub investor_fee_share_bps: u16,
+    pub min_payout_lamports: u64,
+    pub daily_cap: Option<u64>,
+    pub y0: u64,
+    pub creator: Option<String>,
+    pub carry_policy: Option<String>,
+    pub release_days: Option<u16>,
+    pub investor_cap: Option<u64>,
+    pub investor_cap_bps: Option<u16>,
+    pub cap_excess: Option<String>,
+}
+
+impl PolicyConfig {
+    /// Returns the carry policy, sweeping the remainder to the creator by default.
+    pub fn carry_policy(&self) -> Result<CarryPolicy> {
+        match self.carry_policy.as_deref() {
+            None | Some("sweep") => Ok(CarryPolicy::SweepToCreator),
+            Some("roll-over") => Ok(CarryPolicy::RollOver),
+            Some("reserve") => Ok(CarryPolicy::Reserve {
+                release_days: self
+                    .release_days
+                    .ok_or_else(|| anyhow!("The reserve carry policy requires release_days"))?,
+            }),
+            Some(other) => Err(anyhow!("Invalid carry policy {}", other)),
+        }
+    }
+
+    /// Returns the per-investor cap, redistributing the excess by default.
+    pub fn investor_cap(&self) -> Result<Option<InvestorCap>> {
+        let limit = match (self.investor_cap, self.investor_cap_bps) {
+            (None, None) => return Ok(None),
+            (Some(amount), None) => CapLimit::Absolute { amount },
+            (None, Some(bps)) => CapLimit::Bps { bps },
+            (Some(_), Some(_)) => {
+                return Err(anyhow!(
+                    "Set either investor_cap or investor_cap_bps, not both"
+                ))
+            }
+        };
+        let excess = match self.cap_excess.as_deref() {
+            None | Some("redistribute") => CapExcess::Redistribute,
+            Some("carry") => CapExcess::Carry,
+            Some(other) => return Err(anyhow!("Invalid cap excess {}", other)),
+        };
+        Ok(Some(InvestorCap { limit, excess }))
+    }
+}
+
+impl VaultConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        content
+            .parse()
+            .with_context(|| format!("Invalid config {}", path.display()))
+    }
+
+    pub fn vault(&self) -> Result<Keypair> {
+        read_keypair(&self.vault_keypair)
+    }
+
+    pub fn position_nft_mint(&self) -> Result<Keypair> {
+        read_keypair(&self.position_nft_mint_keypair)
+    }
+
+    pub fn vault_pubkey(&self) -> Result<Pubkey> {
+        Ok(self.vault()?.pubkey())
+    }
+
+    pub fn position_nft_mint_pubkey(&self) -> Result<Pubkey> {
+        Ok(self.position_nft_mint()?.pubkey())
+    }
+
+    pub fn pool(&self) -> Result<Pubkey> {
+        parse_pubkey(&self.pool)
+    }
+
+    pub fn pool_config(&self) -> Result<Pubkey> {
+        parse_pubkey(&self.pool_config)
+    }
+
+    pub fn quote_mint(&self) -> Result<Pubkey> {
+        parse_pubkey(&self.quote_mint)
+    }
+
+    pub fn streams(&self) -> Result<Vec<Pubkey>> {
+        self.streams.iter().map(|s| parse_pubkey(s)).collect()
+    }
+
+    /// Returns the `initialize` parameters, counting one investor per stream.
+    pub fn initialize_params(&self) -> Result<InitializeParams> {
+        Ok(InitializeParams {
+            investor_count: self.streams.len() as u32,
+            init_investor_ata: self.policy.init_investor_ata,
+            investor_fee_share_bps: self.policy.investor_fee_share_bps,
+            min_payout_lamports: self.policy.min_payout_lamports,
+            daily_cap: self.policy.daily_cap,
+            y0: self.policy.y0,
+            creator: self
+                .policy
+                .creator
+                .as_deref()
+                .map(parse_pubkey)
+                .transpose()?,
+            carry_policy: self.policy.carry_policy()?,
+            investor_cap: self.policy.investor_cap()?,
+        })
+    }
+}
+
+impl FromStr for VaultConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+}
+
+pub fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|e| anyhow!("Invalid pubkey {}: {}", value, e))
+}
+
+pub fn read_keypair(path: &str) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|e| anyhow!("Failed to read keypair {}: {}", path, e))
+}
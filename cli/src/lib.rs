@@ -0,0 +1,60 @@
+//! Operator CLI of the Tollgate program.
+//!
+//! The binary only parses the [`args::Cli`] arguments and dispatches them to
+//! [`commands`], so the argument and config parsing as well as the command
+//! outputs can be exercised without an RPC node.
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        instruction::Instruction, signature::Keypair, signature::Signature, signer::Signer,
+        transaction::Transaction,
+    },
+};
+use anyhow::Result;
+
+pub mod args;
+pub mod commands;
+pub mod config;
+pub mod rpc;
+
+use config::read_keypair;
+
+/// Shared RPC connection and payer of the CLI commands.
+pub struct Context {
+    pub rpc: RpcClient,
+    pub keypair: String,
+}
+
+impl Context {
+    /// Reads the payer keypair, only needed by commands sending transactions.
+    pub fn payer(&self) -> Result<Keypair> {
+        read_keypair(&expand_home(&self.keypair))
+    }
+
+    /// Signs with the payer and the extra signers, then sends and confirms.
+    pub fn send(
+        &self,
+        payer: &Keypair,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Signature> {
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.rpc.get_latest_blockhash()?,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
@@ -0,0 +1,107 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient, solana_sdk::commitment_config::CommitmentConfig, Cluster,
+};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use tollgate_cli::{
+    args::{Cli, Command},
+    commands,
+    config::{self, VaultConfig},
+    Context,
+};
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let cluster = Cluster::from_str(&cli.url).map_err(|e| anyhow!("Invalid url: {}", e))?;
+    let ctx = Context {
+        rpc: RpcClient::new_with_commitment(
+            cluster.url().to_string(),
+            CommitmentConfig::confirmed(),
+        ),
+        keypair: cli.keypair,
+    };
+
+    match cli.command {
+        Command::Init { config } => commands::init(&ctx, &VaultConfig::load(&config)?),
+        Command::Crank {
+            config,
+            page_size,
+            with_init,
+            compute_unit_limit,
+        } => commands::crank(
+            &ctx,
+            &VaultConfig::load(&config)?,
+            page_size,
+            with_init,
+            compute_unit_limit,
+        ),
+        Command::Status { vault } => commands::status(&ctx, &config::parse_pubkey(&vault)?),
+        Command::InitDayHistory { vault } => {
+            commands::init_day_history(&ctx, &config::parse_pubkey(&vault)?)
+        }
+        Command::InitPositionRegistry { config } => {
+            commands::init_position_registry(&ctx, &VaultConfig::load(&config)?)
+        }
+        Command::AddPosition {
+            config,
+            pool,
+            pool_config,
+        } => commands::add_position(
+            &ctx,
+            &VaultConfig::load(&config)?,
+            &config::parse_pubkey(&pool)?,
+            &config::parse_pubkey(&pool_config)?,
+        ),
+        Command::AddLiquidity {
+            config,
+            liquidity,
+            max_base,
+            max_quote,
+        } => commands::add_locked_liquidity(
+            &ctx,
+            &VaultConfig::load(&config)?,
+            liquidity,
+            max_base,
+            max_quote,
+        ),
+        Command::CrankReward {
+            config,
+            reward_index,
+            page_size,
+            compute_unit_limit,
+        } => commands::crank_reward(
+            &ctx,
+            &VaultConfig::load(&config)?,
+            reward_index,
+            page_size,
+            compute_unit_limit,
+        ),
+        Command::History { vault } => commands::history(&ctx, &config::parse_pubkey(&vault)?),
+        Command::InvestorStats { vault, stream } => commands::investor_stats(
+            &ctx,
+            &config::parse_pubkey(&vault)?,
+            &config::parse_pubkey(&stream)?,
+        ),
+        Command::SetCreator { config, creator } => commands::set_creator(
+            &ctx,
+            &VaultConfig::load(&config)?,
+            &config::parse_pubkey(&creator)?,
+        ),
+        Command::SetPayoutDestination {
+            vault,
+            stream,
+            destination,
+        } => commands::set_payout_destination(
+            &ctx,
+            &config::parse_pubkey(&vault)?,
+            &config::parse_pubkey(&stream)?,
+            &config::parse_pubkey(&destination)?,
+        ),
+        Command::PlanPages { config, page_size } => {
+            commands::plan_pages(&ctx, &VaultConfig::load(&config)?, page_size)
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{account::from_account, clock::Clock, pubkey::Pubkey, sysvar},
+};
+use anyhow::{anyhow, Context, Result};
+use tollgate::state::{
+    DayHistory, InvestorStats, Policy, PositionRegistry, Progress, RewardProgress,
+};
+use tollgate_client::{
+    decode_day_history, decode_investor_stats, decode_payout_redirect, decode_policy,
+    decode_position_registry, decode_progress, decode_reward_progress,
+    pda::{
+        get_day_history_pda, get_investor_stats_pda, get_payout_redirect_pda, get_policy_pda,
+        get_position_registry_pda, get_progress_pda, get_reward_progress_pda,
+    },
+    sort_investors, ExtraPosition, InvestorStream,
+};
+
+pub fn fetch_policy(rpc: &RpcClient, vault: &Pubkey) -> Result<Policy> {
+    let address = get_policy_pda(*vault).0;
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("Policy account {} not found", address))?;
+    decode_policy(&account.data).map_err(|e| anyhow!("Failed to decode policy: {}", e))
+}
+
+pub fn fetch_progress(rpc: &RpcClient, vault: &Pubkey) -> Result<Progress> {
+    let address = get_progress_pda(*vault).0;
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("Progress account {} not found", address))?;
+    decode_progress(&account.data).map_err(|e| anyhow!("Failed to decode progress: {}", e))
+}
+
+pub fn fetch_reward_progress(
+    rpc: &RpcClient,
+    vault: &Pubkey,
+    reward_index: u8,
+) -> Result<RewardProgress> {
+    let address = get_reward_progress_pda(*vault, reward_index).0;
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("Reward progress account {} not found", address))?;
+    decode_reward_progress(&account.data)
+        .map_err(|e| anyhow!("Failed to decode reward progress: {}", e))
+}
+
+pub fn fetch_day_history(rpc: &RpcClient, vault: &Pubkey) -> Result<DayHistory> {
+    let address = get_day_history_pda(*vault).0;
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("Day history account {} not found", address))?;
+    decode_day_history(&account.data).map_err(|e| anyhow!("Failed to decode day history: {}", e))
+}
+
+pub fn fetch_position_registry(rpc: &RpcClient, vault: &Pubkey) -> Result<PositionRegistry> {
+    let address = get_position_registry_pda(*vault).0;
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("Position registry account {} not found", address))?;
+    decode_position_registry(&account.data)
+        .map_err(|e| anyhow!("Failed to decode position registry: {}", e))
+}
+
+/// Returns the positions registered after the primary one, in registry order,
+/// or none when the vault has no position registry.
+pub fn fetch_extra_positions(rpc: &RpcClient, vault: &Pubkey) -> Result<Vec<ExtraPosition>> {
+    let Ok(registry) = fetch_position_registry(rpc, vault) else {
+        return Ok(vec![]);
+    };
+    registry.positions()[1..]
+        .iter()
+        .map(|entry| {
+            let account = rpc
+                .get_account(&entry.position)
+                .with_context(|| format!("Position account {} not found", entry.position))?;
+            let position: damm_v2::accounts::Position = bytemuck::try_pod_read_unaligned(
+                account
+                    .data
+                    .get(8..)
+                    .ok_or_else(|| anyhow!("Position account data too short"))?,
+            )
+            .map_err(|e| anyhow!("Failed to decode position {}: {}", entry.position, e))?;
+            Ok(ExtraPosition {
+                pool: entry.pool,
+                position_nft_mint: position.nft_mint,
+            })
+        })
+        .collect()
+}
+
+pub fn fetch_investor_stats(
+    rpc: &RpcClient,
+    vault: &Pubkey,
+    stream: &Pubkey,
+) -> Result<InvestorStats> {
+    let address = get_investor_stats_pda(*vault, *stream).0;
+    let account = rpc
+        .get_account(&address)
+        .with_context(|| format!("Investor stats account {} not found", address))?;
+    decode_investor_stats(&account.data)
+        .map_err(|e| anyhow!("Failed to decode investor stats: {}", e))
+}
+
+pub fn fetch_clock(rpc: &RpcClient) -> Result<Clock> {
+    let account = rpc
+        .get_account(&sysvar::clock::ID)
+        .context("Clock sysvar not found")?;
+    from_account(&account).ok_or_else(|| anyhow!("Failed to decode clock sysvar"))
+}
+
+/// Returns the owning token program of a mint.
+pub fn fetch_mint_program(rpc: &RpcClient, mint: &Pubkey) -> Result<Pubkey> {
+    let account = rpc
+        .get_account(mint)
+        .with_context(|| format!("Mint account {} not found", mint))?;
+    Ok(account.owner)
+}
+
+/// Returns the base mint of the pool, given its quote mint.
+pub fn fetch_pool_base_mint(rpc: &RpcClient, pool: &Pubkey, quote_mint: &Pubkey) -> Result<Pubkey> {
+    let account = rpc
+        .get_account(pool)
+        .with_context(|| format!("Pool account {} not found", pool))?;
+    let pool: damm_v2::accounts::Pool = bytemuck::try_pod_read_unaligned(
+        account
+            .data
+            .get(8..)
+            .ok_or_else(|| anyhow!("Pool account data too short"))?,
+    )
+    .map_err(|e| anyhow!("Failed to decode pool: {}", e))?;
+
+    if pool.token_a_mint == *quote_mint {
+        Ok(pool.token_b_mint)
+    } else {
+        Ok(pool.token_a_mint)
+    }
+}
+
+/// Returns the mint of an initialized reward of the pool.
+pub fn fetch_pool_reward_mint(rpc: &RpcClient, pool: &Pubkey, reward_index: u8) -> Result<Pubkey> {
+    let account = rpc
+        .get_account(pool)
+        .with_context(|| format!("Pool account {} not found", pool))?;
+    let pool: damm_v2::accounts::Pool = bytemuck::try_pod_read_unaligned(
+        account
+            .data
+            .get(8..)
+            .ok_or_else(|| anyhow!("Pool account data too short"))?,
+    )
+    .map_err(|e| anyhow!("Failed to decode pool: {}", e))?;
+
+    let reward_info = pool
+        .reward_infos
+        .get(reward_index as usize)
+        .filter(|reward_info| reward_info.initialized != 0)
+        .ok_or_else(|| anyhow!("Reward {} is not initialized on the pool", reward_index))?;
+    Ok(reward_info.mint)
+}
+
+/// Fetches the stream accounts and their payout redirects, and resolves the
+/// recipients and payout accounts, in crank order.
+pub fn fetch_investors(
+    rpc: &RpcClient,
+    vault: &Pubkey,
+    streams: &[Pubkey],
+) -> Result<Vec<InvestorStream>> {
+    let mut investors = Vec::with_capacity(streams.len());
+    for chunk in streams.chunks(50) {
+        let redirects: Vec<Pubkey> = chunk
+            .iter()
+            .map(|stream| get_payout_redirect_pda(*vault, *stream).0)
+            .collect();
+        let accounts = rpc.get_multiple_accounts(chunk)?;
+        let redirect_accounts = rpc.get_multiple_accounts(&redirects)?;
+        for ((stream, account), redirect) in chunk.iter().zip(accounts).zip(redirect_accounts) {
+            // A closed stream keeps its slot in the pages with no weight
+            let Some(account) = account.filter(|account| !account.data.is_empty()) else {
+                investors.push(InvestorStream::closed(*stream));
+                continue;
+            };
+            let redirect = redirect
+                .map(|redirect| decode_payout_redirect(&redirect.data))
+                .transpose()
+                .map_err(|e| anyhow!("Failed to decode payout redirect of {}: {}", stream, e))?;
+            let investor = InvestorStream::from_stream_account(*stream, &account.data)
+                .map_err(|e| anyhow!("Failed to decode stream {}: {}", stream, e))?
+                .with_redirect(redirect.as_ref());
+            investors.push(investor);
+        }
+    }
+    sort_investors(&mut investors).map_err(|e| anyhow!("Invalid streams: {}", e))?;
+    Ok(investors)
+}
@@ -0,0 +1,56 @@
+use anchor_lang::{
+    error::ErrorCode, prelude::error, AccountDeserialize, AnchorDeserialize, Discriminator, Result,
+};
+use tollgate::{
+    instructions::CrankPreview,
+    state::{
+        DayHistory, InvestorStats, PayoutRedirect, Policy, PositionRegistry, Progress,
+        RewardProgress,
+    },
+};
+
+/// Decodes a Policy account from its raw data.
+pub fn decode_policy(data: &[u8]) -> Result<Policy> {
+    Policy::try_deserialize(&mut &data[..])
+}
+
+/// Decodes a Progress account from its raw data.
+pub fn decode_progress(data: &[u8]) -> Result<Progress> {
+    Progress::try_deserialize(&mut &data[..])
+}
+
+/// Decodes an InvestorStats account from its raw data.
+pub fn decode_investor_stats(data: &[u8]) -> Result<InvestorStats> {
+    InvestorStats::try_deserialize(&mut &data[..])
+}
+
+/// Decodes a PayoutRedirect account from its raw data.
+pub fn decode_payout_redirect(data: &[u8]) -> Result<PayoutRedirect> {
+    PayoutRedirect::try_deserialize(&mut &data[..])
+}
+
+/// Decodes a PositionRegistry account from its raw data.
+pub fn decode_position_registry(data: &[u8]) -> Result<PositionRegistry> {
+    PositionRegistry::try_deserialize(&mut &data[..])
+}
+
+/// Decodes a RewardProgress account from its raw data.
+pub fn decode_reward_progress(data: &[u8]) -> Result<RewardProgress> {
+    RewardProgress::try_deserialize(&mut &data[..])
+}
+
+/// Decodes a DayHistory account from its raw data.
+pub fn decode_day_history(data: &[u8]) -> Result<DayHistory> {
+    let discriminator = DayHistory::DISCRIMINATOR;
+    if data.len() < DayHistory::SPACE || &data[..discriminator.len()] != discriminator {
+        return Err(error!(ErrorCode::AccountDiscriminatorMismatch));
+    }
+    Ok(*bytemuck::from_bytes(
+        &data[discriminator.len()..DayHistory::SPACE],
+    ))
+}
+
+/// Decodes the `preview_crank` return data of a simulated transaction.
+pub fn decode_crank_preview(return_data: &[u8]) -> Result<CrankPreview> {
+    Ok(CrankPreview::try_from_slice(return_data)?)
+}
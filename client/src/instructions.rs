@@ -0,0 +1,889 @@
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::instruction::Instruction,
+    system_program, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address_with_program_id},
+    token, token_2022,
+};
+use tollgate::{
+    accounts::{
+        AccountAddLockedLiquidity, AccountAddPosition, AccountClaimFees, AccountClaimReward,
+        AccountCrankPage, AccountCrankReward, AccountInitialize, AccountInitializeDayHistory,
+        AccountInitializePositionRegistry, AccountPreviewCrank, AccountSetCreator,
+        AccountSetPayoutDestination,
+    },
+    constants::damm_v2_constants,
+    instructions::{AddLockedLiquidityParams, CrankParams, InitializeParams},
+};
+
+use crate::{
+    investors::{
+        get_crank_remaining_accounts, get_reward_crank_remaining_accounts, InvestorStream,
+    },
+    pda::{
+        get_day_history_pda, get_event_authority_pda, get_owner_pda, get_payout_redirect_pda,
+        get_policy_pda, get_position_nft_account_pda, get_position_pda, get_position_registry_pda,
+        get_progress_pda, get_reward_progress_pda, get_reward_vault_pda, get_token_vault_pda,
+    },
+};
+
+/// Builder for the `initialize` instruction.
+#[derive(Debug, Clone)]
+pub struct InitializeBuilder {
+    vault: Pubkey,
+    pool: Pubkey,
+    pool_cfg: Pubkey,
+    position_nft_mint: Pubkey,
+    quote_mint: Pubkey,
+    payer: Pubkey,
+    params: InitializeParams,
+}
+
+impl InitializeBuilder {
+    pub fn new(
+        vault: Pubkey,
+        pool: Pubkey,
+        pool_cfg: Pubkey,
+        position_nft_mint: Pubkey,
+        quote_mint: Pubkey,
+        payer: Pubkey,
+    ) -> Self {
+        Self {
+            vault,
+            pool,
+            pool_cfg,
+            position_nft_mint,
+            quote_mint,
+            payer,
+            params: InitializeParams::default(),
+        }
+    }
+
+    pub fn params(mut self, params: InitializeParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Returns the accounts of the `initialize` instruction.
+    pub fn accounts(&self) -> AccountInitialize {
+        AccountInitialize {
+            vault: self.vault,
+            policy: get_policy_pda(self.vault).0,
+            progress: get_progress_pda(self.vault).0,
+            day_history: get_day_history_pda(self.vault).0,
+            position_registry: get_position_registry_pda(self.vault).0,
+            pool: self.pool,
+            pool_cfg: self.pool_cfg,
+            position_nft_mint: self.position_nft_mint,
+            position_nft_account: get_position_nft_account_pda(self.position_nft_mint).0,
+            position: get_position_pda(self.position_nft_mint).0,
+            pool_authority: damm_v2_constants::pool_authority::ID,
+            owner: get_owner_pda(self.vault).0,
+            quote_mint: self.quote_mint,
+            payer: self.payer,
+            amm_event_authority: get_event_authority_pda(&damm_v2::ID).0,
+            amm_program: damm_v2::ID,
+            token_2022_program: token_2022::ID,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Builds the `initialize` instruction.
+    pub fn instruction(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            tollgate::ID,
+            &tollgate::instruction::Initialize {
+                params: self.params.clone(),
+            }
+            .data(),
+            self.accounts().to_account_metas(None),
+        )
+    }
+}
+
+/// Builder for the `initialize_day_history` instruction, for vaults created
+/// before the day history existed.
+#[derive(Debug, Clone)]
+pub struct InitializeDayHistoryBuilder {
+    vault: Pubkey,
+    payer: Pubkey,
+}
+
+impl InitializeDayHistoryBuilder {
+    pub fn new(vault: Pubkey, payer: Pubkey) -> Self {
+        Self { vault, payer }
+    }
+
+    /// Returns the accounts of the `initialize_day_history` instruction.
+    pub fn accounts(&self) -> AccountInitializeDayHistory {
+        AccountInitializeDayHistory {
+            vault: self.vault,
+            policy: get_policy_pda(self.vault).0,
+            day_history: get_day_history_pda(self.vault).0,
+            payer: self.payer,
+            system_program: system_program::ID,
+        }
+    }
+
+    /// Builds the `initialize_day_history` instruction.
+    pub fn instruction(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            tollgate::ID,
+            &tollgate::instruction::InitializeDayHistory {}.data(),
+            self.accounts().to_account_metas(None),
+        )
+    }
+}
+
+/// Builder for the `initialize_position_registry` instruction, for vaults
+/// created before the position registry existed.
+#[derive(Debug, Clone)]
+pub struct InitializePositionRegistryBuilder {
+    vault: Pubkey,
+    pool: Pubkey,
+    position_nft_mint: Pubkey,
+    payer: Pubkey,
+}
+
+impl InitializePositionRegistryBuilder {
+    pub fn new(vault: Pubkey, pool: Pubkey, position_nft_mint: Pubkey, payer: Pubkey) -> Self {
+        Self {
+            vault,
+            pool,
+            position_nft_mint,
+            payer,
+        }
+    }
+
+    /// Returns the accounts of the `initialize_position_registry` instruction.
+    pub fn accounts(&self) -> AccountInitializePositionRegistry {
+        AccountInitializePositionRegistry {
+            vault: self.vault,
+            policy: get_policy_pda(self.vault).0,
+            position_registry: get_position_registry_pda(self.vault).0,
+            pool: self.pool,
+            position_nft_account: get_position_nft_account_pda(self.position_nft_mint).0,
+            position: get_position_pda(self.position_nft_mint).0,
+            owner: get_owner_pda(self.vault).0,
+            payer: self.payer,
+            system_program: system_program::ID,
+        }
+    }
+
+    /// Builds the `initialize_position_registry` instruction.
+    pub fn instruction(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            tollgate::ID,
+            &tollgate::instruction::InitializePositionRegistry {}.data(),
+            self.accounts().to_account_metas(None),
+        )
+    }
+}
+
+/// Builder for the `add_position` instruction, signed by the vault.
+#[derive(Debug, Clone)]
+pub struct AddPositionBuilder {
+    vault: Pubkey,
+    pool: Pubkey,
+    pool_cfg: Pubkey,
+    position_nft_mint: Pubkey,
+    payer: Pubkey,
+}
+
+impl AddPositionBuilder {
+    pub fn new(
+        vault: Pubkey,
+        pool: Pubkey,
+        pool_cfg: Pubkey,
+        position_nft_mint: Pubkey,
+        payer: Pubkey,
+    ) -> Self {
+        Self {
+            vault,
+            pool,
+            pool_cfg,
+            position_nft_mint,
+            payer,
+        }
+    }
+
+    /// Returns the accounts of the `add_position` instruction.
+    pub fn accounts(&self) -> AccountAddPosition {
+        AccountAddPosition {
+            vault: self.vault,
+            policy: get_policy_pda(self.vault).0,
+            position_registry: get_position_registry_pda(self.vault).0,
+            pool: self.pool,
+            pool_cfg: self.pool_cfg,
+            position_nft_mint: self.position_nft_mint,
+            position_nft_account: get_position_nft_account_pda(self.position_nft_mint).0,
+            position: get_position_pda(self.position_nft_mint).0,
+            pool_authority: damm_v2_constants::pool_authority::ID,
+            owner: get_owner_pda(self.vault).0,
+            payer: self.payer,
+            amm_event_authority: get_event_authority_pda(&damm_v2::ID).0,
+            amm_program: damm_v2::ID,
+            token_2022_program: token_2022::ID,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Builds the `add_position` instruction.
+    pub fn instruction(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            tollgate::ID,
+            &tollgate::instruction::AddPosition {}.data(),
+            self.accounts().to_account_metas(None),
+        )
+    }
+}
+
+/// Builder for the `add_locked_liquidity` instruction, signed by the depositor.
+#[derive(Debug, Clone)]
+pub struct AddLockedLiquidityBuilder {
+    vault: Pubkey,
+    pool: Pubkey,
+    position_nft_mint: Pubkey,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    base_program: Pubkey,
+    quote_program: Pubkey,
+    depositor: Pubkey,
+    params: AddLockedLiquidityParams,
+}
+
+impl AddLockedLiquidityBuilder {
+    pub fn new(
+        vault: Pubkey,
+        pool: Pubkey,
+        position_nft_mint: Pubkey,
+        base_mint: Pubkey,
+        quote_mint: Pubkey,
+        depositor: Pubkey,
+    ) -> Self {
+        Self {
+            vault,
+            pool,
+            position_nft_mint,
+            base_mint,
+            quote_mint,
+            base_program: token::ID,
+            quote_program: token::ID,
+            depositor,
+            params: AddLockedLiquidityParams::default(),
+        }
+    }
+
+    pub fn base_program(mut self, base_program: Pubkey) -> Self {
+        self.base_program = base_program;
+        self
+    }
+
+    pub fn quote_program(mut self, quote_program: Pubkey) -> Self {
+        self.quote_program = quote_program;
+        self
+    }
+
+    /// Sets the liquidity to add, and the most base and quote the depositor
+    /// spends for it; the unused part is refunded.
+    pub fn liquidity(
+        mut self,
+        liquidity_delta: u128,
+        max_base_amount: u64,
+        max_quote_amount: u64,
+    ) -> Self {
+        self.params = AddLockedLiquidityParams {
+            liquidity_delta,
+            max_base_amount,
+            max_quote_amount,
+        };
+        self
+    }
+
+    /// Returns the accounts of the `add_locked_liquidity` instruction.
+    pub fn accounts(&self) -> AccountAddLockedLiquidity {
+        let owner = get_owner_pda(self.vault).0;
+
+        AccountAddLockedLiquidity {
+            policy: get_policy_pda(self.vault).0,
+            position_registry: get_position_registry_pda(self.vault).0,
+            pool: self.pool,
+            position_nft_account: get_position_nft_account_pda(self.position_nft_mint).0,
+            position: get_position_pda(self.position_nft_mint).0,
+            owner,
+            base_account: get_associated_token_address_with_program_id(
+                &owner,
+                &self.base_mint,
+                &self.base_program,
+            ),
+            quote_account: get_associated_token_address_with_program_id(
+                &owner,
+                &self.quote_mint,
+                &self.quote_program,
+            ),
+            depositor_base_account: get_associated_token_address_with_program_id(
+                &self.depositor,
+                &self.base_mint,
+                &self.base_program,
+            ),
+            depositor_quote_account: get_associated_token_address_with_program_id(
+                &self.depositor,
+                &self.quote_mint,
+                &self.quote_program,
+            ),
+            base_vault: get_token_vault_pda(self.base_mint, self.pool).0,
+            quote_vault: get_token_vault_pda(self.quote_mint, self.pool).0,
+            base_mint: self.base_mint,
+            quote_mint: self.quote_mint,
+            base_program: self.base_program,
+            quote_program: self.quote_program,
+            depositor: self.depositor,
+            amm_event_authority: get_event_authority_pda(&damm_v2::ID).0,
+            amm_program: damm_v2::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Builds the `add_locked_liquidity` instruction.
+    pub fn instruction(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            tollgate::ID,
+            &tollgate::instruction::AddLockedLiquidity {
+                params: self.params.clone(),
+            }
+            .data(),
+            self.accounts().to_account_metas(None),
+        )
+    }
+}
+
+/// A honorary position registered after the primary one, claimed by `claim_fees`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtraPosition {
+    pub pool: Pubkey,              // DAMM v2 pool of the position
+    pub position_nft_mint: Pubkey, // Mint of the position NFT
+}
+
+/// Builder for the `set_creator` instruction, signed by the vault.
+#[derive(Debug, Clone)]
+pub struct SetCreatorBuilder {
+    vault: Pubkey,
+    creator: Pubkey,
+}
+
+impl SetCreatorBuilder {
+    pub fn new(vault: Pubkey, creator: Pubkey) -> Self {
+        Self { vault, creator }
+    }
+
+    /// Returns the accounts of the `set_creator` instruction.
+    pub fn accounts(&self) -> AccountSetCreator {
+        AccountSetCreator {
+            vault: self.vault,
+            policy: get_policy_pda(self.vault).0,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Builds the `set_creator` instruction.
+    pub fn instruction(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            tollgate::ID,
+            &tollgate::instruction::SetCreator {
+                creator: self.creator,
+            }
+            .data(),
+            self.accounts().to_account_metas(None),
+        )
+    }
+}
+
+/// Builder for the `set_payout_destination` instruction, signed by the stream recipient.
+#[derive(Debug, Clone)]
+pub struct SetPayoutDestinationBuilder {
+    vault: Pubkey,
+    stream: Pubkey,
+    destination: Pubkey,
+    recipient: Pubkey,
+}
+
+impl SetPayoutDestinationBuilder {
+    pub fn new(vault: Pubkey, stream: Pubkey, destination: Pubkey, recipient: Pubkey) -> Self {
+        Self {
+            vault,
+            stream,
+            destination,
+            recipient,
+        }
+    }
+
+    /// Returns the accounts of the `set_payout_destination` instruction.
+    pub fn accounts(&self) -> AccountSetPayoutDestination {
+        AccountSetPayoutDestination {
+            vault: self.vault,
+            policy: get_policy_pda(self.vault).0,
+            stream: self.stream,
+            payout_redirect: get_payout_redirect_pda(self.vault, self.stream).0,
+            destination: self.destination,
+            recipient: self.recipient,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Builds the `set_payout_destination` instruction.
+    pub fn instruction(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            tollgate::ID,
+            &tollgate::instruction::SetPayoutDestination {}.data(),
+            self.accounts().to_account_metas(None),
+        )
+    }
+}
+
+/// Builder for the `claim_fees`, `crank`, `crank_with_init` and `preview_crank` instructions.
+#[derive(Debug, Clone)]
+pub struct CrankBuilder {
+    vault: Pubkey,
+    pool: Pubkey,
+    position_nft_mint: Pubkey,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    base_program: Pubkey,
+    quote_program: Pubkey,
+    creator: Pubkey,
+    payer: Pubkey,
+    cursor: u32,
+    investors: Vec<InvestorStream>,
+    positions: Vec<ExtraPosition>,
+}
+
+impl CrankBuilder {
+    pub fn new(
+        vault: Pubkey,
+        pool: Pubkey,
+        position_nft_mint: Pubkey,
+        base_mint: Pubkey,
+        quote_mint: Pubkey,
+        creator: Pubkey,
+        payer: Pubkey,
+    ) -> Self {
+        Self {
+            vault,
+            pool,
+            position_nft_mint,
+            base_mint,
+            quote_mint,
+            base_program: token::ID,
+            quote_program: token::ID,
+            creator,
+            payer,
+            cursor: 0,
+            investors: vec![],
+            positions: vec![],
+        }
+    }
+
+    pub fn base_program(mut self, base_program: Pubkey) -> Self {
+        self.base_program = base_program;
+        self
+    }
+
+    pub fn quote_program(mut self, quote_program: Pubkey) -> Self {
+        self.quote_program = quote_program;
+        self
+    }
+
+    pub fn cursor(mut self, cursor: u32) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    pub fn investors(mut self, investors: Vec<InvestorStream>) -> Self {
+        self.investors = investors;
+        self
+    }
+
+    /// Sets the positions registered after the primary one, in registry order.
+    pub fn positions(mut self, positions: Vec<ExtraPosition>) -> Self {
+        self.positions = positions;
+        self
+    }
+
+    /// Returns the accounts of the `claim_fees` instruction.
+    pub fn claim_accounts(&self) -> AccountClaimFees {
+        let owner = get_owner_pda(self.vault).0;
+
+        AccountClaimFees {
+            policy: get_policy_pda(self.vault).0,
+            progress: get_progress_pda(self.vault).0,
+            day_history: get_day_history_pda(self.vault).0,
+            position_registry: get_position_registry_pda(self.vault).0,
+            pool: self.pool,
+            position_nft_account: get_position_nft_account_pda(self.position_nft_mint).0,
+            position: get_position_pda(self.position_nft_mint).0,
+            pool_authority: damm_v2_constants::pool_authority::ID,
+            owner,
+            base_account: get_associated_token_address_with_program_id(
+                &owner,
+                &self.base_mint,
+                &self.base_program,
+            ),
+            quote_account: get_associated_token_address_with_program_id(
+                &owner,
+                &self.quote_mint,
+                &self.quote_program,
+            ),
+            base_vault: get_token_vault_pda(self.base_mint, self.pool).0,
+            quote_vault: get_token_vault_pda(self.quote_mint, self.pool).0,
+            base_mint: self.base_mint,
+            quote_mint: self.quote_mint,
+            base_program: self.base_program,
+            quote_program: self.quote_program,
+            creator: self.creator,
+            creator_account: get_associated_token_address_with_program_id(
+                &self.creator,
+                &self.quote_mint,
+                &self.quote_program,
+            ),
+            payer: self.payer,
+            amm_event_authority: get_event_authority_pda(&damm_v2::ID).0,
+            amm_program: damm_v2::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Returns the read-only accounts of the `preview_crank` instruction, the
+    /// keys of [`Self::claim_accounts`].
+    pub fn preview_accounts(&self) -> AccountPreviewCrank {
+        let owner = get_owner_pda(self.vault).0;
+
+        AccountPreviewCrank {
+            policy: get_policy_pda(self.vault).0,
+            progress: get_progress_pda(self.vault).0,
+            day_history: get_day_history_pda(self.vault).0,
+            position_registry: get_position_registry_pda(self.vault).0,
+            pool: self.pool,
+            position_nft_account: get_position_nft_account_pda(self.position_nft_mint).0,
+            position: get_position_pda(self.position_nft_mint).0,
+            pool_authority: damm_v2_constants::pool_authority::ID,
+            owner,
+            base_account: get_associated_token_address_with_program_id(
+                &owner,
+                &self.base_mint,
+                &self.base_program,
+            ),
+            quote_account: get_associated_token_address_with_program_id(
+                &owner,
+                &self.quote_mint,
+                &self.quote_program,
+            ),
+            base_vault: get_token_vault_pda(self.base_mint, self.pool).0,
+            quote_vault: get_token_vault_pda(self.quote_mint, self.pool).0,
+            base_mint: self.base_mint,
+            quote_mint: self.quote_mint,
+            base_program: self.base_program,
+            quote_program: self.quote_program,
+            creator: self.creator,
+            creator_account: get_associated_token_address_with_program_id(
+                &self.creator,
+                &self.quote_mint,
+                &self.quote_program,
+            ),
+            payer: self.payer,
+            amm_event_authority: get_event_authority_pda(&damm_v2::ID).0,
+            amm_program: damm_v2::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Returns the accounts of the `crank` and `crank_with_init` page instructions.
+    pub fn accounts(&self) -> AccountCrankPage {
+        let owner = get_owner_pda(self.vault).0;
+
+        AccountCrankPage {
+            policy: get_policy_pda(self.vault).0,
+            progress: get_progress_pda(self.vault).0,
+            day_history: get_day_history_pda(self.vault).0,
+            position_registry: get_position_registry_pda(self.vault).0,
+            owner,
+            quote_account: get_associated_token_address_with_program_id(
+                &owner,
+                &self.quote_mint,
+                &self.quote_program,
+            ),
+            quote_mint: self.quote_mint,
+            quote_program: self.quote_program,
+            creator: self.creator,
+            creator_account: get_associated_token_address_with_program_id(
+                &self.creator,
+                &self.quote_mint,
+                &self.quote_program,
+            ),
+            payer: self.payer,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Returns the remaining accounts for the configured page of investors.
+    pub fn remaining_accounts(&self, init_mode: bool) -> Vec<AccountMeta> {
+        get_crank_remaining_accounts(&self.investors, &self.vault, &self.quote_mint, init_mode)
+    }
+
+    /// Builds the `crank` instruction.
+    pub fn crank(&self) -> Instruction {
+        self.build(
+            self.accounts().to_account_metas(None),
+            tollgate::instruction::Crank {
+                params: CrankParams {
+                    cursor: self.cursor,
+                },
+            }
+            .data(),
+            false,
+        )
+    }
+
+    /// Builds the `crank_with_init` instruction.
+    pub fn crank_with_init(&self) -> Instruction {
+        self.build(
+            self.accounts().to_account_metas(None),
+            tollgate::instruction::CrankWithInit {
+                params: CrankParams {
+                    cursor: self.cursor,
+                },
+            }
+            .data(),
+            true,
+        )
+    }
+
+    /// Returns the (pool, position, position NFT account, base vault, quote vault)
+    /// groups of the configured extra positions.
+    pub fn position_accounts(&self) -> Vec<AccountMeta> {
+        self.positions
+            .iter()
+            .flat_map(|extra| {
+                [
+                    AccountMeta::new(extra.pool, false),
+                    AccountMeta::new(get_position_pda(extra.position_nft_mint).0, false),
+                    AccountMeta::new_readonly(
+                        get_position_nft_account_pda(extra.position_nft_mint).0,
+                        false,
+                    ),
+                    AccountMeta::new(get_token_vault_pda(self.base_mint, extra.pool).0, false),
+                    AccountMeta::new(get_token_vault_pda(self.quote_mint, extra.pool).0, false),
+                ]
+            })
+            .collect()
+    }
+
+    /// Builds the `claim_fees` instruction, opening the day with the fees of
+    /// the primary and the configured extra positions.
+    pub fn claim_fees(&self) -> Instruction {
+        let mut accounts = self.claim_accounts().to_account_metas(None);
+        accounts.extend(self.position_accounts());
+
+        Instruction {
+            program_id: tollgate::ID,
+            accounts,
+            data: tollgate::instruction::ClaimFees {}.data(),
+        }
+    }
+
+    /// Builds the read-only `preview_crank` instruction, meant to be simulated.
+    /// Decode its return data with [`crate::decode_crank_preview`].
+    pub fn preview_crank(&self) -> Instruction {
+        let mut instruction = self.build(
+            self.preview_accounts().to_account_metas(None),
+            tollgate::instruction::PreviewCrank {
+                params: CrankParams {
+                    cursor: self.cursor,
+                },
+            }
+            .data(),
+            false,
+        );
+        // The preview writes nothing, the investor accounts are read-only too
+        for account in &mut instruction.accounts {
+            account.is_writable = false;
+        }
+        instruction
+    }
+
+    fn build(&self, mut accounts: Vec<AccountMeta>, data: Vec<u8>, init_mode: bool) -> Instruction {
+        accounts.extend(self.remaining_accounts(init_mode));
+
+        Instruction {
+            program_id: tollgate::ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+/// Builder for the `claim_reward` and `crank_reward` instructions of a reward index.
+#[derive(Debug, Clone)]
+pub struct RewardCrankBuilder {
+    vault: Pubkey,
+    pool: Pubkey,
+    position_nft_mint: Pubkey,
+    reward_index: u8,
+    reward_mint: Pubkey,
+    reward_program: Pubkey,
+    creator: Pubkey,
+    payer: Pubkey,
+    cursor: u32,
+    investors: Vec<InvestorStream>,
+}
+
+impl RewardCrankBuilder {
+    pub fn new(
+        vault: Pubkey,
+        pool: Pubkey,
+        position_nft_mint: Pubkey,
+        reward_index: u8,
+        reward_mint: Pubkey,
+        creator: Pubkey,
+        payer: Pubkey,
+    ) -> Self {
+        Self {
+            vault,
+            pool,
+            position_nft_mint,
+            reward_index,
+            reward_mint,
+            reward_program: token::ID,
+            creator,
+            payer,
+            cursor: 0,
+            investors: vec![],
+        }
+    }
+
+    pub fn reward_program(mut self, reward_program: Pubkey) -> Self {
+        self.reward_program = reward_program;
+        self
+    }
+
+    pub fn cursor(mut self, cursor: u32) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    pub fn investors(mut self, investors: Vec<InvestorStream>) -> Self {
+        self.investors = investors;
+        self
+    }
+
+    fn reward_account(&self) -> Pubkey {
+        get_associated_token_address_with_program_id(
+            &get_owner_pda(self.vault).0,
+            &self.reward_mint,
+            &self.reward_program,
+        )
+    }
+
+    /// Returns the accounts of the `claim_reward` instruction.
+    pub fn claim_accounts(&self) -> AccountClaimReward {
+        AccountClaimReward {
+            policy: get_policy_pda(self.vault).0,
+            progress: get_progress_pda(self.vault).0,
+            reward_progress: get_reward_progress_pda(self.vault, self.reward_index).0,
+            position_registry: get_position_registry_pda(self.vault).0,
+            pool: self.pool,
+            position_nft_account: get_position_nft_account_pda(self.position_nft_mint).0,
+            position: get_position_pda(self.position_nft_mint).0,
+            pool_authority: damm_v2_constants::pool_authority::ID,
+            owner: get_owner_pda(self.vault).0,
+            reward_vault: get_reward_vault_pda(self.pool, self.reward_index).0,
+            reward_mint: self.reward_mint,
+            reward_program: self.reward_program,
+            reward_account: self.reward_account(),
+            payer: self.payer,
+            amm_event_authority: get_event_authority_pda(&damm_v2::ID).0,
+            amm_program: damm_v2::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Returns the accounts of the `crank_reward` instruction.
+    pub fn accounts(&self) -> AccountCrankReward {
+        AccountCrankReward {
+            policy: get_policy_pda(self.vault).0,
+            reward_progress: get_reward_progress_pda(self.vault, self.reward_index).0,
+            owner: get_owner_pda(self.vault).0,
+            reward_account: self.reward_account(),
+            reward_mint: self.reward_mint,
+            reward_program: self.reward_program,
+            creator: self.creator,
+            creator_reward_account: get_associated_token_address_with_program_id(
+                &self.creator,
+                &self.reward_mint,
+                &self.reward_program,
+            ),
+            payer: self.payer,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: get_event_authority_pda(&tollgate::ID).0,
+            program: tollgate::ID,
+        }
+    }
+
+    /// Builds the `claim_reward` instruction, opening the reward round of the day.
+    pub fn claim_reward(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            tollgate::ID,
+            &tollgate::instruction::ClaimReward {
+                reward_index: self.reward_index,
+            }
+            .data(),
+            self.claim_accounts().to_account_metas(None),
+        )
+    }
+
+    /// Builds the `crank_reward` instruction for the configured page of investors.
+    pub fn crank_reward(&self) -> Instruction {
+        let mut accounts = self.accounts().to_account_metas(None);
+        accounts.extend(get_reward_crank_remaining_accounts(
+            &self.investors,
+            &self.vault,
+            &self.reward_mint,
+            &self.reward_program,
+        ));
+
+        Instruction {
+            program_id: tollgate::ID,
+            accounts,
+            data: tollgate::instruction::CrankReward {
+                params: CrankParams {
+                    cursor: self.cursor,
+                },
+            }
+            .data(),
+        }
+    }
+}
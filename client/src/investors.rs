@@ -0,0 +1,145 @@
+#![allow(deprecated)]
+
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::borsh::try_from_slice_unchecked,
+    Result,
+};
+use anchor_spl::associated_token::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
+use streamflow_sdk::state::Contract;
+
+use tollgate::{error::TollgateError, state::PayoutRedirect};
+
+use crate::pda::{get_investor_stats_pda, get_payout_redirect_pda};
+
+/// An investor stream taking part in the distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvestorStream {
+    pub recipient: Pubkey,                  // Stream recipient
+    pub stream: Pubkey,                     // Streamflow contract
+    pub payout_destination: Option<Pubkey>, // Redirected payout account, if any
+    pub reward_owner: Option<Pubkey>,       // Owner of the redirected reward accounts, if any
+}
+
+impl InvestorStream {
+    pub fn new(recipient: Pubkey, stream: Pubkey) -> Self {
+        Self {
+            recipient,
+            stream,
+            payout_destination: None,
+            reward_owner: None,
+        }
+    }
+
+    pub fn payout_destination(mut self, payout_destination: Option<Pubkey>) -> Self {
+        self.payout_destination = payout_destination;
+        self
+    }
+
+    /// Applies the payout redirect of the stream, if the current recipient set it.
+    pub fn with_redirect(mut self, redirect: Option<&PayoutRedirect>) -> Self {
+        let redirect = redirect.filter(|redirect| redirect.recipient == self.recipient);
+        self.reward_owner = redirect.map(|redirect| redirect.destination_owner);
+        self.payout_destination(redirect.map(|redirect| redirect.destination))
+    }
+
+    /// Builds the zero-weight investor of a stream whose account was closed.
+    /// The crank skips it, so its recipient is left unset.
+    pub fn closed(stream: Pubkey) -> Self {
+        Self::new(Pubkey::default(), stream)
+    }
+
+    /// Builds an investor stream from the raw Streamflow contract data.
+    pub fn from_stream_account(stream: Pubkey, data: &[u8]) -> Result<Self> {
+        let contract = try_from_slice_unchecked::<Contract>(data)?;
+        Ok(Self::new(contract.recipient, stream))
+    }
+
+    /// Returns the investor quote ATA the crank pays out to.
+    pub fn ata(&self, quote_mint: &Pubkey) -> Pubkey {
+        get_associated_token_address(&self.recipient, quote_mint)
+    }
+
+    /// Returns the account the crank pays out to: the redirected payout
+    /// destination if any, the investor quote ATA otherwise.
+    pub fn payout_account(&self, quote_mint: &Pubkey) -> Pubkey {
+        self.payout_destination
+            .unwrap_or_else(|| self.ata(quote_mint))
+    }
+
+    /// Returns the investor stats PDA of the stream in the given vault.
+    pub fn stats(&self, vault: &Pubkey) -> Pubkey {
+        get_investor_stats_pda(*vault, self.stream).0
+    }
+
+    /// Returns the payout redirect PDA of the stream in the given vault.
+    pub fn redirect(&self, vault: &Pubkey) -> Pubkey {
+        get_payout_redirect_pda(*vault, self.stream).0
+    }
+}
+
+/// Sorts investors in the crank order of the program, by strictly increasing
+/// stream. Fails on a stream listed twice, which the crank rejects.
+pub fn sort_investors(investors: &mut [InvestorStream]) -> Result<()> {
+    investors.sort_by_key(|investor| investor.stream);
+    if investors
+        .windows(2)
+        .any(|pair| pair[0].stream == pair[1].stream)
+    {
+        return Err(TollgateError::DuplicateInvestorStream.into());
+    }
+    Ok(())
+}
+
+/// Assembles the crank remaining accounts for a page of investors.
+///
+/// Standard mode expects (stream, payout account, investor stats, payout redirect)
+/// groups while init mode prefixes each group with the investor.
+pub fn get_crank_remaining_accounts(
+    investors: &[InvestorStream],
+    vault: &Pubkey,
+    quote_mint: &Pubkey,
+    init_mode: bool,
+) -> Vec<AccountMeta> {
+    let stride = if init_mode { 5 } else { 4 };
+    let mut remaining_accounts = Vec::with_capacity(investors.len() * stride);
+    for investor in investors {
+        if init_mode {
+            remaining_accounts.push(AccountMeta::new_readonly(investor.recipient, false));
+        }
+        remaining_accounts.push(AccountMeta::new_readonly(investor.stream, false));
+        remaining_accounts.push(AccountMeta::new(investor.payout_account(quote_mint), false));
+        remaining_accounts.push(AccountMeta::new(investor.stats(vault), false));
+        remaining_accounts.push(AccountMeta::new_readonly(investor.redirect(vault), false));
+    }
+    remaining_accounts
+}
+
+/// Assembles the `crank_reward` remaining accounts for a page of investors,
+/// as (stream, reward ATA, investor stats, payout redirect) groups. Rewards are
+/// paid to the reward ATA of the owner of the payout destination if redirected,
+/// of the recipient otherwise, and recorded in the investor stats.
+pub fn get_reward_crank_remaining_accounts(
+    investors: &[InvestorStream],
+    vault: &Pubkey,
+    reward_mint: &Pubkey,
+    reward_program: &Pubkey,
+) -> Vec<AccountMeta> {
+    let mut remaining_accounts = Vec::with_capacity(investors.len() * 4);
+    for investor in investors {
+        remaining_accounts.push(AccountMeta::new_readonly(investor.stream, false));
+        remaining_accounts.push(AccountMeta::new(
+            get_associated_token_address_with_program_id(
+                &investor.reward_owner.unwrap_or(investor.recipient),
+                reward_mint,
+                reward_program,
+            ),
+            false,
+        ));
+        remaining_accounts.push(AccountMeta::new(investor.stats(vault), false));
+        remaining_accounts.push(AccountMeta::new_readonly(investor.redirect(vault), false));
+    }
+    remaining_accounts
+}
@@ -0,0 +1,20 @@
+//! Rust client for the Tollgate program.
+//!
+//! Provides PDA derivation, account decoding, page planning and typed
+//! instruction builders for `initialize`, `initialize_day_history`,
+//! `initialize_position_registry`, `add_position`, `add_locked_liquidity`,
+//! `claim_fees`, `crank`, `crank_with_init`, `preview_crank`,
+//! `claim_reward`, `crank_reward`, `set_creator` and `set_payout_destination`.
+
+pub mod accounts;
+pub mod instructions;
+pub mod investors;
+pub mod pages;
+pub mod pda;
+pub mod planner;
+
+pub use accounts::*;
+pub use instructions::*;
+pub use investors::*;
+pub use pages::*;
+pub use planner::*;
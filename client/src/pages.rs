@@ -0,0 +1,58 @@
+use std::ops::Range;
+
+use tollgate::state::{DayState, Progress};
+
+/// A page of investors processed by a single crank instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    pub cursor: u32, // First investor index of the page
+    pub end: u32,    // One past the last investor index of the page
+}
+
+impl Page {
+    pub fn len(&self) -> u32 {
+        self.end - self.cursor
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cursor == self.end
+    }
+
+    /// Returns the investor index range covered by the page.
+    pub fn range(&self) -> Range<usize> {
+        self.cursor as usize..self.end as usize
+    }
+}
+
+/// Splits the investors in `[cursor, investor_count)` into pages of at most `page_size`.
+pub fn plan_pages(investor_count: u32, cursor: u32, page_size: u32) -> Vec<Page> {
+    let page_size = page_size.max(1);
+    let mut pages = vec![];
+    let mut start = cursor;
+    while start < investor_count {
+        let end = start.saturating_add(page_size).min(investor_count);
+        pages.push(Page { cursor: start, end });
+        start = end;
+    }
+    pages
+}
+
+/// Returns the cursor the next crank should start from, or `None` when the
+/// current day is closed and the next one has not started yet.
+pub fn resume_cursor(progress: &Progress, now_ts: i64) -> Option<u32> {
+    if progress.is_new_day(now_ts) {
+        // claim_fees opens the day with the cursor reset
+        return Some(0);
+    }
+    if progress.day_state == DayState::Closed {
+        return None;
+    }
+    Some(progress.cursor)
+}
+
+/// Returns the timestamp at which the next day can be started.
+pub fn next_day_ts(progress: &Progress) -> i64 {
+    progress
+        .last_distribution_ts
+        .saturating_add(tollgate::constants::TWENTY_FOUR_HOURS)
+}
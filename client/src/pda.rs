@@ -0,0 +1,148 @@
+use anchor_lang::prelude::Pubkey;
+use tollgate::constants::{
+    damm_v2_constants, DAY_HISTORY_SEED, INVESTOR_FEE_POS_OWNER, INVESTOR_STATS_SEED,
+    PAYOUT_REDIRECT_SEED, POLICY_SEED, POSITION_REGISTRY_SEED, PROGRESS_SEED, REWARD_PROGRESS_SEED,
+    VAULT_SEED,
+};
+
+/// Seed of the Anchor event authority PDA.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+pub fn max_key(left: &Pubkey, right: &Pubkey) -> [u8; 32] {
+    std::cmp::max(left, right).to_bytes()
+}
+
+pub fn min_key(left: &Pubkey, right: &Pubkey) -> [u8; 32] {
+    std::cmp::min(left, right).to_bytes()
+}
+
+/// Calculates the address of the Policy PDA.
+pub fn get_policy_pda(vault: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POLICY_SEED, vault.as_ref()], &tollgate::ID)
+}
+
+/// Calculates the address of the Progress PDA.
+pub fn get_progress_pda(vault: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRESS_SEED, vault.as_ref()], &tollgate::ID)
+}
+
+/// Calculates the address of the DayHistory PDA.
+pub fn get_day_history_pda(vault: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DAY_HISTORY_SEED, vault.as_ref()], &tollgate::ID)
+}
+
+/// Calculates the address of the PositionRegistry PDA.
+pub fn get_position_registry_pda(vault: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POSITION_REGISTRY_SEED, vault.as_ref()], &tollgate::ID)
+}
+
+/// Calculates the address of the RewardProgress PDA of a reward index.
+pub fn get_reward_progress_pda(vault: Pubkey, reward_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REWARD_PROGRESS_SEED, vault.as_ref(), &[reward_index]],
+        &tollgate::ID,
+    )
+}
+
+/// Calculates the address of the InvestorStats PDA of a stream.
+pub fn get_investor_stats_pda(vault: Pubkey, stream: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[INVESTOR_STATS_SEED, vault.as_ref(), stream.as_ref()],
+        &tollgate::ID,
+    )
+}
+
+pub fn get_payout_redirect_pda(vault: Pubkey, stream: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PAYOUT_REDIRECT_SEED, vault.as_ref(), stream.as_ref()],
+        &tollgate::ID,
+    )
+}
+
+/// Calculates the address of the honorary position owner PDA.
+pub fn get_owner_pda(vault: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VAULT_SEED, vault.as_ref(), INVESTOR_FEE_POS_OWNER],
+        &tollgate::ID,
+    )
+}
+
+/// Calculates the address of the event authority PDA of the given program.
+pub fn get_event_authority_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id)
+}
+
+/// Calculates the address of the Position NFT account PDA.
+pub fn get_position_nft_account_pda(position_nft_mint: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            damm_v2_constants::seeds::POSITION_NFT_ACCOUNT_PREFIX,
+            position_nft_mint.as_ref(),
+        ],
+        &damm_v2::ID,
+    )
+}
+
+/// Calculates the address of the Pool PDA with config.
+pub fn get_pool_with_config_pda(
+    config: Pubkey,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            damm_v2_constants::seeds::POOL_PREFIX,
+            config.as_ref(),
+            &max_key(&base_mint, &quote_mint),
+            &min_key(&base_mint, &quote_mint),
+        ],
+        &damm_v2::ID,
+    )
+}
+
+/// Calculates the address of the Pool PDA.
+pub fn get_pool_pda(base_mint: Pubkey, quote_mint: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            damm_v2_constants::seeds::CUSTOMIZABLE_POOL_PREFIX,
+            &max_key(&base_mint, &quote_mint),
+            &min_key(&base_mint, &quote_mint),
+        ],
+        &damm_v2::ID,
+    )
+}
+
+/// Calculates the address of the Position PDA.
+pub fn get_position_pda(position_nft_mint: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            damm_v2_constants::seeds::POSITION_PREFIX,
+            position_nft_mint.as_ref(),
+        ],
+        &damm_v2::ID,
+    )
+}
+
+/// Calculates the address of the Token Vault PDA.
+pub fn get_token_vault_pda(token_mint: Pubkey, pool: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            damm_v2_constants::seeds::TOKEN_VAULT_PREFIX,
+            token_mint.as_ref(),
+            pool.as_ref(),
+        ],
+        &damm_v2::ID,
+    )
+}
+
+/// Calculates the address of the Reward Vault PDA of a pool reward index.
+pub fn get_reward_vault_pda(pool: Pubkey, reward_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            damm_v2_constants::seeds::REWARD_VAULT_PREFIX,
+            pool.as_ref(),
+            &[reward_index],
+        ],
+        &damm_v2::ID,
+    )
+}
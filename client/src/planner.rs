@@ -0,0 +1,339 @@
+//! Page planning over v0 transactions.
+//!
+//! A legacy transaction lists every account in full, which caps a crank page
+//! at around 10 investors. Loading the crank accounts from an address lookup
+//! table costs a single byte per account, so the page size is bounded by the
+//! account lock limit and the compute budget instead. The planner compiles
+//! each candidate page into a v0 message and picks the largest page that
+//! fits the packet size, the account locks and the compute unit model.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        hash::Hash,
+        instruction::Instruction,
+        message::{v0, AddressLookupTableAccount, CompileError, VersionedMessage},
+    },
+    ToAccountMetas,
+};
+use solana_address_lookup_table_interface::instruction::{
+    create_lookup_table, extend_lookup_table,
+};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use tollgate::state::CrankPhase;
+
+use crate::{investors::InvestorStream, pages::Page, CrankBuilder};
+
+/// Maximum size of a serialized transaction.
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// Maximum compute unit limit of a transaction.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Maximum number of accounts a transaction can lock.
+pub const MAX_TX_ACCOUNT_LOCKS: usize = 64;
+
+/// Maximum number of addresses held by a lookup table.
+pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
+
+/// Addresses added per `extend_lookup_table` instruction, so that each
+/// extension fits a legacy transaction with a separate authority and payer.
+pub const LOOKUP_TABLE_EXTEND_CHUNK: usize = 20;
+
+/// Compute unit model of a crank page. Every term is an upper bound of the
+/// cost measured by the LiteSVM runs of the tests crate, which check the
+/// model without its margin against each page they send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CuModel {
+    pub base: u32,                  // Account validation, day bookkeeping and page event
+    pub close: u32,                 // Creator payout, day history and event at day close
+    pub per_snapshot_investor: u32, // Stream decoding and first investor stats creation
+    pub per_payout_investor: u32,   // Stream decoding, transfer, stats update and event
+    pub per_init_investor: u32,     // ATA creation
+    pub margin_bps: u16,            // Safety margin on top of the estimate
+}
+
+impl Default for CuModel {
+    fn default() -> Self {
+        Self {
+            base: 65_000,
+            close: 45_000,
+            per_snapshot_investor: 35_000,
+            per_payout_investor: 35_000,
+            per_init_investor: 45_000,
+            margin_bps: 2_000,
+        }
+    }
+}
+
+impl CuModel {
+    /// Estimates the compute units of a page, margin included and capped at
+    /// `MAX_COMPUTE_UNIT_LIMIT`.
+    pub fn estimate(
+        &self,
+        page_size: u32,
+        phase: CrankPhase,
+        init_mode: bool,
+        closes: bool,
+    ) -> u32 {
+        let per_investor = match phase {
+            // A cap pass reads the same streams as the snapshot pass
+            CrankPhase::Snapshot | CrankPhase::Cap => self.per_snapshot_investor as u64,
+            CrankPhase::Payout if init_mode => {
+                self.per_payout_investor as u64 + self.per_init_investor as u64
+            }
+            CrankPhase::Payout => self.per_payout_investor as u64,
+        };
+        let mut units = self.base as u64 + per_investor * page_size as u64;
+        if closes {
+            units += self.close as u64;
+        }
+        let units = units * (10_000 + self.margin_bps as u64) / 10_000;
+        units.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+    }
+}
+
+/// Returns the ComputeBudget instructions for the given limit and optional price.
+pub fn compute_budget_instructions(
+    compute_unit_limit: u32,
+    compute_unit_price: Option<u64>,
+) -> Vec<Instruction> {
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        compute_unit_limit,
+    )];
+    if let Some(price) = compute_unit_price {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    instructions
+}
+
+/// Returns the addresses to store in the lookup tables of a vault: the static
+/// crank accounts followed by the remaining accounts of every investor, split
+/// into tables of at most `LOOKUP_TABLE_MAX_ADDRESSES`.
+pub fn lookup_table_addresses(
+    builder: &CrankBuilder,
+    investors: &[InvestorStream],
+    init_mode: bool,
+) -> Vec<Vec<Pubkey>> {
+    let mut addresses: Vec<Pubkey> = builder
+        .accounts()
+        .to_account_metas(None)
+        .into_iter()
+        .map(|meta| meta.pubkey)
+        .collect();
+    let remaining = builder
+        .clone()
+        .investors(investors.to_vec())
+        .remaining_accounts(init_mode);
+    addresses.extend(remaining.into_iter().map(|meta| meta.pubkey));
+
+    let mut seen = std::collections::HashSet::with_capacity(addresses.len());
+    addresses.retain(|address| seen.insert(*address));
+    addresses
+        .chunks(LOOKUP_TABLE_MAX_ADDRESSES)
+        .map(<[Pubkey]>::to_vec)
+        .collect()
+}
+
+/// Instructions creating and filling a lookup table.
+#[derive(Debug, Clone)]
+pub struct LookupTableInstructions {
+    pub address: Pubkey,          // Lookup table address
+    pub create: Instruction,      // Creates the table
+    pub extend: Vec<Instruction>, // Each one sent in its own transaction
+}
+
+/// Builds the instructions creating a lookup table owned by `authority` and
+/// extending it with at most `LOOKUP_TABLE_MAX_ADDRESSES` addresses.
+/// `recent_slot` must be a recent finalized slot.
+pub fn create_lookup_table_instructions(
+    authority: Pubkey,
+    payer: Pubkey,
+    recent_slot: u64,
+    addresses: &[Pubkey],
+) -> LookupTableInstructions {
+    let (create, address) = create_lookup_table(authority, payer, recent_slot);
+    let extend = addresses
+        .chunks(LOOKUP_TABLE_EXTEND_CHUNK)
+        .map(|chunk| extend_lookup_table(address, authority, Some(payer), chunk.to_vec()))
+        .collect();
+    LookupTableInstructions {
+        address,
+        create,
+        extend,
+    }
+}
+
+/// Plans crank pages as v0 transactions loading their accounts from lookup tables.
+#[derive(Debug, Clone)]
+pub struct PagePlanner {
+    builder: CrankBuilder,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    cu_model: CuModel,
+    max_account_locks: usize,
+    compute_unit_price: Option<u64>,
+}
+
+impl PagePlanner {
+    pub fn new(builder: CrankBuilder, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        Self {
+            builder,
+            lookup_tables,
+            cu_model: CuModel::default(),
+            max_account_locks: MAX_TX_ACCOUNT_LOCKS,
+            compute_unit_price: None,
+        }
+    }
+
+    pub fn cu_model(mut self, cu_model: CuModel) -> Self {
+        self.cu_model = cu_model;
+        self
+    }
+
+    pub fn max_account_locks(mut self, max_account_locks: usize) -> Self {
+        self.max_account_locks = max_account_locks;
+        self
+    }
+
+    pub fn compute_unit_price(mut self, compute_unit_price: Option<u64>) -> Self {
+        self.compute_unit_price = compute_unit_price;
+        self
+    }
+
+    pub fn lookup_tables(&self) -> &[AddressLookupTableAccount] {
+        &self.lookup_tables
+    }
+
+    /// Returns the compute unit limit of a page of `investors` in crank order.
+    pub fn compute_unit_limit(
+        &self,
+        investors: &[InvestorStream],
+        page: Page,
+        phase: CrankPhase,
+        init_mode: bool,
+    ) -> u32 {
+        self.cu_model.estimate(
+            page.len(),
+            phase.clone(),
+            init_mode,
+            phase == CrankPhase::Payout && page.end as usize == investors.len(),
+        )
+    }
+
+    /// Returns the instructions of a page of `investors` in crank order,
+    /// prefixed with its ComputeBudget instructions.
+    pub fn page_instructions(
+        &self,
+        investors: &[InvestorStream],
+        page: Page,
+        phase: CrankPhase,
+        init_mode: bool,
+    ) -> Vec<Instruction> {
+        let mut instructions = compute_budget_instructions(
+            self.compute_unit_limit(investors, page, phase, init_mode),
+            self.compute_unit_price,
+        );
+        let builder = self
+            .builder
+            .clone()
+            .cursor(page.cursor)
+            .investors(investors[page.range()].to_vec());
+        instructions.push(if init_mode {
+            builder.crank_with_init()
+        } else {
+            builder.crank()
+        });
+        instructions
+    }
+
+    /// Compiles the v0 message of a page.
+    pub fn compile_page(
+        &self,
+        payer: &Pubkey,
+        investors: &[InvestorStream],
+        page: Page,
+        phase: CrankPhase,
+        init_mode: bool,
+        recent_blockhash: Hash,
+    ) -> Result<v0::Message, CompileError> {
+        v0::Message::try_compile(
+            payer,
+            &self.page_instructions(investors, page, phase, init_mode),
+            &self.lookup_tables,
+            recent_blockhash,
+        )
+    }
+
+    /// Returns whether a page fits a single v0 transaction signed by the payer.
+    pub fn fits(
+        &self,
+        payer: &Pubkey,
+        investors: &[InvestorStream],
+        page: Page,
+        phase: CrankPhase,
+        init_mode: bool,
+    ) -> bool {
+        if self.compute_unit_limit(investors, page, phase.clone(), init_mode)
+            >= MAX_COMPUTE_UNIT_LIMIT
+        {
+            return false;
+        }
+
+        let Ok(message) =
+            self.compile_page(payer, investors, page, phase, init_mode, Hash::default())
+        else {
+            return false;
+        };
+        let account_locks = message.account_keys.len()
+            + message
+                .address_table_lookups
+                .iter()
+                .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+                .sum::<usize>();
+        let signatures = message.header.num_required_signatures as usize;
+        let size = 1 + signatures * 64 + VersionedMessage::V0(message).serialize().len();
+
+        account_locks <= self.max_account_locks && size <= PACKET_DATA_SIZE
+    }
+
+    /// Returns the largest page starting at `cursor` that fits a v0
+    /// transaction, or `None` when not even a single investor fits.
+    pub fn max_page(
+        &self,
+        payer: &Pubkey,
+        investors: &[InvestorStream],
+        cursor: u32,
+        phase: CrankPhase,
+        init_mode: bool,
+    ) -> Option<Page> {
+        let mut best = None;
+        for end in cursor + 1..=investors.len() as u32 {
+            let page = Page { cursor, end };
+            if !self.fits(payer, investors, page, phase.clone(), init_mode) {
+                break;
+            }
+            best = Some(page);
+        }
+        best
+    }
+
+    /// Splits the investors from `cursor` into the largest pages that fit,
+    /// or `None` when a single investor does not fit.
+    pub fn plan_pass(
+        &self,
+        payer: &Pubkey,
+        investors: &[InvestorStream],
+        cursor: u32,
+        phase: CrankPhase,
+        init_mode: bool,
+    ) -> Option<Vec<Page>> {
+        let mut pages = vec![];
+        let mut start = cursor;
+        while (start as usize) < investors.len() {
+            let page = self.max_page(payer, investors, start, phase.clone(), init_mode)?;
+            start = page.end;
+            pages.push(page);
+        }
+        Some(pages)
+    }
+}
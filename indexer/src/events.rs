@@ -0,0 +1,755 @@
+use anchor_client::{
+    anchor_lang::{event::EVENT_IX_TAG_LE, AnchorDeserialize, Discriminator},
+    solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey},
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use tollgate::{constants::EVENT_SCHEMA_VERSION, events};
+
+/// Decoded `HonoraryPositionInitialized` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HonoraryPositionInitialized {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub progress: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool_cfg: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub position: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub owner: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub base_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub quote_mint: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub min_payout_lamports: u64,
+    pub daily_cap: Option<u64>,
+    pub y0: u64,
+}
+
+/// Decoded `HonoraryPositionAdded` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HonoraryPositionAdded {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub position: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub owner: Pubkey,
+    pub index: u8,
+    pub position_count: u8,
+}
+
+/// Decoded `HonoraryLiquidityLocked` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HonoraryLiquidityLocked {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub position: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub owner: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub depositor: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub liquidity_delta: u128,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub permanent_locked_liquidity: u128,
+}
+
+/// Decoded `QuoteFeesClaimed` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteFeesClaimed {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub progress: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub position: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub owner: Pubkey,
+    pub base_fee_claimed: u64,
+    pub quote_fee_claimed: u64,
+}
+
+/// Decoded `PoolDisabled` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolDisabled {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub progress: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub position: Pubkey,
+    pub pool_status: u8,
+}
+
+/// Decoded `RemainderSettled` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemainderSettled {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub progress: Pubkey,
+    pub remainder: u64,
+    pub swept: u64,
+    pub rolled_over: u64,
+    pub reserved: u64,
+    pub released: u64,
+    pub reserve: u64,
+}
+
+/// Decoded `InvestorPayoutPage` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvestorPayoutPage {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub progress: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub position: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub owner: Pubkey,
+    pub cursor: u32,
+    pub investors: u32,
+    pub page_start: u32,
+    pub page_end: u32,
+    pub payout: u64,
+}
+
+/// Why an investor of a page is not paid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    Cancelled,
+    Paused,
+    Closed,
+    BelowDust,
+    MissingAta,
+    ZeroLocked,
+}
+
+impl From<events::SkipReason> for SkipReason {
+    fn from(reason: events::SkipReason) -> Self {
+        match reason {
+            events::SkipReason::Cancelled => SkipReason::Cancelled,
+            events::SkipReason::Paused => SkipReason::Paused,
+            events::SkipReason::Closed => SkipReason::Closed,
+            events::SkipReason::BelowDust => SkipReason::BelowDust,
+            events::SkipReason::MissingAta => SkipReason::MissingAta,
+            events::SkipReason::ZeroLocked => SkipReason::ZeroLocked,
+        }
+    }
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SkipReason::Cancelled => "cancelled",
+            SkipReason::Paused => "paused",
+            SkipReason::Closed => "closed",
+            SkipReason::BelowDust => "below_dust",
+            SkipReason::MissingAta => "missing_ata",
+            SkipReason::ZeroLocked => "zero_locked",
+        })
+    }
+}
+
+/// Decoded `InvestorPaid` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvestorPaid {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub stream: Pubkey,
+    pub index: u32,
+    #[serde_as(as = "DisplayFromStr")]
+    pub recipient: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub ata: Pubkey,
+    pub locked: u64,
+    pub share_bps: u64,
+    pub amount: u64,
+}
+
+/// Decoded `InvestorSkipped` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvestorSkipped {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub stream: Pubkey,
+    pub index: u32,
+    pub reason: SkipReason,
+}
+
+/// Decoded `InvestorCapped` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvestorCapped {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub stream: Pubkey,
+    pub index: u32,
+    pub locked: u64,
+    pub share: u64,
+    pub cap: u64,
+}
+
+/// Decoded `RewardClaimed` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardClaimed {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub position: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub owner: Pubkey,
+    pub reward_index: u8,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reward_mint: Pubkey,
+    pub reward_claimed: u64,
+    pub carry: u64,
+    pub reward_pool: u64,
+    pub locked_total: u64,
+    pub investor_pool: u64,
+}
+
+/// Decoded `RewardInvestorPaid` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardInvestorPaid {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    pub reward_index: u8,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reward_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub stream: Pubkey,
+    pub index: u32,
+    #[serde_as(as = "DisplayFromStr")]
+    pub recipient: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reward_account: Pubkey,
+    pub locked: u64,
+    pub amount: u64,
+}
+
+/// Decoded `RewardInvestorSkipped` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardInvestorSkipped {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    pub reward_index: u8,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reward_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub stream: Pubkey,
+    pub index: u32,
+    pub reason: SkipReason,
+}
+
+/// Decoded `RewardPayoutPage` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardPayoutPage {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    pub reward_index: u8,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reward_mint: Pubkey,
+    pub cursor: u32,
+    pub investors: u32,
+    pub page_start: u32,
+    pub page_end: u32,
+    pub payout: u64,
+}
+
+/// Decoded `RewardRoundClosed` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardRoundClosed {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    pub reward_index: u8,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reward_mint: Pubkey,
+    pub reward_pool: u64,
+    pub investor_payouts: u64,
+    pub creator_payout: u64,
+    pub carry: u64,
+}
+
+/// Decoded `CreatorPayoutDayClosed` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatorPayoutDayClosed {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub progress: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub position: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub owner: Pubkey,
+    pub timestamp: i64,
+    pub total_distributed: u64,
+    pub creator_payout: u64,
+    pub carry: u64,
+    pub distributable: u64,
+    pub investor_payouts: u64,
+}
+
+/// Decoded `CreatorUpdated` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatorUpdated {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub previous_creator: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub creator: Pubkey,
+}
+
+/// Decoded `PayoutDestinationSet` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutDestinationSet {
+    pub schema_version: u8,
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub stream: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub recipient: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub destination: Pubkey,
+}
+
+/// Decoded `AccountsMigrated` event.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountsMigrated {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub policy: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub progress: Pubkey,
+    pub policy_from_version: u8,
+    pub policy_version: u8,
+    pub progress_from_version: u8,
+    pub progress_version: u8,
+}
+
+/// A Tollgate event, tagged by its name once serialized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event")]
+pub enum TollgateEvent {
+    HonoraryPositionInitialized(HonoraryPositionInitialized),
+    HonoraryPositionAdded(HonoraryPositionAdded),
+    HonoraryLiquidityLocked(HonoraryLiquidityLocked),
+    QuoteFeesClaimed(QuoteFeesClaimed),
+    PoolDisabled(PoolDisabled),
+    RemainderSettled(RemainderSettled),
+    InvestorPayoutPage(InvestorPayoutPage),
+    InvestorPaid(InvestorPaid),
+    InvestorSkipped(InvestorSkipped),
+    InvestorCapped(InvestorCapped),
+    RewardClaimed(RewardClaimed),
+    RewardInvestorPaid(RewardInvestorPaid),
+    RewardInvestorSkipped(RewardInvestorSkipped),
+    RewardPayoutPage(RewardPayoutPage),
+    RewardRoundClosed(RewardRoundClosed),
+    CreatorPayoutDayClosed(CreatorPayoutDayClosed),
+    CreatorUpdated(CreatorUpdated),
+    PayoutDestinationSet(PayoutDestinationSet),
+    AccountsMigrated(AccountsMigrated),
+}
+
+impl TollgateEvent {
+    /// Returns the event name, as declared in the program.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TollgateEvent::HonoraryPositionInitialized(_) => "HonoraryPositionInitialized",
+            TollgateEvent::HonoraryPositionAdded(_) => "HonoraryPositionAdded",
+            TollgateEvent::HonoraryLiquidityLocked(_) => "HonoraryLiquidityLocked",
+            TollgateEvent::QuoteFeesClaimed(_) => "QuoteFeesClaimed",
+            TollgateEvent::PoolDisabled(_) => "PoolDisabled",
+            TollgateEvent::RemainderSettled(_) => "RemainderSettled",
+            TollgateEvent::InvestorPayoutPage(_) => "InvestorPayoutPage",
+            TollgateEvent::InvestorPaid(_) => "InvestorPaid",
+            TollgateEvent::InvestorSkipped(_) => "InvestorSkipped",
+            TollgateEvent::InvestorCapped(_) => "InvestorCapped",
+            TollgateEvent::RewardClaimed(_) => "RewardClaimed",
+            TollgateEvent::RewardInvestorPaid(_) => "RewardInvestorPaid",
+            TollgateEvent::RewardInvestorSkipped(_) => "RewardInvestorSkipped",
+            TollgateEvent::RewardPayoutPage(_) => "RewardPayoutPage",
+            TollgateEvent::RewardRoundClosed(_) => "RewardRoundClosed",
+            TollgateEvent::CreatorPayoutDayClosed(_) => "CreatorPayoutDayClosed",
+            TollgateEvent::CreatorUpdated(_) => "CreatorUpdated",
+            TollgateEvent::PayoutDestinationSet(_) => "PayoutDestinationSet",
+            TollgateEvent::AccountsMigrated(_) => "AccountsMigrated",
+        }
+    }
+
+    /// Returns the vault the event belongs to.
+    pub fn vault(&self) -> Pubkey {
+        match self {
+            TollgateEvent::HonoraryPositionInitialized(e) => e.vault,
+            TollgateEvent::HonoraryPositionAdded(e) => e.vault,
+            TollgateEvent::HonoraryLiquidityLocked(e) => e.vault,
+            TollgateEvent::QuoteFeesClaimed(e) => e.vault,
+            TollgateEvent::PoolDisabled(e) => e.vault,
+            TollgateEvent::RemainderSettled(e) => e.vault,
+            TollgateEvent::InvestorPayoutPage(e) => e.vault,
+            TollgateEvent::InvestorPaid(e) => e.vault,
+            TollgateEvent::InvestorSkipped(e) => e.vault,
+            TollgateEvent::InvestorCapped(e) => e.vault,
+            TollgateEvent::RewardClaimed(e) => e.vault,
+            TollgateEvent::RewardInvestorPaid(e) => e.vault,
+            TollgateEvent::RewardInvestorSkipped(e) => e.vault,
+            TollgateEvent::RewardPayoutPage(e) => e.vault,
+            TollgateEvent::RewardRoundClosed(e) => e.vault,
+            TollgateEvent::CreatorPayoutDayClosed(e) => e.vault,
+            TollgateEvent::CreatorUpdated(e) => e.vault,
+            TollgateEvent::PayoutDestinationSet(e) => e.vault,
+            TollgateEvent::AccountsMigrated(e) => e.vault,
+        }
+    }
+
+    /// Decodes an event from its discriminator-prefixed data, or returns
+    /// `None` when the data is not a known Tollgate event of the current
+    /// schema version.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let (discriminator, mut body) = data.split_at_checked(8)?;
+        if body.first() != Some(&EVENT_SCHEMA_VERSION) {
+            return None;
+        }
+        let event = if discriminator == events::HonoraryPositionInitialized::DISCRIMINATOR {
+            let e = events::HonoraryPositionInitialized::deserialize(&mut body).ok()?;
+            TollgateEvent::HonoraryPositionInitialized(HonoraryPositionInitialized {
+                vault: e.vault,
+                policy: e.policy,
+                progress: e.progress,
+                pool: e.pool,
+                pool_cfg: e.pool_cfg,
+                position: e.position,
+                owner: e.owner,
+                base_mint: e.base_mint,
+                quote_mint: e.quote_mint,
+                investor_fee_share_bps: e.investor_fee_share_bps,
+                min_payout_lamports: e.min_payout_lamports,
+                daily_cap: e.daily_cap,
+                y0: e.y0,
+            })
+        } else if discriminator == events::HonoraryPositionAdded::DISCRIMINATOR {
+            let e = events::HonoraryPositionAdded::deserialize(&mut body).ok()?;
+            TollgateEvent::HonoraryPositionAdded(HonoraryPositionAdded {
+                vault: e.vault,
+                policy: e.policy,
+                pool: e.pool,
+                position: e.position,
+                owner: e.owner,
+                index: e.index,
+                position_count: e.position_count,
+            })
+        } else if discriminator == events::HonoraryLiquidityLocked::DISCRIMINATOR {
+            let e = events::HonoraryLiquidityLocked::deserialize(&mut body).ok()?;
+            TollgateEvent::HonoraryLiquidityLocked(HonoraryLiquidityLocked {
+                vault: e.vault,
+                policy: e.policy,
+                pool: e.pool,
+                position: e.position,
+                owner: e.owner,
+                depositor: e.depositor,
+                liquidity_delta: e.liquidity_delta,
+                base_amount: e.base_amount,
+                quote_amount: e.quote_amount,
+                permanent_locked_liquidity: e.permanent_locked_liquidity,
+            })
+        } else if discriminator == events::QuoteFeesClaimed::DISCRIMINATOR {
+            let e = events::QuoteFeesClaimed::deserialize(&mut body).ok()?;
+            TollgateEvent::QuoteFeesClaimed(QuoteFeesClaimed {
+                vault: e.vault,
+                policy: e.policy,
+                progress: e.progress,
+                pool: e.pool,
+                position: e.position,
+                owner: e.owner,
+                base_fee_claimed: e.base_fee_claimed,
+                quote_fee_claimed: e.quote_fee_claimed,
+            })
+        } else if discriminator == events::PoolDisabled::DISCRIMINATOR {
+            let e = events::PoolDisabled::deserialize(&mut body).ok()?;
+            TollgateEvent::PoolDisabled(PoolDisabled {
+                vault: e.vault,
+                policy: e.policy,
+                progress: e.progress,
+                pool: e.pool,
+                position: e.position,
+                pool_status: e.pool_status,
+            })
+        } else if discriminator == events::RemainderSettled::DISCRIMINATOR {
+            let e = events::RemainderSettled::deserialize(&mut body).ok()?;
+            TollgateEvent::RemainderSettled(RemainderSettled {
+                vault: e.vault,
+                policy: e.policy,
+                progress: e.progress,
+                remainder: e.remainder,
+                swept: e.swept,
+                rolled_over: e.rolled_over,
+                reserved: e.reserved,
+                released: e.released,
+                reserve: e.reserve,
+            })
+        } else if discriminator == events::InvestorPayoutPage::DISCRIMINATOR {
+            let e = events::InvestorPayoutPage::deserialize(&mut body).ok()?;
+            TollgateEvent::InvestorPayoutPage(InvestorPayoutPage {
+                vault: e.vault,
+                policy: e.policy,
+                progress: e.progress,
+                pool: e.pool,
+                position: e.position,
+                owner: e.owner,
+                cursor: e.cursor,
+                investors: e.investors,
+                page_start: e.page_start,
+                page_end: e.page_end,
+                payout: e.payout,
+            })
+        } else if discriminator == events::InvestorPaid::DISCRIMINATOR {
+            let e = events::InvestorPaid::deserialize(&mut body).ok()?;
+            TollgateEvent::InvestorPaid(InvestorPaid {
+                vault: e.vault,
+                policy: e.policy,
+                stream: e.stream,
+                index: e.index,
+                recipient: e.recipient,
+                ata: e.ata,
+                locked: e.locked,
+                share_bps: e.share_bps,
+                amount: e.amount,
+            })
+        } else if discriminator == events::InvestorSkipped::DISCRIMINATOR {
+            let e = events::InvestorSkipped::deserialize(&mut body).ok()?;
+            TollgateEvent::InvestorSkipped(InvestorSkipped {
+                vault: e.vault,
+                policy: e.policy,
+                stream: e.stream,
+                index: e.index,
+                reason: e.reason.into(),
+            })
+        } else if discriminator == events::InvestorCapped::DISCRIMINATOR {
+            let e = events::InvestorCapped::deserialize(&mut body).ok()?;
+            TollgateEvent::InvestorCapped(InvestorCapped {
+                vault: e.vault,
+                policy: e.policy,
+                stream: e.stream,
+                index: e.index,
+                locked: e.locked,
+                share: e.share,
+                cap: e.cap,
+            })
+        } else if discriminator == events::RewardClaimed::DISCRIMINATOR {
+            let e = events::RewardClaimed::deserialize(&mut body).ok()?;
+            TollgateEvent::RewardClaimed(RewardClaimed {
+                vault: e.vault,
+                policy: e.policy,
+                pool: e.pool,
+                position: e.position,
+                owner: e.owner,
+                reward_index: e.reward_index,
+                reward_mint: e.reward_mint,
+                reward_claimed: e.reward_claimed,
+                carry: e.carry,
+                reward_pool: e.reward_pool,
+                locked_total: e.locked_total,
+                investor_pool: e.investor_pool,
+            })
+        } else if discriminator == events::RewardInvestorPaid::DISCRIMINATOR {
+            let e = events::RewardInvestorPaid::deserialize(&mut body).ok()?;
+            TollgateEvent::RewardInvestorPaid(RewardInvestorPaid {
+                vault: e.vault,
+                policy: e.policy,
+                reward_index: e.reward_index,
+                reward_mint: e.reward_mint,
+                stream: e.stream,
+                index: e.index,
+                recipient: e.recipient,
+                reward_account: e.reward_account,
+                locked: e.locked,
+                amount: e.amount,
+            })
+        } else if discriminator == events::RewardInvestorSkipped::DISCRIMINATOR {
+            let e = events::RewardInvestorSkipped::deserialize(&mut body).ok()?;
+            TollgateEvent::RewardInvestorSkipped(RewardInvestorSkipped {
+                vault: e.vault,
+                policy: e.policy,
+                reward_index: e.reward_index,
+                reward_mint: e.reward_mint,
+                stream: e.stream,
+                index: e.index,
+                reason: e.reason.into(),
+            })
+        } else if discriminator == events::RewardPayoutPage::DISCRIMINATOR {
+            let e = events::RewardPayoutPage::deserialize(&mut body).ok()?;
+            TollgateEvent::RewardPayoutPage(RewardPayoutPage {
+                vault: e.vault,
+                policy: e.policy,
+                reward_index: e.reward_index,
+                reward_mint: e.reward_mint,
+                cursor: e.cursor,
+                investors: e.investors,
+                page_start: e.page_start,
+                page_end: e.page_end,
+                payout: e.payout,
+            })
+        } else if discriminator == events::RewardRoundClosed::DISCRIMINATOR {
+            let e = events::RewardRoundClosed::deserialize(&mut body).ok()?;
+            TollgateEvent::RewardRoundClosed(RewardRoundClosed {
+                vault: e.vault,
+                policy: e.policy,
+                reward_index: e.reward_index,
+                reward_mint: e.reward_mint,
+                reward_pool: e.reward_pool,
+                investor_payouts: e.investor_payouts,
+                creator_payout: e.creator_payout,
+                carry: e.carry,
+            })
+        } else if discriminator == events::CreatorPayoutDayClosed::DISCRIMINATOR {
+            let e = events::CreatorPayoutDayClosed::deserialize(&mut body).ok()?;
+            TollgateEvent::CreatorPayoutDayClosed(CreatorPayoutDayClosed {
+                vault: e.vault,
+                policy: e.policy,
+                progress: e.progress,
+                pool: e.pool,
+                position: e.position,
+                owner: e.owner,
+                timestamp: e.timestamp,
+                total_distributed: e.total_distributed,
+                creator_payout: e.creator_payout,
+                carry: e.carry,
+                distributable: e.distributable,
+                investor_payouts: e.investor_payouts,
+            })
+        } else if discriminator == events::CreatorUpdated::DISCRIMINATOR {
+            let e = events::CreatorUpdated::deserialize(&mut body).ok()?;
+            TollgateEvent::CreatorUpdated(CreatorUpdated {
+                vault: e.vault,
+                policy: e.policy,
+                previous_creator: e.previous_creator,
+                creator: e.creator,
+            })
+        } else if discriminator == events::PayoutDestinationSet::DISCRIMINATOR {
+            let e = events::PayoutDestinationSet::deserialize(&mut body).ok()?;
+            TollgateEvent::PayoutDestinationSet(PayoutDestinationSet {
+                schema_version: e.schema_version,
+                vault: e.vault,
+                stream: e.stream,
+                recipient: e.recipient,
+                destination: e.destination,
+            })
+        } else if discriminator == events::AccountsMigrated::DISCRIMINATOR {
+            let e = events::AccountsMigrated::deserialize(&mut body).ok()?;
+            TollgateEvent::AccountsMigrated(AccountsMigrated {
+                vault: e.vault,
+                policy: e.policy,
+                progress: e.progress,
+                policy_from_version: e.policy_from_version,
+                policy_version: e.policy_version,
+                progress_from_version: e.progress_from_version,
+                progress_version: e.progress_version,
+            })
+        } else {
+            return None;
+        };
+        Some(event)
+    }
+}
+
+/// Extracts the Tollgate events from the inner instructions of a transaction.
+///
+/// Events are emitted as self-CPIs tagged with `EVENT_IX_TAG_LE`, so only the
+/// inner instructions invoking the Tollgate program are decoded. The program
+/// rejects such instructions unless its event authority signed them, so
+/// another program cannot forge events. `account_keys` are the static keys of
+/// the transaction followed by its loaded addresses, writable then readonly.
+pub fn parse_inner_instructions(
+    account_keys: &[Pubkey],
+    inner_instructions: &[CompiledInstruction],
+) -> Vec<TollgateEvent> {
+    inner_instructions
+        .iter()
+        .filter(|ix| account_keys.get(ix.program_id_index as usize) == Some(&tollgate::ID))
+        .filter_map(|ix| ix.data.strip_prefix(EVENT_IX_TAG_LE))
+        .filter_map(TollgateEvent::decode)
+        .collect()
+}
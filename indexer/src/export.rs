@@ -0,0 +1,776 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+
+use crate::{
+    build_ledger, AccountsMigrated, CreatorPayoutDayClosed, CreatorUpdated, DayLedger, EventRecord,
+    HonoraryLiquidityLocked, HonoraryPositionAdded, HonoraryPositionInitialized, InvestorCapped,
+    InvestorPaid, InvestorPayoutPage, InvestorSkipped, PayoutDestinationSet, PoolDisabled,
+    QuoteFeesClaimed, RemainderSettled, RewardClaimed, RewardInvestorPaid, RewardInvestorSkipped,
+    RewardPayoutPage, RewardRoundClosed, TollgateEvent,
+};
+
+/// Output format of the exported files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Jsonl,
+    Csv,
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "csv" => Ok(ExportFormat::Csv),
+            _ => Err(anyhow!(
+                "Unknown export format {}, expected jsonl or csv",
+                value
+            )),
+        }
+    }
+}
+
+/// Flat representation of a record as a CSV row.
+///
+/// Every value is a number, a base58 pubkey or a snake case reason, so no
+/// quoting is needed.
+pub trait CsvRow {
+    fn csv_header() -> Vec<&'static str>;
+    fn csv_row(&self) -> Vec<String>;
+}
+
+impl CsvRow for HonoraryPositionInitialized {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "progress",
+            "pool",
+            "pool_cfg",
+            "position",
+            "owner",
+            "base_mint",
+            "quote_mint",
+            "investor_fee_share_bps",
+            "min_payout_lamports",
+            "daily_cap",
+            "y0",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.progress.to_string(),
+            self.pool.to_string(),
+            self.pool_cfg.to_string(),
+            self.position.to_string(),
+            self.owner.to_string(),
+            self.base_mint.to_string(),
+            self.quote_mint.to_string(),
+            self.investor_fee_share_bps.to_string(),
+            self.min_payout_lamports.to_string(),
+            self.daily_cap
+                .map(|cap| cap.to_string())
+                .unwrap_or_default(),
+            self.y0.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for HonoraryPositionAdded {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "pool",
+            "position",
+            "owner",
+            "index",
+            "position_count",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.pool.to_string(),
+            self.position.to_string(),
+            self.owner.to_string(),
+            self.index.to_string(),
+            self.position_count.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for HonoraryLiquidityLocked {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "pool",
+            "position",
+            "owner",
+            "depositor",
+            "liquidity_delta",
+            "base_amount",
+            "quote_amount",
+            "permanent_locked_liquidity",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.pool.to_string(),
+            self.position.to_string(),
+            self.owner.to_string(),
+            self.depositor.to_string(),
+            self.liquidity_delta.to_string(),
+            self.base_amount.to_string(),
+            self.quote_amount.to_string(),
+            self.permanent_locked_liquidity.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for QuoteFeesClaimed {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "progress",
+            "pool",
+            "position",
+            "owner",
+            "base_fee_claimed",
+            "quote_fee_claimed",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.progress.to_string(),
+            self.pool.to_string(),
+            self.position.to_string(),
+            self.owner.to_string(),
+            self.base_fee_claimed.to_string(),
+            self.quote_fee_claimed.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for PoolDisabled {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "progress",
+            "pool",
+            "position",
+            "pool_status",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.progress.to_string(),
+            self.pool.to_string(),
+            self.position.to_string(),
+            self.pool_status.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for RemainderSettled {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "progress",
+            "remainder",
+            "swept",
+            "rolled_over",
+            "reserved",
+            "released",
+            "reserve",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.progress.to_string(),
+            self.remainder.to_string(),
+            self.swept.to_string(),
+            self.rolled_over.to_string(),
+            self.reserved.to_string(),
+            self.released.to_string(),
+            self.reserve.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for InvestorPayoutPage {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "progress",
+            "pool",
+            "position",
+            "owner",
+            "cursor",
+            "investors",
+            "page_start",
+            "page_end",
+            "payout",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.progress.to_string(),
+            self.pool.to_string(),
+            self.position.to_string(),
+            self.owner.to_string(),
+            self.cursor.to_string(),
+            self.investors.to_string(),
+            self.page_start.to_string(),
+            self.page_end.to_string(),
+            self.payout.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for InvestorCapped {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault", "policy", "stream", "index", "locked", "share", "cap",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.stream.to_string(),
+            self.index.to_string(),
+            self.locked.to_string(),
+            self.share.to_string(),
+            self.cap.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for RewardClaimed {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "pool",
+            "position",
+            "owner",
+            "reward_index",
+            "reward_mint",
+            "reward_claimed",
+            "carry",
+            "reward_pool",
+            "locked_total",
+            "investor_pool",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.pool.to_string(),
+            self.position.to_string(),
+            self.owner.to_string(),
+            self.reward_index.to_string(),
+            self.reward_mint.to_string(),
+            self.reward_claimed.to_string(),
+            self.carry.to_string(),
+            self.reward_pool.to_string(),
+            self.locked_total.to_string(),
+            self.investor_pool.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for RewardInvestorPaid {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "reward_index",
+            "reward_mint",
+            "stream",
+            "index",
+            "recipient",
+            "reward_account",
+            "locked",
+            "amount",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.reward_index.to_string(),
+            self.reward_mint.to_string(),
+            self.stream.to_string(),
+            self.index.to_string(),
+            self.recipient.to_string(),
+            self.reward_account.to_string(),
+            self.locked.to_string(),
+            self.amount.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for RewardInvestorSkipped {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "reward_index",
+            "reward_mint",
+            "stream",
+            "index",
+            "reason",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.reward_index.to_string(),
+            self.reward_mint.to_string(),
+            self.stream.to_string(),
+            self.index.to_string(),
+            self.reason.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for RewardPayoutPage {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "reward_index",
+            "reward_mint",
+            "cursor",
+            "investors",
+            "page_start",
+            "page_end",
+            "payout",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.reward_index.to_string(),
+            self.reward_mint.to_string(),
+            self.cursor.to_string(),
+            self.investors.to_string(),
+            self.page_start.to_string(),
+            self.page_end.to_string(),
+            self.payout.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for RewardRoundClosed {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "reward_index",
+            "reward_mint",
+            "reward_pool",
+            "investor_payouts",
+            "creator_payout",
+            "carry",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.reward_index.to_string(),
+            self.reward_mint.to_string(),
+            self.reward_pool.to_string(),
+            self.investor_payouts.to_string(),
+            self.creator_payout.to_string(),
+            self.carry.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for CreatorPayoutDayClosed {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "progress",
+            "pool",
+            "position",
+            "owner",
+            "timestamp",
+            "total_distributed",
+            "creator_payout",
+            "carry",
+            "distributable",
+            "investor_payouts",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.progress.to_string(),
+            self.pool.to_string(),
+            self.position.to_string(),
+            self.owner.to_string(),
+            self.timestamp.to_string(),
+            self.total_distributed.to_string(),
+            self.creator_payout.to_string(),
+            self.carry.to_string(),
+            self.distributable.to_string(),
+            self.investor_payouts.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for InvestorPaid {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "stream",
+            "index",
+            "recipient",
+            "ata",
+            "locked",
+            "share_bps",
+            "amount",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.stream.to_string(),
+            self.index.to_string(),
+            self.recipient.to_string(),
+            self.ata.to_string(),
+            self.locked.to_string(),
+            self.share_bps.to_string(),
+            self.amount.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for InvestorSkipped {
+    fn csv_header() -> Vec<&'static str> {
+        vec!["vault", "policy", "stream", "index", "reason"]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.stream.to_string(),
+            self.index.to_string(),
+            self.reason.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for CreatorUpdated {
+    fn csv_header() -> Vec<&'static str> {
+        vec!["vault", "policy", "previous_creator", "creator"]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.previous_creator.to_string(),
+            self.creator.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for PayoutDestinationSet {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "schema_version",
+            "vault",
+            "stream",
+            "recipient",
+            "destination",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.schema_version.to_string(),
+            self.vault.to_string(),
+            self.stream.to_string(),
+            self.recipient.to_string(),
+            self.destination.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for AccountsMigrated {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "policy",
+            "progress",
+            "policy_from_version",
+            "policy_version",
+            "progress_from_version",
+            "progress_version",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.policy.to_string(),
+            self.progress.to_string(),
+            self.policy_from_version.to_string(),
+            self.policy_version.to_string(),
+            self.progress_from_version.to_string(),
+            self.progress_version.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for DayLedger {
+    fn csv_header() -> Vec<&'static str> {
+        vec![
+            "vault",
+            "day",
+            "first_slot",
+            "last_slot",
+            "fees_claimed",
+            "investor_payouts",
+            "pages",
+            "investors_paid",
+            "investors_skipped",
+            "distributable",
+            "creator_payout",
+            "carry",
+            "total_distributed",
+            "closed_ts",
+        ]
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.vault.to_string(),
+            self.day.to_string(),
+            self.first_slot.to_string(),
+            self.last_slot.to_string(),
+            self.fees_claimed.to_string(),
+            self.investor_payouts.to_string(),
+            self.pages.to_string(),
+            self.investors_paid.to_string(),
+            self.investors_skipped.to_string(),
+            self.distributable.to_string(),
+            self.creator_payout.to_string(),
+            self.carry.to_string(),
+            self.total_distributed.to_string(),
+            self.closed_ts.map(|ts| ts.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+impl TollgateEvent {
+    /// Returns the CSV header of the event kind.
+    pub fn csv_header(&self) -> Vec<&'static str> {
+        match self {
+            TollgateEvent::HonoraryPositionInitialized(_) => {
+                HonoraryPositionInitialized::csv_header()
+            }
+            TollgateEvent::HonoraryPositionAdded(_) => HonoraryPositionAdded::csv_header(),
+            TollgateEvent::HonoraryLiquidityLocked(_) => HonoraryLiquidityLocked::csv_header(),
+            TollgateEvent::QuoteFeesClaimed(_) => QuoteFeesClaimed::csv_header(),
+            TollgateEvent::PoolDisabled(_) => PoolDisabled::csv_header(),
+            TollgateEvent::RemainderSettled(_) => RemainderSettled::csv_header(),
+            TollgateEvent::InvestorPayoutPage(_) => InvestorPayoutPage::csv_header(),
+            TollgateEvent::InvestorPaid(_) => InvestorPaid::csv_header(),
+            TollgateEvent::InvestorSkipped(_) => InvestorSkipped::csv_header(),
+            TollgateEvent::InvestorCapped(_) => InvestorCapped::csv_header(),
+            TollgateEvent::RewardClaimed(_) => RewardClaimed::csv_header(),
+            TollgateEvent::RewardInvestorPaid(_) => RewardInvestorPaid::csv_header(),
+            TollgateEvent::RewardInvestorSkipped(_) => RewardInvestorSkipped::csv_header(),
+            TollgateEvent::RewardPayoutPage(_) => RewardPayoutPage::csv_header(),
+            TollgateEvent::RewardRoundClosed(_) => RewardRoundClosed::csv_header(),
+            TollgateEvent::CreatorPayoutDayClosed(_) => CreatorPayoutDayClosed::csv_header(),
+            TollgateEvent::CreatorUpdated(_) => CreatorUpdated::csv_header(),
+            TollgateEvent::PayoutDestinationSet(_) => PayoutDestinationSet::csv_header(),
+            TollgateEvent::AccountsMigrated(_) => AccountsMigrated::csv_header(),
+        }
+    }
+
+    pub fn csv_row(&self) -> Vec<String> {
+        match self {
+            TollgateEvent::HonoraryPositionInitialized(e) => e.csv_row(),
+            TollgateEvent::HonoraryPositionAdded(e) => e.csv_row(),
+            TollgateEvent::HonoraryLiquidityLocked(e) => e.csv_row(),
+            TollgateEvent::QuoteFeesClaimed(e) => e.csv_row(),
+            TollgateEvent::PoolDisabled(e) => e.csv_row(),
+            TollgateEvent::RemainderSettled(e) => e.csv_row(),
+            TollgateEvent::InvestorPayoutPage(e) => e.csv_row(),
+            TollgateEvent::InvestorPaid(e) => e.csv_row(),
+            TollgateEvent::InvestorSkipped(e) => e.csv_row(),
+            TollgateEvent::InvestorCapped(e) => e.csv_row(),
+            TollgateEvent::RewardClaimed(e) => e.csv_row(),
+            TollgateEvent::RewardInvestorPaid(e) => e.csv_row(),
+            TollgateEvent::RewardInvestorSkipped(e) => e.csv_row(),
+            TollgateEvent::RewardPayoutPage(e) => e.csv_row(),
+            TollgateEvent::RewardRoundClosed(e) => e.csv_row(),
+            TollgateEvent::CreatorPayoutDayClosed(e) => e.csv_row(),
+            TollgateEvent::CreatorUpdated(e) => e.csv_row(),
+            TollgateEvent::PayoutDestinationSet(e) => e.csv_row(),
+            TollgateEvent::AccountsMigrated(e) => e.csv_row(),
+        }
+    }
+}
+
+/// Columns written before the event columns of every event CSV row.
+const RECORD_CSV_HEADER: [&str; 4] = ["signature", "slot", "block_time", "index"];
+
+/// Writes one JSON document per line.
+pub fn write_jsonl<T: Serialize>(mut writer: impl Write, items: &[T]) -> Result<()> {
+    for item in items {
+        serde_json::to_writer(&mut writer, item)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Writes the header and one row per item.
+pub fn write_csv<T: CsvRow>(mut writer: impl Write, items: &[T]) -> Result<()> {
+    writeln!(writer, "{}", T::csv_header().join(","))?;
+    for item in items {
+        writeln!(writer, "{}", item.csv_row().join(","))?;
+    }
+    Ok(())
+}
+
+/// Writes event records of a single kind, prefixed with their transaction columns.
+pub fn write_event_csv(mut writer: impl Write, records: &[&EventRecord]) -> Result<()> {
+    let Some(first) = records.first() else {
+        return Ok(());
+    };
+    let mut header = RECORD_CSV_HEADER.to_vec();
+    header.extend(first.event.csv_header());
+    writeln!(writer, "{}", header.join(","))?;
+
+    for record in records {
+        let mut row = vec![
+            record.signature.clone(),
+            record.slot.to_string(),
+            record
+                .block_time
+                .map(|ts| ts.to_string())
+                .unwrap_or_default(),
+            record.index.to_string(),
+        ];
+        row.extend(record.event.csv_row());
+        writeln!(writer, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+/// Writes the events and the per-day ledger of a vault into `out_dir`.
+///
+/// JSONL writes `<vault>.events.jsonl` and `<vault>.ledger.jsonl`; CSV writes
+/// one `<vault>.<Event>.csv` per event kind and `<vault>.ledger.csv`.
+/// Returns the written paths.
+pub fn export_vault(
+    out_dir: &Path,
+    vault: &Pubkey,
+    records: &[EventRecord],
+    format: ExportFormat,
+) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+    let ledger = build_ledger(vault, records);
+    let records: Vec<&EventRecord> = records
+        .iter()
+        .filter(|r| r.event.vault() == *vault)
+        .collect();
+    let mut paths = vec![];
+
+    match format {
+        ExportFormat::Jsonl => {
+            let path = out_dir.join(format!("{}.events.jsonl", vault));
+            write_jsonl(BufWriter::new(create(&path)?), &records)?;
+            paths.push(path);
+
+            let path = out_dir.join(format!("{}.ledger.jsonl", vault));
+            write_jsonl(BufWriter::new(create(&path)?), &ledger)?;
+            paths.push(path);
+        }
+        ExportFormat::Csv => {
+            let mut by_event: BTreeMap<&str, Vec<&EventRecord>> = BTreeMap::new();
+            for record in &records {
+                by_event
+                    .entry(record.event.name())
+                    .or_default()
+                    .push(record);
+            }
+            for (name, records) in by_event {
+                let path = out_dir.join(format!("{}.{}.csv", vault, name));
+                write_event_csv(BufWriter::new(create(&path)?), &records)?;
+                paths.push(path);
+            }
+
+            let path = out_dir.join(format!("{}.ledger.csv", vault));
+            write_csv(BufWriter::new(create(&path)?), &ledger)?;
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+fn create(path: &Path) -> Result<File> {
+    File::create(path).with_context(|| format!("Failed to create {}", path.display()))
+}
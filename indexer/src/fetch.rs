@@ -0,0 +1,127 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    solana_client::{
+        rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+        rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey, signature::Signature},
+};
+use anyhow::{anyhow, Result};
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, EncodedTransaction, UiInstruction, UiMessage,
+    UiTransactionEncoding, UiTransactionStatusMeta,
+};
+use tollgate_client::pda::get_progress_pda;
+
+use crate::{records_from_inner_instructions, EventRecord};
+
+fn parse_pubkey(key: &str) -> Result<Pubkey> {
+    Pubkey::from_str(key).map_err(|e| anyhow!("Invalid account key {}: {}", key, e))
+}
+
+/// Returns the account keys of a JSON encoded transaction, its static keys
+/// followed by the addresses loaded from lookup tables.
+fn get_account_keys(
+    transaction: &EncodedTransaction,
+    meta: &UiTransactionStatusMeta,
+) -> Result<Vec<Pubkey>> {
+    let EncodedTransaction::Json(transaction) = transaction else {
+        return Err(anyhow!("Transaction is not JSON encoded"));
+    };
+    let UiMessage::Raw(message) = &transaction.message else {
+        return Err(anyhow!("Transaction message is not raw"));
+    };
+    let mut keys = message.account_keys.clone();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        keys.extend(loaded.writable.iter().cloned());
+        keys.extend(loaded.readonly.iter().cloned());
+    }
+    keys.iter().map(|key| parse_pubkey(key)).collect()
+}
+
+/// Returns the inner instructions of a transaction, in execution order.
+fn get_inner_instructions(meta: &UiTransactionStatusMeta) -> Result<Vec<CompiledInstruction>> {
+    let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions else {
+        return Ok(vec![]);
+    };
+    inner_instructions
+        .iter()
+        .flat_map(|inner| &inner.instructions)
+        .filter_map(|instruction| match instruction {
+            UiInstruction::Compiled(compiled) => Some(compiled),
+            UiInstruction::Parsed(_) => None,
+        })
+        .map(|compiled| {
+            Ok(CompiledInstruction {
+                program_id_index: compiled.program_id_index,
+                accounts: compiled.accounts.clone(),
+                data: bs58::decode(&compiled.data)
+                    .into_vec()
+                    .map_err(|e| anyhow!("Invalid instruction data: {}", e))?,
+            })
+        })
+        .collect()
+}
+
+/// Fetches the events of a vault from the transactions touching its progress
+/// account, oldest first.
+///
+/// Signatures are walked back from the latest one, or from `before`, and stop
+/// at `until` when given. Failed transactions are skipped.
+pub fn fetch_vault_records(
+    rpc: &RpcClient,
+    vault: &Pubkey,
+    before: Option<Signature>,
+    until: Option<Signature>,
+) -> Result<Vec<EventRecord>> {
+    let progress = get_progress_pda(*vault).0;
+    let mut signatures = vec![];
+    let mut before = before;
+
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(
+            &progress,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: None,
+                commitment: Some(rpc.commitment()),
+            },
+        )?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(
+            Signature::from_str(&last.signature)
+                .map_err(|e| anyhow!("Invalid signature {}: {}", last.signature, e))?,
+        );
+        signatures.extend(page.into_iter().filter(|s| s.err.is_none()));
+    }
+
+    let mut records = vec![];
+    for status in signatures.into_iter().rev() {
+        let signature = Signature::from_str(&status.signature)
+            .map_err(|e| anyhow!("Invalid signature {}: {}", status.signature, e))?;
+        let transaction = rpc.get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(rpc.commitment()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        let Some(meta) = &transaction.transaction.meta else {
+            continue;
+        };
+        let account_keys = get_account_keys(&transaction.transaction.transaction, meta)?;
+        records.extend(records_from_inner_instructions(
+            &status.signature,
+            transaction.slot,
+            transaction.block_time,
+            &account_keys,
+            &get_inner_instructions(meta)?,
+        ));
+    }
+    Ok(records)
+}
@@ -0,0 +1,126 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::{EventRecord, TollgateEvent};
+
+/// Fees and payouts of a single distribution day of a vault.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayLedger {
+    #[serde_as(as = "DisplayFromStr")]
+    pub vault: Pubkey,
+    pub day: u32,               // Index of the day among the indexed days
+    pub first_slot: u64,        // Slot of the first event of the day
+    pub last_slot: u64,         // Slot of the last event of the day
+    pub fees_claimed: u64,      // Quote fees claimed from the position
+    pub investor_payouts: u64,  // Sum of the investor page payouts
+    pub pages: u32,             // Number of investor pages paid
+    pub investors_paid: u32,    // Investors paid by the payout pass
+    pub investors_skipped: u32, // Investors left unpaid by the payout pass
+    pub distributable: u64,     // Distributable amount, zero until the day closes
+    pub creator_payout: u64,    // Creator share, zero until the day closes
+    pub carry: u64,             // Carry left after the day closes
+    pub total_distributed: u64, // Total distributed, zero until the day closes
+    pub closed_ts: Option<i64>, // Close timestamp, `None` while the day is open
+}
+
+impl DayLedger {
+    fn open(vault: Pubkey, day: u32, slot: u64) -> Self {
+        Self {
+            vault,
+            day,
+            first_slot: slot,
+            last_slot: slot,
+            fees_claimed: 0,
+            investor_payouts: 0,
+            pages: 0,
+            investors_paid: 0,
+            investors_skipped: 0,
+            distributable: 0,
+            creator_payout: 0,
+            carry: 0,
+            total_distributed: 0,
+            closed_ts: None,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed_ts.is_some()
+    }
+}
+
+/// Rebuilds the per-day ledger of a vault from its records, in chain order.
+///
+/// A day spans every event up to its `CreatorPayoutDayClosed` event; events
+/// after the last close form a trailing open day.
+pub fn build_ledger(vault: &Pubkey, records: &[EventRecord]) -> Vec<DayLedger> {
+    let mut days = vec![];
+    let mut current: Option<DayLedger> = None;
+
+    for record in records.iter().filter(|r| r.event.vault() == *vault) {
+        if matches!(
+            record.event,
+            TollgateEvent::HonoraryPositionInitialized(_)
+                | TollgateEvent::HonoraryPositionAdded(_)
+                | TollgateEvent::HonoraryLiquidityLocked(_)
+                | TollgateEvent::RewardClaimed(_)
+                | TollgateEvent::RewardInvestorPaid(_)
+                | TollgateEvent::RewardInvestorSkipped(_)
+                | TollgateEvent::RewardPayoutPage(_)
+                | TollgateEvent::RewardRoundClosed(_)
+                | TollgateEvent::PoolDisabled(_)
+                | TollgateEvent::CreatorUpdated(_)
+                | TollgateEvent::RemainderSettled(_)
+                | TollgateEvent::InvestorCapped(_)
+                | TollgateEvent::PayoutDestinationSet(_)
+                | TollgateEvent::AccountsMigrated(_)
+        ) {
+            continue;
+        }
+        let day =
+            current.get_or_insert_with(|| DayLedger::open(*vault, days.len() as u32, record.slot));
+        day.last_slot = record.slot;
+
+        match &record.event {
+            TollgateEvent::QuoteFeesClaimed(e) => {
+                day.fees_claimed = day.fees_claimed.saturating_add(e.quote_fee_claimed);
+            }
+            TollgateEvent::InvestorPayoutPage(e) => {
+                day.investor_payouts = day.investor_payouts.saturating_add(e.payout);
+                day.pages += 1;
+            }
+            TollgateEvent::InvestorPaid(_) => {
+                day.investors_paid += 1;
+            }
+            TollgateEvent::InvestorSkipped(_) => {
+                day.investors_skipped += 1;
+            }
+            TollgateEvent::CreatorPayoutDayClosed(e) => {
+                day.distributable = e.distributable;
+                day.creator_payout = e.creator_payout;
+                day.carry = e.carry;
+                day.total_distributed = e.total_distributed;
+                day.closed_ts = Some(e.timestamp);
+                days.extend(current.take());
+            }
+            TollgateEvent::HonoraryPositionInitialized(_)
+            | TollgateEvent::HonoraryPositionAdded(_)
+            | TollgateEvent::HonoraryLiquidityLocked(_)
+            | TollgateEvent::RewardClaimed(_)
+            | TollgateEvent::RewardInvestorPaid(_)
+            | TollgateEvent::RewardInvestorSkipped(_)
+            | TollgateEvent::RewardPayoutPage(_)
+            | TollgateEvent::RewardRoundClosed(_)
+            | TollgateEvent::PoolDisabled(_)
+            | TollgateEvent::CreatorUpdated(_)
+            | TollgateEvent::RemainderSettled(_)
+            | TollgateEvent::InvestorCapped(_)
+            | TollgateEvent::PayoutDestinationSet(_)
+            | TollgateEvent::AccountsMigrated(_) => {}
+        }
+    }
+
+    days.extend(current);
+    days
+}
@@ -0,0 +1,17 @@
+//! Indexer decoding Tollgate events into structured records.
+//!
+//! Events are parsed from the event-CPI inner instructions of transactions
+//! into [`EventRecord`]s, exported as JSONL or CSV per vault, and folded into
+//! a per-day [`DayLedger`].
+
+pub mod events;
+pub mod export;
+pub mod fetch;
+pub mod ledger;
+pub mod record;
+
+pub use events::*;
+pub use export::*;
+pub use fetch::*;
+pub use ledger::*;
+pub use record::*;
@@ -0,0 +1,68 @@
+use std::{path::PathBuf, str::FromStr};
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+    Cluster,
+};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use tollgate_indexer::{export_vault, fetch_vault_records, ExportFormat};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "tollgate-indexer",
+    version,
+    about = "Export Tollgate events and daily ledgers"
+)]
+struct Cli {
+    /// RPC URL or moniker (localnet, devnet, mainnet)
+    #[arg(short, long, default_value = "localnet")]
+    url: String,
+
+    /// Vault pubkeys to index
+    #[arg(long, required = true)]
+    vault: Vec<String>,
+
+    /// Output format (jsonl or csv)
+    #[arg(short, long, default_value = "jsonl")]
+    format: String,
+
+    /// Output directory
+    #[arg(short, long, default_value = ".")]
+    out: PathBuf,
+
+    /// Only index transactions older than this signature
+    #[arg(long)]
+    before: Option<String>,
+
+    /// Only index transactions newer than this signature
+    #[arg(long)]
+    until: Option<String>,
+}
+
+fn parse_signature(value: &str) -> Result<Signature> {
+    Signature::from_str(value).map_err(|e| anyhow!("Invalid signature {}: {}", value, e))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let cluster = Cluster::from_str(&cli.url).map_err(|e| anyhow!("Invalid url: {}", e))?;
+    let rpc =
+        RpcClient::new_with_commitment(cluster.url().to_string(), CommitmentConfig::confirmed());
+    let format = ExportFormat::from_str(&cli.format)?;
+    let before = cli.before.as_deref().map(parse_signature).transpose()?;
+    let until = cli.until.as_deref().map(parse_signature).transpose()?;
+
+    for vault in &cli.vault {
+        let vault =
+            Pubkey::from_str(vault).map_err(|e| anyhow!("Invalid pubkey {}: {}", vault, e))?;
+        let records = fetch_vault_records(&rpc, &vault, before, until)?;
+        println!("Vault {}: {} events", vault, records.len());
+        for path in export_vault(&cli.out, &vault, &records, format)? {
+            println!("  wrote {}", path.display());
+        }
+    }
+    Ok(())
+}
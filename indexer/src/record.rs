@@ -0,0 +1,36 @@
+use anchor_client::solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_inner_instructions, TollgateEvent};
+
+/// An event and the transaction it was emitted in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub signature: String,       // Transaction signature
+    pub slot: u64,               // Slot of the transaction
+    pub block_time: Option<i64>, // Block time, when known
+    pub index: u32,              // Position of the event within the transaction
+    #[serde(flatten)]
+    pub event: TollgateEvent,
+}
+
+/// Decodes the events of a transaction into records.
+pub fn records_from_inner_instructions(
+    signature: &str,
+    slot: u64,
+    block_time: Option<i64>,
+    account_keys: &[Pubkey],
+    inner_instructions: &[CompiledInstruction],
+) -> Vec<EventRecord> {
+    parse_inner_instructions(account_keys, inner_instructions)
+        .into_iter()
+        .enumerate()
+        .map(|(index, event)| EventRecord {
+            signature: signature.to_string(),
+            slot,
+            block_time,
+            index: index as u32,
+            event,
+        })
+        .collect()
+}
@@ -0,0 +1,106 @@
+use std::{fs, path::Path, str::FromStr, time::Duration};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+/// Vault cranked by the keeper and how pages are sent.
+#[derive(Debug, Clone)]
+pub struct KeeperConfig {
+    pub vault: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub streams: Vec<Pubkey>,    // Investor streams, cranked in stream order
+    pub page_size: u32,          // Number of investors per crank instruction
+    pub with_init: bool,         // Use crank_with_init to create missing investor ATAs
+    pub compute_unit_limit: u32, // Compute unit limit of each crank transaction
+    pub max_retries: u32,        // Retries of a transaction that did not land
+    pub retry_backoff: Duration, // Delay before the first retry, doubled on each retry
+}
+
+impl KeeperConfig {
+    pub const DEFAULT_PAGE_SIZE: u32 = 10;
+    pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+    pub const DEFAULT_MAX_RETRIES: u32 = 5;
+    pub const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+
+    pub fn new(
+        vault: Pubkey,
+        pool: Pubkey,
+        position_nft_mint: Pubkey,
+        quote_mint: Pubkey,
+        streams: Vec<Pubkey>,
+    ) -> Self {
+        Self {
+            vault,
+            pool,
+            position_nft_mint,
+            quote_mint,
+            streams,
+            page_size: Self::DEFAULT_PAGE_SIZE,
+            with_init: false,
+            compute_unit_limit: Self::DEFAULT_COMPUTE_UNIT_LIMIT,
+            max_retries: Self::DEFAULT_MAX_RETRIES,
+            retry_backoff: Duration::from_millis(Self::DEFAULT_RETRY_BACKOFF_MS),
+        }
+    }
+
+    /// Reads a keeper config from a TOML file.
+    ///
+    /// ```toml
+    /// vault = "<vault pubkey>"
+    /// pool = "<pool pubkey>"
+    /// position_nft_mint = "<position nft mint pubkey>"
+    /// quote_mint = "<quote mint pubkey>"
+    /// streams = ["<stream pubkey>", "..."]
+    /// page_size = 10
+    /// with_init = false
+    /// compute_unit_limit = 1400000
+    /// max_retries = 5
+    /// retry_backoff_ms = 500
+    /// ```
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        let file: KeeperConfigFile = toml::from_str(&content)
+            .with_context(|| format!("Invalid config {}", path.display()))?;
+
+        let mut config = Self::new(
+            parse_pubkey(&file.vault)?,
+            parse_pubkey(&file.pool)?,
+            parse_pubkey(&file.position_nft_mint)?,
+            parse_pubkey(&file.quote_mint)?,
+            file.streams
+                .iter()
+                .map(|s| parse_pubkey(s))
+                .collect::<Result<_>>()?,
+        );
+        config.page_size = file.page_size.unwrap_or(config.page_size);
+        config.with_init = file.with_init.unwrap_or(config.with_init);
+        config.compute_unit_limit = file.compute_unit_limit.unwrap_or(config.compute_unit_limit);
+        config.max_retries = file.max_retries.unwrap_or(config.max_retries);
+        if let Some(ms) = file.retry_backoff_ms {
+            config.retry_backoff = Duration::from_millis(ms);
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KeeperConfigFile {
+    vault: String,
+    pool: String,
+    position_nft_mint: String,
+    quote_mint: String,
+    streams: Vec<String>,
+    page_size: Option<u32>,
+    with_init: Option<bool>,
+    compute_unit_limit: Option<u32>,
+    max_retries: Option<u32>,
+    retry_backoff_ms: Option<u64>,
+}
+
+pub fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|e| anyhow!("Invalid pubkey {}: {}", value, e))
+}
@@ -0,0 +1,414 @@
+use std::{path::PathBuf, thread::sleep, time::Duration};
+
+use anchor_client::solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey,
+    signature::Keypair, signer::Signer,
+};
+use anyhow::{anyhow, ensure, Context, Result};
+use tollgate::{
+    error::TollgateError,
+    state::{DayState, Policy, Progress},
+};
+use tollgate_client::{
+    decode_payout_redirect, decode_policy, decode_position_registry, decode_progress, next_day_ts,
+    pda::{get_payout_redirect_pda, get_policy_pda, get_position_registry_pda, get_progress_pda},
+    resume_cursor, sort_investors, CrankBuilder, ExtraPosition, InvestorStream, Page,
+};
+
+use crate::{KeeperConfig, KeeperRpc, KeeperState, PassPhase, SendError, SendOutcome};
+
+/// Result of a single keeper pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TickOutcome {
+    /// The current day is closed, the next one starts at `next_day_ts`.
+    Idle { next_day_ts: i64 },
+    /// Every remaining page was sent and the day is closed.
+    DayClosed { day_ts: i64, pages: u32 },
+    /// The crank stopped advancing the cursor, so the remaining pages must
+    /// wait for the next pass.
+    Stalled {
+        day_ts: i64,
+        cursor: u32,
+        pages: u32,
+    },
+}
+
+/// Drives the daily crank of a single vault.
+pub struct Keeper<R: KeeperRpc> {
+    rpc: R,
+    payer: Keypair,
+    config: KeeperConfig,
+    state: KeeperState,
+    state_path: Option<PathBuf>,
+}
+
+impl<R: KeeperRpc> Keeper<R> {
+    pub fn new(rpc: R, payer: Keypair, config: KeeperConfig) -> Self {
+        Self {
+            rpc,
+            payer,
+            config,
+            state: KeeperState::default(),
+            state_path: None,
+        }
+    }
+
+    /// Loads the state from `path` and saves it there after every landed page.
+    pub fn state_path(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        self.state = KeeperState::load(&path)?;
+        self.state_path = Some(path);
+        Ok(self)
+    }
+
+    pub fn state(&self) -> &KeeperState {
+        &self.state
+    }
+
+    pub fn config(&self) -> &KeeperConfig {
+        &self.config
+    }
+
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    /// Runs passes forever, waiting `poll_interval` between them.
+    pub fn run(&mut self, poll_interval: Duration) -> ! {
+        loop {
+            match self.tick() {
+                Ok(TickOutcome::Idle { .. }) => {}
+                Ok(outcome) => println!("Keeper::{:?}", outcome),
+                Err(err) => eprintln!("Keeper::Pass failed: {:#}", err),
+            }
+            sleep(poll_interval);
+        }
+    }
+
+    /// Sends every page that can be cranked right now.
+    pub fn tick(&mut self) -> Result<TickOutcome> {
+        let progress = self.fetch_progress()?;
+        let now_ts = self.rpc.get_unix_timestamp()?;
+        let Some(progress_cursor) = resume_cursor(&progress, now_ts) else {
+            return Ok(TickOutcome::Idle {
+                next_day_ts: next_day_ts(&progress),
+            });
+        };
+
+        let policy = self.fetch_policy()?;
+        let investor_count = policy.investor_count;
+        ensure!(
+            self.config.streams.len() >= investor_count as usize,
+            "Config lists {} streams but the policy expects {} investors",
+            self.config.streams.len(),
+            investor_count
+        );
+
+        // Cap passes repeat over the same pages, so a pass is told apart by
+        // its phase and the cap passes run before it
+        let mut pass = if progress.is_new_day(now_ts) {
+            (PassPhase::Snapshot, 0)
+        } else {
+            progress_pass(&progress)
+        };
+
+        // Within a day the persisted cursor is trusted over a possibly lagging
+        // RPC node, a wrong one is corrected by the program below.
+        let state_pass = (self.state.day_ts == progress.last_distribution_ts
+            && self.state.cursor < investor_count)
+            .then(|| self.state.pass());
+        let mut cursor = match state_pass {
+            // A new day restarts with the snapshot pass
+            _ if progress.is_new_day(now_ts) => 0,
+            Some(state_pass) if state_pass > pass => self.state.cursor,
+            Some(state_pass) if state_pass == pass => {
+                if self.state.cursor < progress_cursor {
+                    println!(
+                        "Keeper::Pages from {} already cranked, resuming at {}",
+                        self.state.cursor, progress_cursor
+                    );
+                }
+                self.state.cursor.max(progress_cursor)
+            }
+            _ => progress_cursor,
+        };
+
+        let investors = self.fetch_investors(investor_count as usize)?;
+        let builder = self.crank_builder(&policy)?;
+        let mut pages = 0;
+
+        // A new day is opened by claiming the fees of every position
+        if progress.is_new_day(now_ts) {
+            let positions = self.fetch_extra_positions()?;
+            let outcome = self
+                .send_with_retry(&[
+                    ComputeBudgetInstruction::set_compute_unit_limit(
+                        self.config.compute_unit_limit,
+                    ),
+                    builder.clone().positions(positions).claim_fees(),
+                ])
+                .context("Failed to claim the position fees")?;
+            pages += 1;
+            self.state.last_signature = Some(outcome.signature);
+
+            // A distributable below the minimum payout closes the day at once
+            let progress = self.fetch_progress()?;
+            self.state.day_ts = progress.last_distribution_ts;
+            if progress.day_state == DayState::Closed {
+                self.state.days_closed += 1;
+                self.save_state()?;
+                return Ok(TickOutcome::DayClosed {
+                    day_ts: progress.last_distribution_ts,
+                    pages,
+                });
+            }
+        }
+
+        while cursor < investor_count {
+            let page = Page {
+                cursor,
+                end: cursor
+                    .saturating_add(self.config.page_size.max(1))
+                    .min(investor_count),
+            };
+            let page_builder = builder
+                .clone()
+                .cursor(page.cursor)
+                .investors(investors[page.range()].to_vec());
+            let crank_instruction = if self.config.with_init {
+                page_builder.crank_with_init()
+            } else {
+                page_builder.crank()
+            };
+
+            match self.send_with_retry(&[
+                ComputeBudgetInstruction::set_compute_unit_limit(self.config.compute_unit_limit),
+                crank_instruction,
+            ]) {
+                Ok(outcome) => {
+                    pages += 1;
+                    self.state.last_signature = Some(outcome.signature);
+                }
+                Err(err)
+                    if err.custom_code()
+                        == Some(error_code(TollgateError::PaginationCursorTooLarge)) =>
+                {
+                    println!(
+                        "Keeper::Cursor {} ahead of progress, resyncing",
+                        page.cursor
+                    );
+                }
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("Failed to crank page {}", page.cursor))
+                }
+            }
+
+            // The program is the source of truth for the cursor, whatever the
+            // outcome of the page.
+            let progress = self.fetch_progress()?;
+            let progress_pass = progress_pass(&progress);
+            self.state.day_ts = progress.last_distribution_ts;
+            self.state.cursor = progress.cursor;
+            (self.state.phase, self.state.cap_passes) = progress_pass;
+
+            if progress.day_state == DayState::Closed {
+                self.state.days_closed += 1;
+                self.save_state()?;
+                return Ok(TickOutcome::DayClosed {
+                    day_ts: progress.last_distribution_ts,
+                    pages,
+                });
+            }
+            self.save_state()?;
+
+            // Completing a pass rewinds the cursor for the next one
+            if progress.cursor == page.cursor && progress_pass == pass {
+                return Ok(TickOutcome::Stalled {
+                    day_ts: progress.last_distribution_ts,
+                    cursor: progress.cursor,
+                    pages,
+                });
+            }
+            cursor = progress.cursor;
+            pass = progress_pass;
+        }
+
+        Err(anyhow!(
+            "Cursor {} reached the investor count but the day is not closed",
+            cursor
+        ))
+    }
+
+    /// Sends the instructions, retrying with exponential backoff while the
+    /// transaction does not land.
+    fn send_with_retry(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> std::result::Result<SendOutcome, SendError> {
+        let mut attempt = 0;
+        loop {
+            match self.rpc.send(instructions, &self.payer) {
+                Err(SendError::Transport(reason)) if attempt < self.config.max_retries => {
+                    let delay = self
+                        .config
+                        .retry_backoff
+                        .saturating_mul(1 << attempt.min(16));
+                    eprintln!(
+                        "Keeper::Transaction not landed ({}), retrying in {:?}",
+                        reason, delay
+                    );
+                    sleep(delay);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn save_state(&self) -> Result<()> {
+        match &self.state_path {
+            Some(path) => self.state.save(path),
+            None => Ok(()),
+        }
+    }
+
+    fn fetch_policy(&self) -> Result<Policy> {
+        let address = get_policy_pda(self.config.vault).0;
+        let account = self
+            .rpc
+            .get_account(&address)?
+            .ok_or_else(|| anyhow!("Policy account {} not found", address))?;
+        decode_policy(&account.data).map_err(|e| anyhow!("Failed to decode policy: {}", e))
+    }
+
+    fn fetch_progress(&self) -> Result<Progress> {
+        let address = get_progress_pda(self.config.vault).0;
+        let account = self
+            .rpc
+            .get_account(&address)?
+            .ok_or_else(|| anyhow!("Progress account {} not found", address))?;
+        decode_progress(&account.data).map_err(|e| anyhow!("Failed to decode progress: {}", e))
+    }
+
+    /// Fetches the first `count` streams and resolves their current recipients
+    /// and payout accounts, in crank order.
+    fn fetch_investors(&self, count: usize) -> Result<Vec<InvestorStream>> {
+        let streams = &self.config.streams[..count];
+        let redirects: Vec<Pubkey> = streams
+            .iter()
+            .map(|stream| get_payout_redirect_pda(self.config.vault, *stream).0)
+            .collect();
+        let accounts = self.rpc.get_multiple_accounts(streams)?;
+        let redirect_accounts = self.rpc.get_multiple_accounts(&redirects)?;
+        let mut investors = streams
+            .iter()
+            .zip(accounts)
+            .zip(redirect_accounts)
+            .map(|((stream, account), redirect)| {
+                // A closed stream keeps its slot in the pages with no weight
+                let Some(account) = account.filter(|account| !account.data.is_empty()) else {
+                    return Ok(InvestorStream::closed(*stream));
+                };
+                let redirect = redirect
+                    .map(|redirect| decode_payout_redirect(&redirect.data))
+                    .transpose()
+                    .map_err(|e| {
+                        anyhow!("Failed to decode payout redirect of {}: {}", stream, e)
+                    })?;
+                Ok(InvestorStream::from_stream_account(*stream, &account.data)
+                    .map_err(|e| anyhow!("Failed to decode stream {}: {}", stream, e))?
+                    .with_redirect(redirect.as_ref()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        sort_investors(&mut investors).map_err(|e| anyhow!("Invalid streams: {}", e))?;
+        Ok(investors)
+    }
+
+    /// Fetches the positions registered after the primary one, in registry order.
+    fn fetch_extra_positions(&self) -> Result<Vec<ExtraPosition>> {
+        let address = get_position_registry_pda(self.config.vault).0;
+        let Some(account) = self.rpc.get_account(&address)? else {
+            return Ok(vec![]);
+        };
+        let registry = decode_position_registry(&account.data)
+            .map_err(|e| anyhow!("Failed to decode position registry: {}", e))?;
+        let extra = &registry.positions()[1..];
+
+        let keys: Vec<Pubkey> = extra.iter().map(|entry| entry.position).collect();
+        let accounts = self.rpc.get_multiple_accounts(&keys)?;
+        extra
+            .iter()
+            .zip(accounts)
+            .map(|(entry, account)| {
+                let account = account
+                    .ok_or_else(|| anyhow!("Position account {} not found", entry.position))?;
+                let position: damm_v2::accounts::Position = bytemuck::try_pod_read_unaligned(
+                    account
+                        .data
+                        .get(8..)
+                        .ok_or_else(|| anyhow!("Position account data too short"))?,
+                )
+                .map_err(|e| anyhow!("Failed to decode position {}: {}", entry.position, e))?;
+                Ok(ExtraPosition {
+                    pool: entry.pool,
+                    position_nft_mint: position.nft_mint,
+                })
+            })
+            .collect()
+    }
+
+    fn crank_builder(&self, policy: &Policy) -> Result<CrankBuilder> {
+        let base_mint = self.fetch_pool_base_mint()?;
+        Ok(CrankBuilder::new(
+            self.config.vault,
+            self.config.pool,
+            self.config.position_nft_mint,
+            base_mint,
+            self.config.quote_mint,
+            policy.creator,
+            self.payer.pubkey(),
+        )
+        .base_program(self.fetch_owner(&base_mint)?)
+        .quote_program(self.fetch_owner(&self.config.quote_mint)?))
+    }
+
+    fn fetch_owner(&self, address: &Pubkey) -> Result<Pubkey> {
+        Ok(self
+            .rpc
+            .get_account(address)?
+            .ok_or_else(|| anyhow!("Account {} not found", address))?
+            .owner)
+    }
+
+    /// Returns the base mint of the pool, given its quote mint.
+    fn fetch_pool_base_mint(&self) -> Result<Pubkey> {
+        let account = self
+            .rpc
+            .get_account(&self.config.pool)?
+            .ok_or_else(|| anyhow!("Pool account {} not found", self.config.pool))?;
+        let pool: damm_v2::accounts::Pool = bytemuck::try_pod_read_unaligned(
+            account
+                .data
+                .get(8..)
+                .ok_or_else(|| anyhow!("Pool account data too short"))?,
+        )
+        .map_err(|e| anyhow!("Failed to decode pool: {}", e))?;
+
+        if pool.token_a_mint == self.config.quote_mint {
+            Ok(pool.token_b_mint)
+        } else {
+            Ok(pool.token_a_mint)
+        }
+    }
+}
+
+/// Returns the custom instruction error code of a Tollgate error.
+pub fn error_code(err: TollgateError) -> u32 {
+    u32::from(err)
+}
+
+/// Returns the pass the progress cursor belongs to.
+fn progress_pass(progress: &Progress) -> (PassPhase, u8) {
+    (PassPhase::from(&progress.phase), progress.cap_passes)
+}
@@ -0,0 +1,16 @@
+//! Keeper daemon driving the Tollgate daily crank.
+//!
+//! The keeper watches the vault progress, starts a new day as soon as the
+//! previous one is over and submits every investor page in cursor order. All
+//! chain access goes through [`KeeperRpc`] so the keeper can run against an RPC
+//! node as well as an in-process bank.
+
+pub mod config;
+pub mod keeper;
+pub mod rpc;
+pub mod state;
+
+pub use config::*;
+pub use keeper::*;
+pub use rpc::*;
+pub use state::*;
@@ -0,0 +1,67 @@
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file},
+    Cluster,
+};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use tollgate_keeper::{Keeper, KeeperConfig};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "tollgate-keeper",
+    version,
+    about = "Drive the Tollgate daily crank"
+)]
+struct Cli {
+    /// RPC URL or moniker (localnet, devnet, mainnet)
+    #[arg(short, long, default_value = "localnet")]
+    url: String,
+
+    /// Payer keypair file
+    #[arg(short, long, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Keeper config file
+    #[arg(short, long)]
+    config: PathBuf,
+
+    /// Keeper state file, created on the first landed page
+    #[arg(short, long, default_value = "keeper-state.json")]
+    state: PathBuf,
+
+    /// Seconds between two passes
+    #[arg(long, default_value_t = 30)]
+    poll_interval: u64,
+
+    /// Run a single pass and exit
+    #[arg(long)]
+    once: bool,
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let cluster = Cluster::from_str(&cli.url).map_err(|e| anyhow!("Invalid url: {}", e))?;
+    let rpc =
+        RpcClient::new_with_commitment(cluster.url().to_string(), CommitmentConfig::confirmed());
+    let payer = read_keypair_file(expand_home(&cli.keypair))
+        .map_err(|e| anyhow!("Failed to read keypair {}: {}", cli.keypair, e))?;
+    let config = KeeperConfig::load(&cli.config)?;
+
+    let mut keeper = Keeper::new(rpc, payer, config).state_path(cli.state)?;
+    if cli.once {
+        println!("Keeper::{:?}", keeper.tick()?);
+        return Ok(());
+    }
+    keeper.run(Duration::from_secs(cli.poll_interval))
+}
@@ -0,0 +1,143 @@
+use anchor_client::{
+    solana_client::{
+        client_error::ClientErrorKind,
+        rpc_client::RpcClient,
+        rpc_request::{RpcError, RpcResponseErrorData},
+    },
+    solana_sdk::{
+        account::{from_account, Account},
+        clock::Clock,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        sysvar,
+        transaction::{Transaction, TransactionError},
+    },
+};
+use anyhow::{anyhow, Result};
+
+/// Transaction accepted by the cluster.
+#[derive(Debug, Clone, Default)]
+pub struct SendOutcome {
+    pub signature: String, // Transaction signature
+    pub logs: Vec<String>, // Program logs, when the backend returns them
+}
+
+/// Failure of a transaction sent by the keeper.
+#[derive(Debug, Clone)]
+pub enum SendError {
+    /// An instruction failed, sending the same transaction again will fail too.
+    Instruction {
+        index: u8,
+        error: InstructionError,
+        logs: Vec<String>,
+    },
+    /// The transaction did not land, it can be retried.
+    Transport(String),
+}
+
+impl SendError {
+    /// Returns the custom program error code of a failed instruction.
+    pub fn custom_code(&self) -> Option<u32> {
+        match self {
+            SendError::Instruction {
+                error: InstructionError::Custom(code),
+                ..
+            } => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SendError::Instruction { index, error, .. } => {
+                write!(f, "Instruction {} failed: {}", index, error)
+            }
+            SendError::Transport(reason) => write!(f, "Transaction not landed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SendError {}
+
+/// Chain access used by the keeper.
+pub trait KeeperRpc {
+    /// Returns the account at `address`, or `None` when it does not exist.
+    fn get_account(&self, address: &Pubkey) -> Result<Option<Account>>;
+
+    /// Returns the accounts at `addresses`, in the same order.
+    fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>>;
+
+    /// Returns the cluster unix timestamp.
+    fn get_unix_timestamp(&self) -> Result<i64>;
+
+    /// Signs the instructions with the payer, then sends and confirms them.
+    fn send(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+    ) -> std::result::Result<SendOutcome, SendError>;
+}
+
+impl KeeperRpc for RpcClient {
+    fn get_account(&self, address: &Pubkey) -> Result<Option<Account>> {
+        Ok(self
+            .get_account_with_commitment(address, self.commitment())?
+            .value)
+    }
+
+    fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(100) {
+            accounts.extend(RpcClient::get_multiple_accounts(self, chunk)?);
+        }
+        Ok(accounts)
+    }
+
+    fn get_unix_timestamp(&self) -> Result<i64> {
+        let account = KeeperRpc::get_account(self, &sysvar::clock::ID)?
+            .ok_or_else(|| anyhow!("Clock sysvar not found"))?;
+        let clock: Clock =
+            from_account(&account).ok_or_else(|| anyhow!("Failed to decode clock sysvar"))?;
+        Ok(clock.unix_timestamp)
+    }
+
+    fn send(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+    ) -> std::result::Result<SendOutcome, SendError> {
+        let blockhash = self
+            .get_latest_blockhash()
+            .map_err(|e| SendError::Transport(e.to_string()))?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        );
+
+        match self.send_and_confirm_transaction(&transaction) {
+            Ok(signature) => Ok(SendOutcome {
+                signature: signature.to_string(),
+                logs: vec![],
+            }),
+            Err(err) => match err.get_transaction_error() {
+                Some(TransactionError::InstructionError(index, error)) => {
+                    let logs = match err.kind() {
+                        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+                            ..
+                        }) => result.logs.clone().unwrap_or_default(),
+                        _ => vec![],
+                    };
+                    Err(SendError::Instruction { index, error, logs })
+                }
+                _ => Err(SendError::Transport(err.to_string())),
+            },
+        }
+    }
+}
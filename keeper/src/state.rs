@@ -0,0 +1,65 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tollgate::state::CrankPhase;
+
+/// Crank phase of the persisted cursor, in the order the passes of a day run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PassPhase {
+    #[default]
+    Snapshot,
+    Cap,
+    Payout,
+}
+
+impl From<&CrankPhase> for PassPhase {
+    fn from(phase: &CrankPhase) -> Self {
+        match phase {
+            CrankPhase::Snapshot => Self::Snapshot,
+            CrankPhase::Cap => Self::Cap,
+            CrankPhase::Payout => Self::Payout,
+        }
+    }
+}
+
+/// Keeper state persisted between runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeeperState {
+    pub day_ts: i64, // Start timestamp of the last cranked day
+    pub cursor: u32, // Next investor index to crank on that day
+    #[serde(default)]
+    pub phase: PassPhase, // Crank phase the cursor belongs to
+    #[serde(default)]
+    pub cap_passes: u8, // Cap passes run before that pass
+    pub days_closed: u64, // Number of days closed by the keeper
+    pub last_signature: Option<String>, // Signature of the last landed page
+}
+
+impl KeeperState {
+    /// Returns the pass of the cursor, ordered like the passes of a day.
+    pub fn pass(&self) -> (PassPhase, u8) {
+        (self.phase, self.cap_passes)
+    }
+
+    /// Reads the state file, or returns a fresh state when it does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keeper state {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid keeper state {}", path.display()))
+    }
+
+    /// Writes the state file through a temporary file so a crash never leaves it truncated.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write keeper state {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to write keeper state {}", path.display()))
+    }
+}
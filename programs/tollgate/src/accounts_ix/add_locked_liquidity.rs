@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface};
+
+use crate::{
+    constants::{INVESTOR_FEE_POS_OWNER, POLICY_SEED, POSITION_REGISTRY_SEED, VAULT_SEED},
+    error::TollgateError,
+    state::{Policy, PositionRegistry},
+    utils::pool::is_valid_pool,
+};
+
+/// Accounts required to add permanently locked liquidity to a honorary position
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccountAddLockedLiquidity<'info> {
+    /// The policy account that must be at the current version.
+    #[account(
+        seeds = [POLICY_SEED, policy.vault.as_ref()],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// The position registry account, where the position must be registered.
+    #[account(
+        seeds = [POSITION_REGISTRY_SEED, policy.vault.as_ref()],
+        bump = position_registry.bump,
+        constraint = position_registry.version == PositionRegistry::VERSION @ TollgateError::StaleAccountVersion,
+        constraint = position_registry.is_registered(&pool.key(), &position.key()) @ TollgateError::InvalidPosition,
+    )]
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
+
+    /// The DAMM v2 pool account that must be valid.
+    #[account(mut, constraint = is_valid_pool(&pool.load().ok()) @ TollgateError::InvalidPool)]
+    pub pool: AccountLoader<'info, damm_v2::accounts::Pool>,
+
+    /// The position NFT account.
+    #[account(
+        constraint = position_nft_account.mint == position.load()?.nft_mint,
+        constraint = position_nft_account.amount == 1,
+        token::authority = owner,
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The DAMM v2 pool position account.
+    #[account(
+        mut,
+        has_one = pool @ TollgateError::InvalidPosition,
+    )]
+    pub position: AccountLoader<'info, damm_v2::accounts::Position>,
+
+    /// The system account that owns the vault.
+    #[account(
+        seeds = [VAULT_SEED, policy.vault.as_ref(), INVESTOR_FEE_POS_OWNER],
+        bump = policy.owner_bump,
+    )]
+    pub owner: SystemAccount<'info>,
+
+    /// The owner base account, the deposit goes through.
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = base_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = base_program,
+    )]
+    pub base_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The owner quote account, the deposit goes through.
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = quote_program,
+    )]
+    pub quote_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The depositor base account.
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = depositor,
+        token::token_program = base_program,
+    )]
+    pub depositor_base_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The depositor quote account.
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = depositor,
+        token::token_program = quote_program,
+    )]
+    pub depositor_quote_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The base vault account.
+    #[account(mut, token::token_program = base_program, token::mint = base_mint)]
+    pub base_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The quote vault account.
+    #[account(mut, token::token_program = quote_program, token::mint = quote_mint)]
+    pub quote_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The base mint account of the vault.
+    #[account(address = position_registry.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The quote mint account of the policy.
+    #[account(address = policy.quote_mint)]
+    pub quote_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The base token program account.
+    pub base_program: Interface<'info, token_interface::TokenInterface>,
+
+    /// The quote token program account.
+    pub quote_program: Interface<'info, token_interface::TokenInterface>,
+
+    /// The signer account that deposits the liquidity and pays for the instruction.
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    /// The DAMM v2 event authority account (unchecked).
+    /// CHECK: DAMM v2 event authority.
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = damm_v2::ID,
+    )]
+    pub amm_event_authority: UncheckedAccount<'info>,
+
+    /// The DAMM v2 AMM program account.
+    #[account(address = damm_v2::ID @ TollgateError::AMMProgramMismatch)]
+    pub amm_program: Program<'info, damm_v2::program::CpAmm>,
+
+    /// The associated token program account.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program account.
+    pub system_program: Program<'info, System>,
+}
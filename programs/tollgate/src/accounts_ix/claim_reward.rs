@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface};
+
+use crate::{
+    constants::{
+        damm_v2_constants, INVESTOR_FEE_POS_OWNER, POLICY_SEED, POSITION_REGISTRY_SEED,
+        PROGRESS_SEED, REWARD_PROGRESS_SEED, VAULT_SEED,
+    },
+    error::TollgateError,
+    state::{Policy, PositionRegistry, Progress, RewardProgress},
+    utils::pool::is_valid_pool,
+};
+
+/// Accounts required to claim a DAMM v2 reward of the primary position and open its round
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct AccountClaimReward<'info> {
+    /// The policy account that must be at the current version.
+    #[account(
+        seeds = [POLICY_SEED, policy.vault.as_ref()],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// The progress account, whose day snapshot the rewards are split over.
+    #[account(
+        seeds = [PROGRESS_SEED, policy.vault.as_ref()],
+        bump = progress.bump,
+    )]
+    pub progress: Account<'info, Progress>,
+
+    /// The reward progress account of the reward index, created on the first claim.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RewardProgress::SPACE,
+        seeds = [REWARD_PROGRESS_SEED, policy.vault.as_ref(), &[reward_index]],
+        bump,
+    )]
+    pub reward_progress: Box<Account<'info, RewardProgress>>,
+
+    /// The position registry account, whose primary position is the one below.
+    #[account(
+        seeds = [POSITION_REGISTRY_SEED, policy.vault.as_ref()],
+        bump = position_registry.bump,
+        constraint = position_registry.version == PositionRegistry::VERSION @ TollgateError::StaleAccountVersion,
+        constraint = position_registry.is_primary(&pool.key(), &position.key()) @ TollgateError::InvalidPosition,
+    )]
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
+
+    /// The DAMM v2 pool account that must be valid.
+    #[account(mut, constraint = is_valid_pool(&pool.load().ok()) @ TollgateError::InvalidPool)]
+    pub pool: AccountLoader<'info, damm_v2::accounts::Pool>,
+
+    /// The position NFT account.
+    #[account(
+        constraint = position_nft_account.mint == position.load()?.nft_mint,
+        constraint = position_nft_account.amount == 1,
+        token::authority = owner,
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The DAMM v2 pool position account.
+    #[account(
+        mut,
+        has_one = pool @ TollgateError::InvalidPosition,
+    )]
+    pub position: AccountLoader<'info, damm_v2::accounts::Position>,
+
+    /// The pool authority account (unchecked).
+    /// CHECK: DAMM v2 pool authority.
+    #[account(address = damm_v2_constants::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The system account that owns the vault.
+    #[account(
+        seeds = [VAULT_SEED, policy.vault.as_ref(), INVESTOR_FEE_POS_OWNER],
+        bump = policy.owner_bump,
+    )]
+    pub owner: SystemAccount<'info>,
+
+    /// The reward vault account of the pool.
+    #[account(mut, token::token_program = reward_program, token::mint = reward_mint)]
+    pub reward_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The reward mint account.
+    pub reward_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The reward token program account.
+    pub reward_program: Interface<'info, token_interface::TokenInterface>,
+
+    /// The owner reward account, holding the claimed rewards.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = reward_program,
+    )]
+    pub reward_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The signer account that will pay for the instruction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The DAMM v2 event authority account (unchecked).
+    /// CHECK: DAMM v2 event authority.
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = damm_v2::ID,
+    )]
+    pub amm_event_authority: UncheckedAccount<'info>,
+
+    /// The DAMM v2 AMM program account.
+    #[account(address = damm_v2::ID @ TollgateError::AMMProgramMismatch)]
+    pub amm_program: Program<'info, damm_v2::program::CpAmm>,
+
+    /// The associated token program account.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program account.
+    pub system_program: Program<'info, System>,
+}
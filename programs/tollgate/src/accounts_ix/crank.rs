@@ -3,24 +3,25 @@ use anchor_spl::{associated_token::AssociatedToken, token_interface};
 
 use crate::{
     constants::{
-        damm_v2_constants, INVESTOR_FEE_POS_OWNER, POLICY_SEED, PROGRESS_SEED, VAULT_SEED,
+        DAY_HISTORY_SEED, INVESTOR_FEE_POS_OWNER, POLICY_SEED, POSITION_REGISTRY_SEED,
+        PROGRESS_SEED, VAULT_SEED,
     },
     error::TollgateError,
-    state::{Policy, Progress},
-    utils::pool::is_valid_pool,
+    state::{DayHistory, Policy, PositionRegistry, Progress},
 };
 
-/// Accounts required for the daily crank
+/// Accounts required for a page of the daily crank, once `claim_fees` opened the day
+#[event_cpi]
 #[derive(Accounts)]
-pub struct AccountCrank<'info> {
-    /// The policy account.
+pub struct AccountCrankPage<'info> {
+    /// The policy account that must be at the current version.
     #[account(
         seeds = [POLICY_SEED, policy.vault.as_ref()],
         bump = policy.bump,
     )]
     pub policy: Account<'info, Policy>,
 
-    /// The progress account.
+    /// The progress account that must be at the current version.
     #[account(
         mut,
         seeds = [PROGRESS_SEED, policy.vault.as_ref()],
@@ -28,29 +29,22 @@ pub struct AccountCrank<'info> {
     )]
     pub progress: Account<'info, Progress>,
 
-    /// The DAMM v2 pool account that must be valid.
-    #[account(constraint = is_valid_pool(&pool.load().ok()) @ TollgateError::InvalidPool)]
-    pub pool: AccountLoader<'info, damm_v2::accounts::Pool>,
-
-    /// The position NFT account.
+    /// The day history account that must be at the current version.
     #[account(
-        constraint = position_nft_account.mint == position.load()?.nft_mint,
-        constraint = position_nft_account.amount == 1,
-        token::authority = owner,
+        mut,
+        seeds = [DAY_HISTORY_SEED, policy.vault.as_ref()],
+        bump = day_history.load()?.bump,
+        constraint = day_history.load()?.version == DayHistory::VERSION @ TollgateError::StaleAccountVersion,
     )]
-    pub position_nft_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    pub day_history: AccountLoader<'info, DayHistory>,
 
-    /// The DAMM v2 pool position account.
+    /// The position registry account, whose primary position the page events refer to.
     #[account(
-        mut,
-        has_one = pool @ TollgateError::InvalidPosition,
+        seeds = [POSITION_REGISTRY_SEED, policy.vault.as_ref()],
+        bump = position_registry.bump,
+        constraint = position_registry.version == PositionRegistry::VERSION @ TollgateError::StaleAccountVersion,
     )]
-    pub position: AccountLoader<'info, damm_v2::accounts::Position>,
-
-    /// The pool authority account (unchecked).
-    /// CHECK: DAMM v2 pool authority.
-    #[account(address = damm_v2_constants::pool_authority::ID)]
-    pub pool_authority: UncheckedAccount<'info>,
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
 
     /// The system account that owns the vault.
     #[account(
@@ -59,51 +53,33 @@ pub struct AccountCrank<'info> {
     )]
     pub owner: SystemAccount<'info>,
 
-    /// The owner base account.
+    /// The owner quote account, holding the claimed fees.
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = base_mint,
-        associated_token::authority = owner,
-        associated_token::token_program = base_program,
-    )]
-    pub base_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
-
-    /// The owner quote account.
-    #[account(
-        init_if_needed,
-        payer = payer,
+        mut,
         associated_token::mint = quote_mint,
         associated_token::authority = owner,
         associated_token::token_program = quote_program,
     )]
     pub quote_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
-    /// The base vault account.
-    #[account(mut, token::token_program = base_program, token::mint = base_mint)]
-    pub base_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
-
-    /// The quote vault account.
-    #[account(mut, token::token_program = quote_program, token::mint = quote_mint)]
-    pub quote_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
-
-    /// The base mint account.
-    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
-
-    /// The quote mint account.
+    /// The quote mint account of the policy.
+    #[account(address = policy.quote_mint)]
     pub quote_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
-    /// The base token program account.
-    pub base_program: Interface<'info, token_interface::TokenInterface>,
-
     /// The quote token program account.
     pub quote_program: Interface<'info, token_interface::TokenInterface>,
 
-    /// The creator account.
+    /// The creator of the policy (unchecked).
+    /// CHECK: Only owns the creator account.
+    #[account(address = policy.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// The creator account, created if missing.
     #[account(
-        mut,
+        init_if_needed,
+        payer = payer,
         associated_token::mint = quote_mint,
-        associated_token::authority = policy.creator,
+        associated_token::authority = creator,
         associated_token::token_program = quote_program,
     )]
     pub creator_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
@@ -112,19 +88,6 @@ pub struct AccountCrank<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    /// The event authority account (unchecked).
-    /// CHECK: DAMM v2 event authority.
-    #[account(
-        seeds = [b"__event_authority"],
-        bump,
-        seeds::program = damm_v2::ID,
-    )]
-    pub event_authority: UncheckedAccount<'info>,
-
-    /// The DAMM v2 AMM program account.
-    #[account(address = damm_v2::ID @ TollgateError::AMMProgramMismatch)]
-    pub amm_program: Program<'info, damm_v2::program::CpAmm>,
-
     /// The associated token program account.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface};
+
+use crate::{
+    constants::{INVESTOR_FEE_POS_OWNER, POLICY_SEED, REWARD_PROGRESS_SEED, VAULT_SEED},
+    error::TollgateError,
+    state::{Policy, RewardProgress},
+};
+
+/// Accounts required for a page of a reward round, once `claim_reward` opened it
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccountCrankReward<'info> {
+    /// The policy account that must be at the current version.
+    #[account(
+        seeds = [POLICY_SEED, policy.vault.as_ref()],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// The reward progress account that must be at the current version.
+    #[account(
+        mut,
+        seeds = [REWARD_PROGRESS_SEED, policy.vault.as_ref(), &[reward_progress.reward_index]],
+        bump = reward_progress.bump,
+        constraint = reward_progress.version == RewardProgress::VERSION @ TollgateError::StaleAccountVersion,
+    )]
+    pub reward_progress: Box<Account<'info, RewardProgress>>,
+
+    /// The system account that owns the vault.
+    #[account(
+        seeds = [VAULT_SEED, policy.vault.as_ref(), INVESTOR_FEE_POS_OWNER],
+        bump = policy.owner_bump,
+    )]
+    pub owner: SystemAccount<'info>,
+
+    /// The owner reward account, holding the claimed rewards.
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = reward_program,
+    )]
+    pub reward_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The reward mint account of the reward progress.
+    #[account(address = reward_progress.reward_mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The reward token program account.
+    pub reward_program: Interface<'info, token_interface::TokenInterface>,
+
+    /// The creator account of the policy (unchecked).
+    /// CHECK: Only owns the creator reward account.
+    #[account(address = policy.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// The creator reward account, created if missing.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = reward_program,
+    )]
+    pub creator_reward_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The signer account that will pay for the instruction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The associated token program account.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program account.
+    pub system_program: Program<'info, System>,
+}
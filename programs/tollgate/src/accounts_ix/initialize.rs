@@ -3,17 +3,19 @@ use anchor_spl::{token_2022::Token2022, token_interface::Mint};
 
 use crate::{
     constants::{
-        damm_v2_constants, INVESTOR_FEE_POS_OWNER, POLICY_SEED, PROGRESS_SEED, VAULT_SEED,
+        damm_v2_constants, DAY_HISTORY_SEED, INVESTOR_FEE_POS_OWNER, POLICY_SEED,
+        POSITION_REGISTRY_SEED, PROGRESS_SEED, VAULT_SEED,
     },
     error::TollgateError,
-    state::{Policy, Progress},
+    state::{DayHistory, Policy, PositionRegistry, Progress},
     utils::pool::{is_valid_pool, is_valid_pool_cfg},
 };
 
 /// Accounts required for the initialization of a honorary position
+#[event_cpi]
 #[derive(Accounts)]
 pub struct AccountInitialize<'info> {
-    /// The signer account that will be used to create the policy and progress accounts.
+    /// The signer account that will be used to create the policy, progress, day history and position registry accounts.
     pub vault: Signer<'info>,
 
     /// The policy account that will be initialized.
@@ -36,6 +38,26 @@ pub struct AccountInitialize<'info> {
     )]
     pub progress: Account<'info, Progress>,
 
+    /// The day history account that will be initialized.
+    #[account(
+        init,
+        payer = payer,
+        space = DayHistory::SPACE,
+        seeds = [DAY_HISTORY_SEED, vault.key().as_ref()],
+        bump,
+    )]
+    pub day_history: AccountLoader<'info, DayHistory>,
+
+    /// The position registry account that will be initialized with the position.
+    #[account(
+        init,
+        payer = payer,
+        space = PositionRegistry::SPACE,
+        seeds = [POSITION_REGISTRY_SEED, vault.key().as_ref()],
+        bump,
+    )]
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
+
     /// The DAMM v2 pool account that must be valid and will be used for validations.
     #[account(mut, constraint = is_valid_pool(&pool.load().ok()) @ TollgateError::InvalidPool)]
     pub pool: AccountLoader<'info, damm_v2::accounts::Pool>,
@@ -87,14 +109,14 @@ pub struct AccountInitialize<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    /// The event authority account (unchecked).
+    /// The DAMM v2 event authority account (unchecked).
     /// CHECK: DAMM v2 event authority.
     #[account(
         seeds = [b"__event_authority"],
         bump,
         seeds::program = damm_v2::ID,
     )]
-    pub event_authority: UncheckedAccount<'info>,
+    pub amm_event_authority: UncheckedAccount<'info>,
 
     /// The DAMM v2 AMM program account.
     #[account(address = damm_v2::ID @ TollgateError::AMMProgramMismatch)]
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DAY_HISTORY_SEED, POLICY_SEED},
+    state::{DayHistory, Policy},
+};
+
+/// Accounts required for the initialization of the day history of an existing vault
+#[derive(Accounts)]
+pub struct AccountInitializeDayHistory<'info> {
+    /// The vault the day history belongs to (unchecked).
+    /// CHECK: Only used as a PDA seed.
+    pub vault: UncheckedAccount<'info>,
+
+    /// The policy account of the vault, which must already exist.
+    #[account(
+        seeds = [POLICY_SEED, vault.key().as_ref()],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// The day history account that will be initialized.
+    #[account(
+        init,
+        payer = payer,
+        space = DayHistory::SPACE,
+        seeds = [DAY_HISTORY_SEED, vault.key().as_ref()],
+        bump,
+    )]
+    pub day_history: AccountLoader<'info, DayHistory>,
+
+    /// The signer account that will pay for the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The system program account.
+    pub system_program: Program<'info, System>,
+}
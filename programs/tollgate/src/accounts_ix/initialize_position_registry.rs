@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+use crate::{
+    constants::{INVESTOR_FEE_POS_OWNER, POLICY_SEED, POSITION_REGISTRY_SEED, VAULT_SEED},
+    error::TollgateError,
+    state::{Policy, PositionRegistry},
+    utils::pool::is_valid_pool,
+};
+
+/// Accounts required for the initialization of the position registry of an existing vault
+#[derive(Accounts)]
+pub struct AccountInitializePositionRegistry<'info> {
+    /// The vault the position registry belongs to (unchecked).
+    /// CHECK: Only used as a PDA seed.
+    pub vault: UncheckedAccount<'info>,
+
+    /// The policy account of the vault, which must already exist.
+    #[account(
+        seeds = [POLICY_SEED, vault.key().as_ref()],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// The position registry account that will be initialized.
+    #[account(
+        init,
+        payer = payer,
+        space = PositionRegistry::SPACE,
+        seeds = [POSITION_REGISTRY_SEED, vault.key().as_ref()],
+        bump,
+    )]
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
+
+    /// The DAMM v2 pool account of the existing position.
+    #[account(constraint = is_valid_pool(&pool.load().ok()) @ TollgateError::InvalidPool)]
+    pub pool: AccountLoader<'info, damm_v2::accounts::Pool>,
+
+    /// The position NFT account, held by the vault owner.
+    #[account(
+        constraint = position_nft_account.mint == position.load()?.nft_mint,
+        constraint = position_nft_account.amount == 1,
+        token::authority = owner,
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The existing DAMM v2 position of the vault.
+    #[account(has_one = pool @ TollgateError::InvalidPosition)]
+    pub position: AccountLoader<'info, damm_v2::accounts::Position>,
+
+    /// The system account that owns the vault.
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), INVESTOR_FEE_POS_OWNER],
+        bump = policy.owner_bump,
+    )]
+    pub owner: SystemAccount<'info>,
+
+    /// The signer account that will pay for the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The system program account.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{POLICY_SEED, PROGRESS_SEED};
+
+/// Accounts required for the migration of policy and progress accounts
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccountMigrate<'info> {
+    /// The vault the policy and progress accounts belong to (unchecked).
+    /// CHECK: Only used as a PDA seed.
+    pub vault: UncheckedAccount<'info>,
+
+    /// The policy account that will be migrated (unchecked).
+    /// CHECK: Deserialized by version during the migration.
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub policy: UncheckedAccount<'info>,
+
+    /// The progress account that will be migrated (unchecked).
+    /// CHECK: Deserialized by version during the migration.
+    #[account(
+        mut,
+        seeds = [PROGRESS_SEED, vault.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub progress: UncheckedAccount<'info>,
+
+    /// The signer account that will pay for the reallocation.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The system program account.
+    pub system_program: Program<'info, System>,
+}
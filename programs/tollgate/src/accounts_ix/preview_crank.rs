@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface};
+
+use crate::{
+    constants::{
+        damm_v2_constants, DAY_HISTORY_SEED, INVESTOR_FEE_POS_OWNER, POLICY_SEED,
+        POSITION_REGISTRY_SEED, PROGRESS_SEED, VAULT_SEED,
+    },
+    error::TollgateError,
+    state::{DayHistory, Policy, PositionRegistry, Progress},
+    utils::pool::is_initialized_pool,
+};
+
+/// Accounts required to preview a crank page, the keys of `claim_fees` without
+/// any writable or created account, so that a simulation changes nothing
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccountPreviewCrank<'info> {
+    /// The policy account that must be at the current version.
+    #[account(
+        seeds = [POLICY_SEED, policy.vault.as_ref()],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// The progress account that must be at the current version.
+    #[account(
+        seeds = [PROGRESS_SEED, policy.vault.as_ref()],
+        bump = progress.bump,
+    )]
+    pub progress: Account<'info, Progress>,
+
+    /// The day history account that must be at the current version.
+    #[account(
+        seeds = [DAY_HISTORY_SEED, policy.vault.as_ref()],
+        bump = day_history.load()?.bump,
+        constraint = day_history.load()?.version == DayHistory::VERSION @ TollgateError::StaleAccountVersion,
+    )]
+    pub day_history: AccountLoader<'info, DayHistory>,
+
+    /// The position registry account, whose primary position is the one below.
+    #[account(
+        seeds = [POSITION_REGISTRY_SEED, policy.vault.as_ref()],
+        bump = position_registry.bump,
+        constraint = position_registry.version == PositionRegistry::VERSION @ TollgateError::StaleAccountVersion,
+        constraint = position_registry.is_primary(&pool.key(), &position.key()) @ TollgateError::InvalidPosition,
+    )]
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
+
+    /// The DAMM v2 pool account that must be initialized, a disabled pool has nothing claimable.
+    #[account(constraint = is_initialized_pool(&pool.load().ok()) @ TollgateError::InvalidPool)]
+    pub pool: AccountLoader<'info, damm_v2::accounts::Pool>,
+
+    /// The position NFT account.
+    #[account(
+        constraint = position_nft_account.mint == position.load()?.nft_mint,
+        constraint = position_nft_account.amount == 1,
+        token::authority = owner,
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The DAMM v2 pool position account.
+    #[account(has_one = pool @ TollgateError::InvalidPosition)]
+    pub position: AccountLoader<'info, damm_v2::accounts::Position>,
+
+    /// The pool authority account (unchecked).
+    /// CHECK: DAMM v2 pool authority.
+    #[account(address = damm_v2_constants::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The system account that owns the vault.
+    #[account(
+        seeds = [VAULT_SEED, policy.vault.as_ref(), INVESTOR_FEE_POS_OWNER],
+        bump = policy.owner_bump,
+    )]
+    pub owner: SystemAccount<'info>,
+
+    /// The owner base account (unchecked).
+    /// CHECK: Not read by the preview, may not exist before the first claim.
+    pub base_account: UncheckedAccount<'info>,
+
+    /// The owner quote account (unchecked), empty until the first claim creates it.
+    /// CHECK: Checked against the owner quote ATA by the preview.
+    pub quote_account: UncheckedAccount<'info>,
+
+    /// The base vault account.
+    #[account(token::token_program = base_program, token::mint = base_mint)]
+    pub base_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The quote vault account.
+    #[account(token::token_program = quote_program, token::mint = quote_mint)]
+    pub quote_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The base mint account.
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The quote mint account.
+    pub quote_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The base token program account.
+    pub base_program: Interface<'info, token_interface::TokenInterface>,
+
+    /// The quote token program account.
+    pub quote_program: Interface<'info, token_interface::TokenInterface>,
+
+    /// The creator of the policy (unchecked).
+    /// CHECK: Only owns the creator account.
+    #[account(address = policy.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// The creator account (unchecked).
+    /// CHECK: Not read by the preview, may not exist before the first payout.
+    pub creator_account: UncheckedAccount<'info>,
+
+    /// The payer of `claim_fees` (unchecked), which the preview neither signs nor charges.
+    /// CHECK: Not read by the preview.
+    pub payer: UncheckedAccount<'info>,
+
+    /// The DAMM v2 event authority account (unchecked).
+    /// CHECK: DAMM v2 event authority.
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = damm_v2::ID,
+    )]
+    pub amm_event_authority: UncheckedAccount<'info>,
+
+    /// The DAMM v2 AMM program account.
+    #[account(address = damm_v2::ID @ TollgateError::AMMProgramMismatch)]
+    pub amm_program: Program<'info, damm_v2::program::CpAmm>,
+
+    /// The associated token program account.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program account.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::POLICY_SEED, state::Policy};
+
+/// Accounts required to change the creator receiving the creator payouts
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccountSetCreator<'info> {
+    /// The vault signer that initialized the policy.
+    pub vault: Signer<'info>,
+
+    /// The policy account that must be at the current version.
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault.key().as_ref()],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, Policy>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    constants::{PAYOUT_REDIRECT_SEED, POLICY_SEED},
+    state::{PayoutRedirect, Policy},
+};
+
+/// Accounts required to redirect the payouts of an investor stream
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccountSetPayoutDestination<'info> {
+    /// The vault the stream is distributed by (unchecked).
+    /// CHECK: Only used as a PDA seed.
+    pub vault: UncheckedAccount<'info>,
+
+    /// The policy account that must be at the current version.
+    #[account(
+        seeds = [POLICY_SEED, vault.key().as_ref()],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, Policy>,
+
+    /// The Streamflow stream of the investor.
+    /// CHECK: Owned by Streamflow, decoded in the instruction, its recipient must sign.
+    #[account(owner = streamflow_sdk::ID)]
+    pub stream: UncheckedAccount<'info>,
+
+    /// The payout redirect account that will be created or updated.
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        space = PayoutRedirect::SPACE,
+        seeds = [PAYOUT_REDIRECT_SEED, vault.key().as_ref(), stream.key().as_ref()],
+        bump,
+    )]
+    pub payout_redirect: Account<'info, PayoutRedirect>,
+
+    /// The quote token account that will receive the payouts.
+    #[account(token::mint = policy.quote_mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// The stream recipient, paying for the redirect account.
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    /// The system program account.
+    pub system_program: Program<'info, System>,
+}
@@ -8,6 +8,21 @@ pub const POLICY_SEED: &[u8] = b"policy";
 #[constant]
 pub const PROGRESS_SEED: &[u8] = b"progress";
 
+#[constant]
+pub const DAY_HISTORY_SEED: &[u8] = b"day_history";
+
+#[constant]
+pub const INVESTOR_STATS_SEED: &[u8] = b"investor_stats";
+
+#[constant]
+pub const PAYOUT_REDIRECT_SEED: &[u8] = b"payout_redirect";
+
+#[constant]
+pub const POSITION_REGISTRY_SEED: &[u8] = b"position_registry";
+
+#[constant]
+pub const REWARD_PROGRESS_SEED: &[u8] = b"reward_progress";
+
 #[constant]
 pub const VAULT_SEED: &[u8] = b"vault";
 
@@ -29,6 +44,8 @@ pub mod damm_v2_constants {
         pub const CLAIM_FEE_OPERATOR_PREFIX: &[u8] = b"cf_operator";
     }
 
+    pub const NUM_REWARDS: usize = 2; // Reward slots of a pool
+
     pub mod pool_authority {
         use anchor_lang::solana_program::pubkey::Pubkey;
         use const_crypto::ed25519;
@@ -45,6 +62,25 @@ pub mod damm_v2_constants {
     }
 }
 
+/// Event constants
+#[constant]
+pub const EVENT_SCHEMA_VERSION: u8 = 1; // Leading field of every event, bumped on layout changes
+
+/// Account layout constants
+pub const ACCOUNT_RESERVED_SPACE: usize = 64; // Spare bytes kept for future fields
+
+/// Day history constants
+#[constant]
+pub const DAY_HISTORY_LEN: usize = 30; // Closed days kept in the ring buffer
+
+/// Position registry constants
+#[constant]
+pub const MAX_HONORARY_POSITIONS: usize = 8; // Positions claimed by `claim_fees`
+
+/// Preview constants
+#[constant]
+pub const MAX_PREVIEW_PAGE_SIZE: u32 = 32; // Keeps the breakdown within the 1024 bytes of return data
+
 /// Time constants
 
 #[constant]
@@ -71,4 +71,90 @@ pub enum TollgateError {
     PolicyAlreadyInitialized,
     #[msg("The provided progress has already been initialized")]
     ProgressAlreadyInitialized,
+
+    // Account versioning errors
+    #[msg("The account version is stale, run migrate first")]
+    StaleAccountVersion,
+    #[msg("The account version is not supported by this program")]
+    UnsupportedAccountVersion,
+
+    // Preview errors
+    #[msg("Page size exceeds the maximum previewable page size")]
+    PreviewPageTooLarge,
+
+    // Math errors
+    #[msg("Math operation overflowed")]
+    MathOverflow,
+    #[msg("Math division by zero")]
+    MathDivisionByZero,
+
+    // Distribution errors
+    #[msg("The crank phase is invalid for this operation")]
+    InvalidCrankPhase,
+    #[msg("The investors do not match the day snapshot")]
+    SnapshotMismatch,
+    #[msg("The day payouts do not add up to the distributable amount")]
+    DistributionInvariantViolated,
+
+    // Day history errors
+    #[msg("Day history already initialized")]
+    DayHistoryAlreadyInitialized,
+
+    // Investor stats errors
+    #[msg("The investor stats account is invalid")]
+    InvalidInvestorStats,
+
+    // Payout redirect errors
+    #[msg("The payout redirect account is invalid")]
+    InvalidPayoutRedirect,
+    #[msg("The payout destination does not match the payout redirect")]
+    InvalidPayoutDestination,
+    #[msg("Only the stream recipient can set its payout destination")]
+    PayoutRedirectUnauthorized,
+
+    // Position registry errors
+    #[msg("The pool already has a registered position")]
+    PoolAlreadyRegistered,
+    #[msg("The position registry is full")]
+    PositionRegistryFull,
+    #[msg("The position accounts do not match the position registry")]
+    InvalidPositionAccounts,
+    #[msg("The day has not been opened, run claim_fees first")]
+    DayNotOpened,
+
+    // Liquidity errors
+    #[msg("The liquidity delta must be greater than 0")]
+    InvalidLiquidityDelta,
+
+    // Reward errors
+    #[msg("The reward index is not initialized on the pool")]
+    InvalidRewardIndex,
+    #[msg("The reward mint must differ from the vault pair")]
+    UnsupportedRewardMint,
+    #[msg("The day snapshot must be completed before claiming rewards")]
+    RewardSnapshotNotReady,
+    #[msg("The reward has already been claimed for the day")]
+    RewardAlreadyClaimed,
+
+    // Creator errors
+    #[msg("The creator cannot be the default pubkey")]
+    InvalidCreator,
+
+    // Carry errors
+    #[msg("The reserve must be released over at least one day")]
+    InvalidReleaseDays,
+
+    // Investor cap errors
+    #[msg("The investor cap must be greater than 0 and at most 100%")]
+    InvalidInvestorCap,
+
+    // Investor order errors
+    #[msg("The investor stream is already cranked in the pass")]
+    DuplicateInvestorStream,
+    #[msg("The investor streams must be in increasing order")]
+    InvestorStreamsOutOfOrder,
+
+    // Stream errors
+    #[msg("The stream is neither a Streamflow contract nor a closed stream of the vault")]
+    InvalidStreamAccount,
 }
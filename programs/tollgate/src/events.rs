@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 #[event]
 pub struct HonoraryPositionInitialized {
+    pub schema_version: u8,
     pub vault: Pubkey,
     pub policy: Pubkey,
     pub progress: Pubkey,
@@ -17,8 +18,36 @@ pub struct HonoraryPositionInitialized {
     pub y0: u64,
 }
 
+#[event]
+pub struct HonoraryPositionAdded {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub index: u8,
+    pub position_count: u8,
+}
+
+#[event]
+pub struct HonoraryLiquidityLocked {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub depositor: Pubkey,
+    pub liquidity_delta: u128,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub permanent_locked_liquidity: u128,
+}
+
 #[event]
 pub struct QuoteFeesClaimed {
+    pub schema_version: u8,
     pub vault: Pubkey,
     pub policy: Pubkey,
     pub progress: Pubkey,
@@ -29,8 +58,34 @@ pub struct QuoteFeesClaimed {
     pub quote_fee_claimed: u64,
 }
 
+#[event]
+pub struct PoolDisabled {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub progress: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub pool_status: u8,
+}
+
+#[event]
+pub struct RemainderSettled {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub progress: Pubkey,
+    pub remainder: u64,
+    pub swept: u64,
+    pub rolled_over: u64,
+    pub reserved: u64,
+    pub released: u64,
+    pub reserve: u64,
+}
+
 #[event]
 pub struct InvestorPayoutPage {
+    pub schema_version: u8,
     pub vault: Pubkey,
     pub policy: Pubkey,
     pub progress: Pubkey,
@@ -44,8 +99,127 @@ pub struct InvestorPayoutPage {
     pub payout: u64,
 }
 
+/// Why an investor of a page is not paid.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    Cancelled,  // The stream was cancelled
+    Paused,     // The stream is paused
+    Closed,     // The stream was closed, or its account emptied
+    BelowDust,  // The allocation is below the minimum payout
+    MissingAta, // The payout account does not exist
+    ZeroLocked, // Nothing is locked in the stream over the day
+}
+
+#[event]
+pub struct InvestorPaid {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub stream: Pubkey,
+    pub index: u32,
+    pub recipient: Pubkey,
+    pub ata: Pubkey, // Payout account, the redirect destination if any
+    pub locked: u64,
+    pub share_bps: u64, // Weight of the investor, in bps of the locked total of the day
+    pub amount: u64,
+}
+
+#[event]
+pub struct InvestorSkipped {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub stream: Pubkey,
+    pub index: u32,
+    pub reason: SkipReason,
+}
+
+#[event]
+pub struct InvestorCapped {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub stream: Pubkey,
+    pub index: u32,
+    pub locked: u64,
+    pub share: u64, // Pro-rata share of the investor pool before the cap
+    pub cap: u64,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub reward_claimed: u64,
+    pub carry: u64,
+    pub reward_pool: u64,
+    pub locked_total: u64,
+    pub investor_pool: u64,
+}
+
+#[event]
+pub struct RewardInvestorPaid {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub stream: Pubkey,
+    pub index: u32,
+    pub recipient: Pubkey,
+    pub reward_account: Pubkey,
+    pub locked: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardInvestorSkipped {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub stream: Pubkey,
+    pub index: u32,
+    pub reason: SkipReason,
+}
+
+#[event]
+pub struct RewardPayoutPage {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub cursor: u32,
+    pub investors: u32,
+    pub page_start: u32,
+    pub page_end: u32,
+    pub payout: u64,
+}
+
+#[event]
+pub struct RewardRoundClosed {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub reward_pool: u64,
+    pub investor_payouts: u64,
+    pub creator_payout: u64,
+    pub carry: u64,
+}
+
 #[event]
 pub struct CreatorPayoutDayClosed {
+    pub schema_version: u8,
     pub vault: Pubkey,
     pub policy: Pubkey,
     pub progress: Pubkey,
@@ -56,4 +230,36 @@ pub struct CreatorPayoutDayClosed {
     pub total_distributed: u64,
     pub creator_payout: u64,
     pub carry: u64,
+    pub distributable: u64,
+    pub investor_payouts: u64,
+}
+
+#[event]
+pub struct CreatorUpdated {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub previous_creator: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct PayoutDestinationSet {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct AccountsMigrated {
+    pub schema_version: u8,
+    pub vault: Pubkey,
+    pub policy: Pubkey,
+    pub progress: Pubkey,
+    pub policy_from_version: u8,
+    pub policy_version: u8,
+    pub progress_from_version: u8,
+    pub progress_version: u8,
 }
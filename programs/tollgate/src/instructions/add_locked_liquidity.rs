@@ -0,0 +1,234 @@
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+use crate::{
+    constants::{EVENT_SCHEMA_VERSION, INVESTOR_FEE_POS_OWNER, VAULT_SEED},
+    error::TollgateError,
+    events::HonoraryLiquidityLocked,
+    utils::{self, event::EventEmitter, token::TokenOrder},
+    AccountAddLockedLiquidity,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct AddLockedLiquidityParams {
+    pub liquidity_delta: u128,
+    pub max_base_amount: u64,
+    pub max_quote_amount: u64,
+}
+
+impl AddLockedLiquidityParams {
+    pub fn assert(&self) -> Result<()> {
+        // assert liquidity delta is greater than 0
+        require_gt!(
+            self.liquidity_delta,
+            0,
+            TollgateError::InvalidLiquidityDelta
+        );
+
+        Ok(())
+    }
+}
+
+pub fn add_locked_liquidity(
+    ctx: Context<AccountAddLockedLiquidity>,
+    params: AddLockedLiquidityParams,
+) -> Result<()> {
+    msg!(
+        "AddLockedLiquidity::Adding liquidity: liquidity_delta={}, max_base_amount={}, max_quote_amount={}",
+        params.liquidity_delta,
+        params.max_base_amount,
+        params.max_quote_amount
+    );
+
+    // Validate the liquidity parameters
+    params.assert()?;
+
+    // The pool must trade the vault pair
+    let quote_token_order = {
+        let pool = ctx.accounts.pool.load()?;
+        require!(
+            utils::token::get_token_order(&pool, &ctx.accounts.base_mint.key()).is_some(),
+            TollgateError::BaseMintNotInPool
+        );
+        utils::token::get_token_order(&pool, &ctx.accounts.quote_mint.key())
+            .ok_or(TollgateError::QuoteMintNotInPool)?
+    };
+
+    let vault_seeds = [
+        VAULT_SEED,
+        ctx.accounts.policy.vault.as_ref(),
+        INVESTOR_FEE_POS_OWNER,
+        &[ctx.accounts.policy.owner_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    // The owner accounts may hold fees of the day, only the deposit flows through them
+    let base_before = ctx.accounts.base_account.amount;
+    let quote_before = ctx.accounts.quote_account.amount;
+
+    msg!("AddLockedLiquidity::Transferring deposit to owner");
+    for (amount, from, to, token_program) in [
+        (
+            params.max_base_amount,
+            &ctx.accounts.depositor_base_account,
+            &ctx.accounts.base_account,
+            &ctx.accounts.base_program,
+        ),
+        (
+            params.max_quote_amount,
+            &ctx.accounts.depositor_quote_account,
+            &ctx.accounts.quote_account,
+            &ctx.accounts.quote_program,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = token_interface::Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        anchor_spl::token_interface::transfer(cpi_ctx, amount)?;
+    }
+    ctx.accounts.base_account.reload()?;
+    ctx.accounts.quote_account.reload()?;
+    let base_received = ctx.accounts.base_account.amount - base_before;
+    let quote_received = ctx.accounts.quote_account.amount - quote_before;
+
+    let (token_a_account, token_b_account) = match quote_token_order {
+        TokenOrder::A => (&ctx.accounts.quote_account, &ctx.accounts.base_account),
+        TokenOrder::B => (&ctx.accounts.base_account, &ctx.accounts.quote_account),
+    };
+    let (token_a_vault, token_b_vault) = match quote_token_order {
+        TokenOrder::A => (&ctx.accounts.quote_vault, &ctx.accounts.base_vault),
+        TokenOrder::B => (&ctx.accounts.base_vault, &ctx.accounts.quote_vault),
+    };
+    let (token_a_mint, token_b_mint) = match quote_token_order {
+        TokenOrder::A => (&ctx.accounts.quote_mint, &ctx.accounts.base_mint),
+        TokenOrder::B => (&ctx.accounts.base_mint, &ctx.accounts.quote_mint),
+    };
+    let (token_a_program, token_b_program) = match quote_token_order {
+        TokenOrder::A => (&ctx.accounts.quote_program, &ctx.accounts.base_program),
+        TokenOrder::B => (&ctx.accounts.base_program, &ctx.accounts.quote_program),
+    };
+    let (token_a_amount_threshold, token_b_amount_threshold) = match quote_token_order {
+        TokenOrder::A => (quote_received, base_received),
+        TokenOrder::B => (base_received, quote_received),
+    };
+
+    // Add DAMM v2 liquidity
+    msg!("AddLockedLiquidity::Adding DAMM v2 liquidity");
+    damm_v2::cpi::add_liquidity(
+        CpiContext::new_with_signer(
+            ctx.accounts.amm_program.to_account_info(),
+            damm_v2::cpi::accounts::AddLiquidity {
+                pool: ctx.accounts.pool.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                token_a_account: token_a_account.to_account_info(),
+                token_b_account: token_b_account.to_account_info(),
+                token_a_vault: token_a_vault.to_account_info(),
+                token_b_vault: token_b_vault.to_account_info(),
+                token_a_mint: token_a_mint.to_account_info(),
+                token_b_mint: token_b_mint.to_account_info(),
+                position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+                owner: ctx.accounts.owner.to_account_info(),
+                token_a_program: token_a_program.to_account_info(),
+                token_b_program: token_b_program.to_account_info(),
+                event_authority: ctx.accounts.amm_event_authority.to_account_info(),
+                program: ctx.accounts.amm_program.to_account_info(),
+            },
+            vault_signer,
+        ),
+        damm_v2::types::AddLiquidityParameters {
+            liquidity_delta: params.liquidity_delta,
+            token_a_amount_threshold,
+            token_b_amount_threshold,
+        },
+    )?;
+
+    // Permanently lock the added liquidity
+    msg!("AddLockedLiquidity::Permanently locking DAMM v2 liquidity");
+    damm_v2::cpi::permanent_lock_position(
+        CpiContext::new_with_signer(
+            ctx.accounts.amm_program.to_account_info(),
+            damm_v2::cpi::accounts::PermanentLockPosition {
+                pool: ctx.accounts.pool.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+                owner: ctx.accounts.owner.to_account_info(),
+                event_authority: ctx.accounts.amm_event_authority.to_account_info(),
+                program: ctx.accounts.amm_program.to_account_info(),
+            },
+            vault_signer,
+        ),
+        params.liquidity_delta,
+    )?;
+
+    // Refund the unused deposit, restoring the owner balances
+    ctx.accounts.base_account.reload()?;
+    ctx.accounts.quote_account.reload()?;
+    let base_refund = ctx.accounts.base_account.amount - base_before;
+    let quote_refund = ctx.accounts.quote_account.amount - quote_before;
+    for (amount, from, to, token_program) in [
+        (
+            base_refund,
+            &ctx.accounts.base_account,
+            &ctx.accounts.depositor_base_account,
+            &ctx.accounts.base_program,
+        ),
+        (
+            quote_refund,
+            &ctx.accounts.quote_account,
+            &ctx.accounts.depositor_quote_account,
+            &ctx.accounts.quote_program,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = token_interface::Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            vault_signer,
+        );
+        anchor_spl::token_interface::transfer(cpi_ctx, amount)?;
+    }
+
+    let base_amount = base_received - base_refund;
+    let quote_amount = quote_received - quote_refund;
+    let permanent_locked_liquidity = ctx.accounts.position.load()?.permanent_locked_liquidity;
+
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    EventEmitter::new(&event_authority, ctx.bumps.event_authority).emit(
+        HonoraryLiquidityLocked {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vault: ctx.accounts.policy.vault,
+            policy: ctx.accounts.policy.key(),
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            owner: ctx.accounts.owner.key(),
+            depositor: ctx.accounts.depositor.key(),
+            liquidity_delta: params.liquidity_delta,
+            base_amount,
+            quote_amount,
+            permanent_locked_liquidity,
+        },
+    )?;
+
+    msg!(
+        "AddLockedLiquidity::Locked liquidity {}: base_amount={}, quote_amount={}",
+        params.liquidity_delta,
+        base_amount,
+        quote_amount
+    );
+    Ok(())
+}
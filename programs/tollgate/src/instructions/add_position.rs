@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use super::initialize::assert_pool;
+use crate::{
+    constants::EVENT_SCHEMA_VERSION, events::HonoraryPositionAdded, state::HonoraryPosition,
+    utils::event::EventEmitter, AccountAddPosition,
+};
+
+pub fn add_position(ctx: Context<AccountAddPosition>) -> Result<()> {
+    msg!("AddPosition::Adding honorary position");
+
+    // The pool must trade the vault pair with quote-only fees, as checked by initialize
+    assert_pool(
+        &ctx.accounts.pool.load()?,
+        &ctx.accounts.pool_cfg.load()?,
+        &ctx.accounts.position_registry.base_mint,
+        &ctx.accounts.policy.quote_mint,
+    )?;
+
+    let index = ctx.accounts.position_registry.register(HonoraryPosition {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+    })?;
+
+    // Create a DAMM v2 position
+    msg!("AddPosition::Creating DAMM v2 position");
+    damm_v2::cpi::create_position(CpiContext::new(
+        ctx.accounts.amm_program.to_account_info(),
+        damm_v2::cpi::accounts::CreatePosition {
+            owner: ctx.accounts.owner.to_account_info(),
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            payer: ctx.accounts.payer.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            event_authority: ctx.accounts.amm_event_authority.to_account_info(),
+            program: ctx.accounts.amm_program.to_account_info(),
+        },
+    ))?;
+
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    EventEmitter::new(&event_authority, ctx.bumps.event_authority).emit(HonoraryPositionAdded {
+        schema_version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        policy: ctx.accounts.policy.key(),
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        index,
+        position_count: ctx.accounts.position_registry.position_count,
+    })?;
+
+    msg!(
+        "AddPosition::Position {} added at index {}",
+        ctx.accounts.position.key(),
+        index
+    );
+    Ok(())
+}
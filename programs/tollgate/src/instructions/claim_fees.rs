@@ -0,0 +1,487 @@
+#![allow(deprecated)]
+
+use std::cell::Ref;
+
+use anchor_lang::{prelude::*, ZeroCopy};
+use anchor_spl::token_interface;
+
+use super::crank::close_day;
+use crate::{
+    constants::{EVENT_SCHEMA_VERSION, INVESTOR_FEE_POS_OWNER, VAULT_SEED},
+    error::TollgateError,
+    events::{PoolDisabled, QuoteFeesClaimed, RemainderSettled},
+    state::{CarryPolicy, DayState, Policy, Progress},
+    utils::{self, event::EventEmitter, pool::is_initialized_pool},
+    AccountClaimFees,
+};
+
+/// Accounts of a honorary position whose fees are claimed.
+struct ClaimedPosition<'a, 'info> {
+    pool: &'a AccountInfo<'info>,
+    pool_status: u8,
+    position: &'a AccountInfo<'info>,
+    position_nft_account: &'a AccountInfo<'info>,
+    base_vault: &'a AccountInfo<'info>,
+    quote_vault: &'a AccountInfo<'info>,
+}
+
+fn claim_position_fees<'info>(
+    ctx: &Context<'_, '_, '_, 'info, AccountClaimFees<'info>>,
+    claimed: ClaimedPosition<'_, 'info>,
+    quote_token_order: utils::token::TokenOrder,
+    fee_a_pending: u64,
+    fee_b_pending: u64,
+    vault_signer: &[&[&[u8]]],
+    events: &EventEmitter<'_, 'info>,
+) -> Result<u64> {
+    // A disabled pool cannot be claimed, its fees stay pending on the position
+    // while the balance already held is distributed
+    if claimed.pool_status != 0 {
+        msg!(
+            "ClaimFees::Pool {} is disabled, skipping the DAMM v2 claim",
+            claimed.pool.key()
+        );
+        events.emit(PoolDisabled {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vault: ctx.accounts.policy.vault,
+            policy: ctx.accounts.policy.key(),
+            progress: ctx.accounts.progress.key(),
+            pool: claimed.pool.key(),
+            position: claimed.position.key(),
+            pool_status: claimed.pool_status,
+        })?;
+        return Ok(0);
+    }
+
+    let (base_fee, quote_fee) = split_pending_fees(quote_token_order, fee_a_pending, fee_b_pending);
+
+    require_eq!(base_fee, 0, TollgateError::BaseDenominatedFees);
+
+    if quote_fee > 0 {
+        let token_a_account = match quote_token_order {
+            utils::token::TokenOrder::A => ctx.accounts.quote_account.to_account_info(),
+            utils::token::TokenOrder::B => ctx.accounts.base_account.to_account_info(),
+        };
+
+        let token_b_account = match quote_token_order {
+            utils::token::TokenOrder::A => ctx.accounts.base_account.to_account_info(),
+            utils::token::TokenOrder::B => ctx.accounts.quote_account.to_account_info(),
+        };
+
+        let token_a_vault = match quote_token_order {
+            utils::token::TokenOrder::A => claimed.quote_vault.clone(),
+            utils::token::TokenOrder::B => claimed.base_vault.clone(),
+        };
+
+        let token_b_vault = match quote_token_order {
+            utils::token::TokenOrder::A => claimed.base_vault.clone(),
+            utils::token::TokenOrder::B => claimed.quote_vault.clone(),
+        };
+
+        let token_a_mint = match quote_token_order {
+            utils::token::TokenOrder::A => ctx.accounts.quote_mint.to_account_info(),
+            utils::token::TokenOrder::B => ctx.accounts.base_mint.to_account_info(),
+        };
+
+        let token_b_mint = match quote_token_order {
+            utils::token::TokenOrder::A => ctx.accounts.base_mint.to_account_info(),
+            utils::token::TokenOrder::B => ctx.accounts.quote_mint.to_account_info(),
+        };
+
+        let token_a_program = match quote_token_order {
+            utils::token::TokenOrder::A => ctx.accounts.quote_program.to_account_info(),
+            utils::token::TokenOrder::B => ctx.accounts.base_program.to_account_info(),
+        };
+
+        let token_b_program = match quote_token_order {
+            utils::token::TokenOrder::A => ctx.accounts.base_program.to_account_info(),
+            utils::token::TokenOrder::B => ctx.accounts.quote_program.to_account_info(),
+        };
+
+        // Claim DAMM v2 position fee
+        msg!(
+            "ClaimFees::Claiming DAMM v2 position fee: quote_fee={}",
+            quote_fee
+        );
+        damm_v2::cpi::claim_position_fee(CpiContext::new_with_signer(
+            ctx.accounts.amm_program.to_account_info(),
+            damm_v2::cpi::accounts::ClaimPositionFee {
+                pool_authority: ctx.accounts.pool_authority.to_account_info(),
+                pool: claimed.pool.clone(),
+                position: claimed.position.clone(),
+                token_a_account,
+                token_b_account,
+                token_a_vault,
+                token_b_vault,
+                token_a_mint,
+                token_b_mint,
+                position_nft_account: claimed.position_nft_account.clone(),
+                owner: ctx.accounts.owner.to_account_info(),
+                token_a_program,
+                token_b_program,
+                event_authority: ctx.accounts.amm_event_authority.to_account_info(),
+                program: ctx.accounts.amm_program.to_account_info(),
+            },
+            vault_signer,
+        ))?;
+
+        // Emit QuoteFeesClaimed event
+        events.emit(QuoteFeesClaimed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vault: ctx.accounts.policy.vault,
+            policy: ctx.accounts.policy.key(),
+            progress: ctx.accounts.progress.key(),
+            pool: claimed.pool.key(),
+            position: claimed.position.key(),
+            owner: ctx.accounts.owner.key(),
+            base_fee_claimed: base_fee,
+            quote_fee_claimed: quote_fee,
+        })?;
+    }
+
+    Ok(quote_fee)
+}
+
+/// Claims the fees of the positions registered after the primary one, passed
+/// as (pool, position, position NFT account, base vault, quote vault) groups
+/// in registry order. Returns the quote fees claimed.
+fn claim_registered_positions<'info>(
+    ctx: &Context<'_, '_, '_, 'info, AccountClaimFees<'info>>,
+    position_accounts: &[AccountInfo<'info>],
+    vault_signer: &[&[&[u8]]],
+    events: &EventEmitter<'_, 'info>,
+) -> Result<u64> {
+    let registered = &ctx.accounts.position_registry.positions()[1..];
+    require_eq!(
+        registered.len() * 5,
+        position_accounts.len(),
+        TollgateError::InvalidPositionAccounts
+    );
+
+    let mut quote_fees = 0u64;
+    for (entry, group) in registered.iter().zip(position_accounts.chunks(5)) {
+        require!(
+            group[0].key() == entry.pool && group[1].key() == entry.position,
+            TollgateError::InvalidPositionAccounts
+        );
+
+        let (fee_a_pending, fee_b_pending, quote_token_order, pool_status) = {
+            let pool = load_damm_v2_account::<damm_v2::accounts::Pool>(&group[0]).ok();
+            require!(is_initialized_pool(&pool), TollgateError::InvalidPool);
+            let pool = pool.as_deref().ok_or(TollgateError::InvalidPool)?;
+            let position = load_damm_v2_account::<damm_v2::accounts::Position>(&group[1])?;
+            let (fee_a_pending, fee_b_pending, quote_token_order) =
+                pending_fees_of(&ctx.accounts.policy.quote_mint, pool, &position)?;
+            (
+                fee_a_pending,
+                fee_b_pending,
+                quote_token_order,
+                pool.pool_status,
+            )
+        };
+        let claimed = ClaimedPosition {
+            pool: &group[0],
+            pool_status,
+            position: &group[1],
+            position_nft_account: &group[2],
+            base_vault: &group[3],
+            quote_vault: &group[4],
+        };
+
+        let quote_fee = claim_position_fees(
+            ctx,
+            claimed,
+            quote_token_order,
+            fee_a_pending,
+            fee_b_pending,
+            vault_signer,
+            events,
+        )?;
+        quote_fees = quote_fees
+            .checked_add(quote_fee)
+            .ok_or(TollgateError::MathOverflow)?;
+    }
+
+    Ok(quote_fees)
+}
+
+/// Borrows a zero-copy DAMM v2 account passed in the remaining accounts.
+fn load_damm_v2_account<'a, T: ZeroCopy + Owner>(info: &'a AccountInfo) -> Result<Ref<'a, T>> {
+    require_keys_eq!(
+        *info.owner,
+        T::owner(),
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let data = info.try_borrow_data()?;
+    let size = T::DISCRIMINATOR.len() + std::mem::size_of::<T>();
+    require!(
+        data.len() >= size && data.starts_with(T::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(Ref::map(data, |data| {
+        bytemuck::from_bytes(&data[T::DISCRIMINATOR.len()..size])
+    }))
+}
+
+/// Splits the pending position fees into (base, quote) fees.
+pub(crate) fn split_pending_fees(
+    quote_token_order: utils::token::TokenOrder,
+    fee_a_pending: u64,
+    fee_b_pending: u64,
+) -> (u64, u64) {
+    match quote_token_order {
+        utils::token::TokenOrder::A => (fee_b_pending, fee_a_pending),
+        utils::token::TokenOrder::B => (fee_a_pending, fee_b_pending),
+    }
+}
+
+/// Loads the pending fees of the primary position and the quote token order of its pool.
+pub(crate) fn load_pending_fees(
+    accounts: &AccountClaimFees,
+) -> Result<(u64, u64, utils::token::TokenOrder)> {
+    // Load pool and position accounts
+    let pool = &accounts.pool.load()?;
+    let position = &accounts.position.load()?;
+    pending_fees_of(&accounts.policy.quote_mint, pool, position)
+}
+
+/// Returns the pending fees of a position and the quote token order of its pool.
+pub(crate) fn pending_fees_of(
+    policy_quote_mint: &Pubkey,
+    pool: &damm_v2::accounts::Pool,
+    position: &damm_v2::accounts::Position,
+) -> Result<(u64, u64, utils::token::TokenOrder)> {
+    // Determine base/quote mints
+    let (base_mint, quote_mint) = if *policy_quote_mint == pool.token_a_mint {
+        (&pool.token_b_mint, &pool.token_a_mint)
+    } else {
+        (&pool.token_a_mint, &pool.token_b_mint)
+    };
+
+    // Determine base/quote token order
+    let base_token_order = utils::token::get_token_order(pool, base_mint);
+    let quote_token_order = utils::token::get_token_order(pool, quote_mint);
+
+    // Check base/quote token order is valid
+    require!(base_token_order.is_some(), TollgateError::BaseMintNotInPool);
+    let Some(quote_token_order) = quote_token_order else {
+        return Err(TollgateError::QuoteMintNotInPool.into());
+    };
+
+    Ok((
+        position.fee_a_pending,
+        position.fee_b_pending,
+        quote_token_order,
+    ))
+}
+
+/// Split of the previous day remainder when a new day is opened.
+#[derive(Debug, Default)]
+pub(crate) struct RemainderSettlement {
+    pub remainder: u64,   // Quote balance held on top of the carry and the reserve
+    pub swept: u64,       // Swept to the creator
+    pub rolled_over: u64, // Rolled into the distributable of the day
+    pub reserved: u64,    // Added to the reserve
+    pub released: u64,    // Released from the reserve into the distributable of the day
+}
+
+/// Settles the previous day remainder according to the carry policy, before
+/// the new day is started. The remainder is the quote balance held on top of
+/// the carry and the reserve, made of the excess over the daily cap. A day
+/// that was opened but never cranked is rolled into the new one.
+pub(crate) fn settle_remainder(
+    quote_amount: u64,
+    policy: &Policy,
+    progress: &mut Progress,
+) -> Result<RemainderSettlement> {
+    let remainder = if progress.day_state == DayState::New && progress.last_distribution_ts != 0 {
+        0
+    } else {
+        quote_amount
+            .saturating_sub(progress.reserve)
+            .saturating_sub(progress.carry)
+    };
+
+    let mut settlement = RemainderSettlement {
+        remainder,
+        ..Default::default()
+    };
+    match policy.carry_policy {
+        CarryPolicy::SweepToCreator => settlement.swept = remainder,
+        CarryPolicy::RollOver => settlement.rolled_over = remainder,
+        CarryPolicy::Reserve { release_days } => {
+            if remainder != 0 {
+                progress.hold_reserve(remainder, release_days)?;
+            }
+            settlement.reserved = remainder;
+        }
+    }
+    settlement.released = progress.release_reserve();
+
+    Ok(settlement)
+}
+
+/// Opens a new day: settles the previous day remainder, claims
+/// the fees of every registered position, the extra ones being passed in the
+/// remaining accounts, and snapshots the distributable of the day.
+pub fn claim_fees<'info>(ctx: Context<'_, '_, '_, 'info, AccountClaimFees<'info>>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "ClaimFees::Claiming fees of {} positions",
+        ctx.accounts.position_registry.position_count
+    );
+
+    require!(
+        ctx.accounts.progress.is_new_day(timestamp),
+        TollgateError::CannotStartNewDay
+    );
+
+    let vault_seeds = &[
+        VAULT_SEED,
+        ctx.accounts.policy.vault.as_ref(),
+        INVESTOR_FEE_POS_OWNER,
+        &[ctx.accounts.policy.owner_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    let events = EventEmitter::new(&event_authority, ctx.bumps.event_authority);
+
+    let settlement = settle_remainder(
+        ctx.accounts.quote_account.amount,
+        &ctx.accounts.policy,
+        &mut ctx.accounts.progress,
+    )?;
+    if settlement.swept != 0 {
+        let cpi_accounts = token_interface::Transfer {
+            from: ctx.accounts.quote_account.to_account_info(),
+            to: ctx.accounts.creator_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.quote_program.to_account_info(),
+            cpi_accounts,
+            vault_signer,
+        );
+        anchor_spl::token_interface::transfer(cpi_ctx, settlement.swept)?;
+        msg!(
+            "ClaimFees::Transferred previous day remainder to creator: {}",
+            settlement.swept
+        );
+    }
+    if settlement.rolled_over != 0 {
+        msg!(
+            "ClaimFees::Rolled previous day remainder over: {}",
+            settlement.rolled_over
+        );
+    }
+    if settlement.reserved != 0 || settlement.released != 0 {
+        msg!(
+            "ClaimFees::Reserved previous day remainder: {}, released: {}, reserve: {}",
+            settlement.reserved,
+            settlement.released,
+            ctx.accounts.progress.reserve
+        );
+    }
+    if settlement.remainder != 0 || settlement.released != 0 {
+        events.emit(RemainderSettled {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vault: ctx.accounts.policy.vault,
+            policy: ctx.accounts.policy.key(),
+            progress: ctx.accounts.progress.key(),
+            remainder: settlement.remainder,
+            swept: settlement.swept,
+            rolled_over: settlement.rolled_over,
+            reserved: settlement.reserved,
+            released: settlement.released,
+            reserve: ctx.accounts.progress.reserve,
+        })?;
+    }
+    ctx.accounts.progress.start_new_day(timestamp)?;
+    ctx.accounts.day_history.load_mut()?.open_day();
+
+    // Load the pending position fees
+    let (fee_a_pending, fee_b_pending, quote_token_order) = load_pending_fees(ctx.accounts)?;
+    let pool_status = ctx.accounts.pool.load()?.pool_status;
+
+    let quote_fee = claim_position_fees(
+        &ctx,
+        ClaimedPosition {
+            pool: &ctx.accounts.pool.to_account_info(),
+            pool_status,
+            position: &ctx.accounts.position.to_account_info(),
+            position_nft_account: &ctx.accounts.position_nft_account.to_account_info(),
+            base_vault: &ctx.accounts.base_vault.to_account_info(),
+            quote_vault: &ctx.accounts.quote_vault.to_account_info(),
+        },
+        quote_token_order,
+        fee_a_pending,
+        fee_b_pending,
+        vault_signer,
+        &events,
+    )?
+    .checked_add(claim_registered_positions(
+        &ctx,
+        ctx.remaining_accounts,
+        vault_signer,
+        &events,
+    )?)
+    .ok_or(TollgateError::MathOverflow)?;
+    msg!(
+        "ClaimFees::Claimed quote fees of {} positions: {}",
+        ctx.accounts.position_registry.position_count,
+        quote_fee
+    );
+    ctx.accounts
+        .day_history
+        .load_mut()?
+        .record_fees_claimed(quote_fee)?;
+
+    // The balance held, less the reserve and the swept remainder
+    let mut distributable = quote_fee.saturating_add(
+        ctx.accounts
+            .quote_account
+            .amount
+            .saturating_sub(ctx.accounts.progress.reserve)
+            .saturating_sub(settlement.swept),
+    );
+
+    // Optional daily cap
+    if let Some(cap) = ctx.accounts.policy.daily_cap {
+        distributable = distributable.min(cap);
+        msg!(
+            "ClaimFees::Applied daily cap, cap={}, distributable={}",
+            cap,
+            distributable
+        );
+    }
+
+    // Snapshot the distributable amount for the rest of the day
+    ctx.accounts.progress.distributable = distributable;
+    msg!(
+        "ClaimFees::Distributable amount after carry: {}",
+        distributable
+    );
+
+    if distributable < ctx.accounts.policy.min_payout_lamports {
+        ctx.accounts.progress.carry = distributable;
+        msg!(
+            "ClaimFees::Distributable below min payout, carrying over: {}",
+            distributable
+        );
+        close_day(
+            &ctx.accounts.policy,
+            &mut ctx.accounts.progress,
+            &ctx.accounts.day_history,
+            &ctx.accounts.position_registry.positions()[0],
+            ctx.accounts.owner.key(),
+            timestamp,
+            0,
+            0,
+            &events,
+        )?;
+    }
+
+    msg!("ClaimFees::Completed successfully");
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+
+use super::crank::compute_investor_fee_quote;
+use crate::{
+    constants::{damm_v2_constants, EVENT_SCHEMA_VERSION, INVESTOR_FEE_POS_OWNER, VAULT_SEED},
+    error::TollgateError,
+    events::RewardClaimed,
+    state::{CrankPhase, RewardProgress},
+    utils::event::EventEmitter,
+    AccountClaimReward,
+};
+
+pub fn claim_reward(ctx: Context<AccountClaimReward>, reward_index: u8) -> Result<()> {
+    msg!("ClaimReward::Claiming reward {}", reward_index);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    // The reward must be initialized on the pool, in a token outside the vault pair
+    require_gt!(
+        damm_v2_constants::NUM_REWARDS,
+        reward_index as usize,
+        TollgateError::InvalidRewardIndex
+    );
+    {
+        let pool = ctx.accounts.pool.load()?;
+        let reward_info = &pool.reward_infos[reward_index as usize];
+        require!(
+            reward_info.initialized != 0
+                && reward_info.mint == ctx.accounts.reward_mint.key()
+                && reward_info.vault == ctx.accounts.reward_vault.key(),
+            TollgateError::InvalidRewardIndex
+        );
+        require!(
+            reward_info.mint != pool.token_a_mint && reward_info.mint != pool.token_b_mint,
+            TollgateError::UnsupportedRewardMint
+        );
+    }
+
+    let reward_progress = &mut ctx.accounts.reward_progress;
+    if reward_progress.version == 0 {
+        msg!("ClaimReward::Initializing reward progress");
+        reward_progress.initialize(
+            ctx.accounts.policy.vault,
+            reward_index,
+            ctx.accounts.reward_mint.key(),
+            ctx.bumps.reward_progress,
+        );
+    }
+    require_eq!(
+        reward_progress.version,
+        RewardProgress::VERSION,
+        TollgateError::StaleAccountVersion
+    );
+
+    // The rewards are split over the completed snapshot of the current day
+    let progress = &ctx.accounts.progress;
+    require!(
+        progress.phase == CrankPhase::Payout && progress.is_same_day(timestamp),
+        TollgateError::RewardSnapshotNotReady
+    );
+    if !reward_progress.closed {
+        msg!("ClaimReward::Previous round left open, carrying its unpaid rewards");
+    }
+
+    let vault_seeds = [
+        VAULT_SEED,
+        ctx.accounts.policy.vault.as_ref(),
+        INVESTOR_FEE_POS_OWNER,
+        &[ctx.accounts.policy.owner_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    // Rewards left over by previous rounds are split again
+    let carry = ctx.accounts.reward_account.amount;
+
+    // Claim DAMM v2 position reward
+    damm_v2::cpi::claim_reward(
+        CpiContext::new_with_signer(
+            ctx.accounts.amm_program.to_account_info(),
+            damm_v2::cpi::accounts::ClaimReward {
+                pool_authority: ctx.accounts.pool_authority.to_account_info(),
+                pool: ctx.accounts.pool.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                reward_vault: ctx.accounts.reward_vault.to_account_info(),
+                reward_mint: ctx.accounts.reward_mint.to_account_info(),
+                user_token_account: ctx.accounts.reward_account.to_account_info(),
+                position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+                owner: ctx.accounts.owner.to_account_info(),
+                token_program: ctx.accounts.reward_program.to_account_info(),
+                event_authority: ctx.accounts.amm_event_authority.to_account_info(),
+                program: ctx.accounts.amm_program.to_account_info(),
+            },
+            vault_signer,
+        ),
+        reward_index,
+        0,
+    )?;
+
+    ctx.accounts.reward_account.reload()?;
+    let reward_pool = ctx.accounts.reward_account.amount;
+    let reward_claimed = reward_pool
+        .checked_sub(carry)
+        .ok_or(TollgateError::MathOverflow)?;
+    msg!(
+        "ClaimReward::Claimed reward: {}, carry: {}",
+        reward_claimed,
+        carry
+    );
+
+    // Same split as the quote fees of the day
+    let locked_total = ctx.accounts.progress.locked_total;
+    let (_, _, investor_pool) =
+        compute_investor_fee_quote(&ctx.accounts.policy, reward_pool, locked_total)?;
+    ctx.accounts.reward_progress.open_round(
+        ctx.accounts.progress.last_distribution_ts,
+        reward_pool,
+        carry,
+        locked_total,
+        investor_pool,
+    )?;
+
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    EventEmitter::new(&event_authority, ctx.bumps.event_authority).emit(RewardClaimed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.policy.vault,
+        policy: ctx.accounts.policy.key(),
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        reward_index,
+        reward_mint: ctx.accounts.reward_mint.key(),
+        reward_claimed,
+        carry,
+        reward_pool,
+        locked_total,
+        investor_pool,
+    })?;
+
+    msg!(
+        "ClaimReward::Opened round, reward pool: {}, investor pool: {}",
+        reward_pool,
+        investor_pool
+    );
+    Ok(())
+}
@@ -8,11 +8,22 @@ use anchor_spl::{
 use streamflow_sdk::state::Contract;
 
 use crate::{
-    constants::{INVESTOR_FEE_POS_OWNER, MAX_BPS, VAULT_SEED},
+    constants::{
+        EVENT_SCHEMA_VERSION, INVESTOR_FEE_POS_OWNER, INVESTOR_STATS_SEED, MAX_BPS,
+        PAYOUT_REDIRECT_SEED, TWENTY_FOUR_HOURS, VAULT_SEED,
+    },
     error::TollgateError,
-    events::{CreatorPayoutDayClosed, InvestorPayoutPage, QuoteFeesClaimed},
-    state::{DayState, Policy},
-    utils, AccountCrank,
+    events::{
+        CreatorPayoutDayClosed, InvestorCapped, InvestorPaid, InvestorPayoutPage, InvestorSkipped,
+        SkipReason,
+    },
+    math,
+    state::{
+        CrankPhase, DayHistory, DayState, HonoraryPosition, InvestorStats, PayoutRedirect, Policy,
+        Progress,
+    },
+    utils::{self, event::EventEmitter},
+    AccountCrankPage,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
@@ -37,158 +48,329 @@ impl CrankParams {
     }
 }
 
-fn claim_position_fees<'info>(
-    ctx: &Context<'_, '_, '_, 'info, AccountCrank<'info>>,
-    quote_token_order: utils::token::TokenOrder,
-    fee_a_pending: u64,
-    fee_b_pending: u64,
-    vault_signer: &[&[&[u8]]],
-) -> Result<u64> {
-    let (base_fee, quote_fee) = match quote_token_order {
-        utils::token::TokenOrder::A => (fee_b_pending, fee_a_pending),
-        utils::token::TokenOrder::B => (fee_a_pending, fee_b_pending),
+/// Returns (f_locked, eligible_investor_share_bps, investor_fee_quote) of a page.
+pub(crate) fn compute_investor_fee_quote(
+    policy: &Policy,
+    distributable: u64,
+    locked_total: u64,
+) -> Result<(u64, u64, u64)> {
+    let f_locked = math::locked_fraction_bps(locked_total, policy.y0)?;
+    let eligible_investor_share_bps = (policy.investor_fee_share_bps as u64).min(f_locked);
+    let investor_fee_quote = math::bps_share(distributable, eligible_investor_share_bps)?;
+    Ok((f_locked, eligible_investor_share_bps, investor_fee_quote))
+}
+
+/// Contract of a page stream, or the reason the stream carries no weight.
+pub(crate) enum PageStream {
+    Active(Box<Contract>),
+    Skipped(SkipReason),
+}
+
+/// Returns whether the investor stats or payout redirect account of the vault
+/// records the stream at its PDA, which binds the stream key to a former investor.
+fn records_stream(account: &AccountInfo, vault: &Pubkey, stream: &Pubkey) -> Result<bool> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(false);
+    }
+
+    let data = account.try_borrow_data()?;
+    let (seed, bump) = if data.starts_with(InvestorStats::DISCRIMINATOR) {
+        let stats = InvestorStats::try_deserialize(&mut &data[..])?;
+        (INVESTOR_STATS_SEED, stats.bump)
+    } else if data.starts_with(PayoutRedirect::DISCRIMINATOR) {
+        let redirect = PayoutRedirect::try_deserialize(&mut &data[..])?;
+        (PAYOUT_REDIRECT_SEED, redirect.bump)
+    } else {
+        return Ok(false);
     };
+    let address = Pubkey::create_program_address(
+        &[seed, vault.as_ref(), stream.as_ref(), &[bump]],
+        &crate::ID,
+    );
+    Ok(address.is_ok_and(|address| address == *account.key))
+}
 
-    require_eq!(base_fee, 0, TollgateError::BaseDenominatedFees);
+/// Loads the contract of a stream, detecting streams that no longer vest.
+fn load_page_stream(
+    stream: &AccountInfo,
+    stats: &AccountInfo,
+    redirect: &AccountInfo,
+    vault: &Pubkey,
+) -> Result<PageStream> {
+    // Closed accounts are emptied and handed back to the system program, so
+    // an empty account is only taken for a stream the vault recorded
+    if stream.data_is_empty() {
+        require!(
+            records_stream(stats, vault, stream.key)?
+                || records_stream(redirect, vault, stream.key)?,
+            TollgateError::InvalidStreamAccount
+        );
+        return Ok(PageStream::Skipped(SkipReason::Closed));
+    }
 
-    if quote_fee > 0 {
-        let token_a_account = match quote_token_order {
-            utils::token::TokenOrder::A => ctx.accounts.quote_account.to_account_info(),
-            utils::token::TokenOrder::B => ctx.accounts.base_account.to_account_info(),
-        };
+    require_keys_eq!(
+        *stream.owner,
+        streamflow_sdk::ID,
+        TollgateError::InvalidStreamAccount
+    );
+    let contract = try_from_slice_unchecked::<Contract>(&stream.data.borrow())?;
+    let reason = if contract.canceled_at != 0 {
+        Some(SkipReason::Cancelled)
+    } else if contract.closed {
+        Some(SkipReason::Closed)
+    } else if contract.current_pause_start != 0 {
+        Some(SkipReason::Paused)
+    } else {
+        None
+    };
 
-        let token_b_account = match quote_token_order {
-            utils::token::TokenOrder::A => ctx.accounts.base_account.to_account_info(),
-            utils::token::TokenOrder::B => ctx.accounts.quote_account.to_account_info(),
-        };
+    Ok(reason.map_or(PageStream::Active(Box::new(contract)), PageStream::Skipped))
+}
 
-        let token_a_vault = match quote_token_order {
-            utils::token::TokenOrder::A => ctx.accounts.quote_vault.to_account_info(),
-            utils::token::TokenOrder::B => ctx.accounts.base_vault.to_account_info(),
-        };
+/// Returns the unlock schedule of an active Streamflow contract, following
+/// `Contract::available_to_claim` without the withdrawals: the cliff amount
+/// and the amount unlocked before the last rate change, then the periodic
+/// unlocks at the current rate, shifted by the past pauses.
+pub(crate) fn unlock_schedule(contract: &Contract) -> math::UnlockSchedule {
+    math::UnlockSchedule {
+        amount: contract.ix.net_amount_deposited,
+        start: contract.start_time(),
+        end: contract.end_time,
+        base: contract
+            .ix
+            .cliff_amount
+            .saturating_add(contract.funds_unlocked_at_last_rate_change),
+        steps_start: contract
+            .effective_start_time()
+            .saturating_add(contract.pause_cumulative),
+        period: contract.ix.period,
+        amount_per_period: contract.ix.amount_per_period,
+    }
+}
 
-        let token_b_vault = match quote_token_order {
-            utils::token::TokenOrder::A => ctx.accounts.base_vault.to_account_info(),
-            utils::token::TokenOrder::B => ctx.accounts.quote_vault.to_account_info(),
+/// Computes contracts and their weights for a page of streams: the average
+/// locked amount over `[epoch_start, epoch_start + 24h)`, so that the weight
+/// does not depend on when the page lands. Skipped streams are zero-weight.
+pub(crate) fn compute_page_contracts_and_locked(
+    streams: &[AccountInfo],
+    stats: &[AccountInfo],
+    redirects: &[AccountInfo],
+    vault: &Pubkey,
+    epoch_start: u64,
+) -> Result<(Vec<PageStream>, Vec<u64>)> {
+    let epoch_end = epoch_start.saturating_add(TWENTY_FOUR_HOURS as u64);
+    let mut contracts = Vec::with_capacity(streams.len());
+    let mut lockeds = Vec::with_capacity(streams.len());
+    for (i, stream) in streams.iter().enumerate() {
+        let contract = load_page_stream(stream, &stats[i], &redirects[i], vault)?;
+        let locked = match &contract {
+            PageStream::Active(contract) => {
+                math::average_locked(&unlock_schedule(contract), epoch_start, epoch_end)?
+            }
+            PageStream::Skipped(_) => 0,
         };
+        contracts.push(contract);
+        lockeds.push(locked);
+    }
+    Ok((contracts, lockeds))
+}
 
-        let token_a_mint = match quote_token_order {
-            utils::token::TokenOrder::A => ctx.accounts.quote_mint.to_account_info(),
-            utils::token::TokenOrder::B => ctx.accounts.base_mint.to_account_info(),
-        };
+/// Creates the missing investor stats of the streams of a page. The stats
+/// bind every stream cranked by the vault to it, so that the stream is still
+/// taken once its account is closed.
+pub(crate) fn bind_page_streams<'info>(
+    streams: &[AccountInfo<'info>],
+    stats: &[AccountInfo<'info>],
+    vault: &Pubkey,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    for (stream, stats_ai) in streams.iter().zip(stats) {
+        // A closed stream is already bound, or rejected by `load_page_stream`
+        if stream.data_is_empty() || (stats_ai.owner == &crate::ID && !stats_ai.data_is_empty()) {
+            continue;
+        }
 
-        let token_b_mint = match quote_token_order {
-            utils::token::TokenOrder::A => ctx.accounts.base_mint.to_account_info(),
-            utils::token::TokenOrder::B => ctx.accounts.quote_mint.to_account_info(),
-        };
+        let (expected_stats, bump) = Pubkey::find_program_address(
+            &[INVESTOR_STATS_SEED, vault.as_ref(), stream.key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            stats_ai.key(),
+            expected_stats,
+            TollgateError::InvalidInvestorStats
+        );
+        let recipient = try_from_slice_unchecked::<Contract>(&stream.data.borrow())?.recipient;
+        utils::account::create_pda(
+            stats_ai,
+            InvestorStats::SPACE,
+            &[
+                INVESTOR_STATS_SEED,
+                vault.as_ref(),
+                stream.key.as_ref(),
+                &[bump],
+            ],
+            &payer.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+        InvestorStats::new(*vault, *stream.key, recipient, bump)
+            .try_serialize(&mut &mut stats_ai.try_borrow_mut_data()?[..])?;
+    }
 
-        let token_a_program = match quote_token_order {
-            utils::token::TokenOrder::A => ctx.accounts.quote_program.to_account_info(),
-            utils::token::TokenOrder::B => ctx.accounts.base_program.to_account_info(),
-        };
+    Ok(())
+}
 
-        let token_b_program = match quote_token_order {
-            utils::token::TokenOrder::A => ctx.accounts.base_program.to_account_info(),
-            utils::token::TokenOrder::B => ctx.accounts.quote_program.to_account_info(),
-        };
+/// Loads the stats of the investor of a stream, bound by `bind_page_streams`.
+pub(crate) fn load_investor_stats(
+    stats_ai: &AccountInfo,
+    vault: &Pubkey,
+    stream: &Pubkey,
+) -> Result<InvestorStats> {
+    require!(
+        stats_ai.owner == &crate::ID && !stats_ai.data_is_empty(),
+        TollgateError::InvalidInvestorStats
+    );
+    let stats = InvestorStats::try_deserialize(&mut &stats_ai.try_borrow_data()?[..])?;
+    require!(
+        stats.vault == *vault && stats.stream == *stream,
+        TollgateError::InvalidInvestorStats
+    );
+    Ok(stats)
+}
 
-        // Claim DAMM v2 position fee
-        msg!(
-            "Crank::Claiming DAMM v2 position fee: quote_fee={}",
-            quote_fee
+/// Loads the payout redirect of a stream. A missing redirect must still be
+/// passed as its PDA, so that a crank cannot leave an existing redirect out.
+pub(crate) fn load_payout_redirect(
+    redirect_ai: &AccountInfo,
+    vault: &Pubkey,
+    stream: &Pubkey,
+) -> Result<Option<PayoutRedirect>> {
+    if redirect_ai.owner == &crate::ID && !redirect_ai.data_is_empty() {
+        let redirect = PayoutRedirect::try_deserialize(&mut &redirect_ai.try_borrow_data()?[..])?;
+        require!(
+            redirect.vault == *vault && redirect.stream == *stream,
+            TollgateError::InvalidPayoutRedirect
         );
-        damm_v2::cpi::claim_position_fee(CpiContext::new_with_signer(
-            ctx.accounts.amm_program.to_account_info(),
-            damm_v2::cpi::accounts::ClaimPositionFee {
-                pool_authority: ctx.accounts.pool_authority.to_account_info(),
-                pool: ctx.accounts.pool.to_account_info(),
-                position: ctx.accounts.position.to_account_info(),
-                token_a_account,
-                token_b_account,
-                token_a_vault,
-                token_b_vault,
-                token_a_mint,
-                token_b_mint,
-                position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
-                owner: ctx.accounts.owner.to_account_info(),
-                token_a_program,
-                token_b_program,
-                event_authority: ctx.accounts.event_authority.to_account_info(),
-                program: ctx.accounts.amm_program.to_account_info(),
-            },
-            vault_signer,
-        ))?;
-
-        // Emit QuoteFeesClaimed event
-        emit!(QuoteFeesClaimed {
-            vault: ctx.accounts.policy.vault,
-            policy: ctx.accounts.policy.key(),
-            progress: ctx.accounts.progress.key(),
-            pool: ctx.accounts.pool.key(),
-            position: ctx.accounts.position.key(),
-            owner: ctx.accounts.owner.key(),
-            base_fee_claimed: base_fee,
-            quote_fee_claimed: quote_fee
-        });
+        return Ok(Some(redirect));
     }
 
-    Ok(quote_fee)
+    let (expected_redirect, _) = Pubkey::find_program_address(
+        &[PAYOUT_REDIRECT_SEED, vault.as_ref(), stream.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        redirect_ai.key(),
+        expected_redirect,
+        TollgateError::InvalidPayoutRedirect
+    );
+    Ok(None)
 }
 
-/// Computes contracts and their locked amounts for a page of streams.
-fn compute_page_contracts_and_locked(
-    streams: &[AccountInfo],
-    timestamp: u64,
-) -> Result<(Vec<Contract>, Vec<u64>)> {
-    let mut contracts = Vec::with_capacity(streams.len());
-    let mut lockeds = Vec::with_capacity(streams.len());
-    for stream in streams {
-        let contract_data = stream.data.borrow();
-        let contract = try_from_slice_unchecked::<Contract>(&contract_data)?;
-        let net = contract.ix.net_amount_deposited;
-        let avail = contract.available_to_claim(timestamp, 0.0);
-        let locked = net.saturating_sub(avail);
-        contracts.push(contract);
-        lockeds.push(locked);
+/// Returns the account the investor of a stream is paid to: the destination
+/// of its payout redirect if the recipient set one, its quote ATA otherwise.
+pub(crate) fn get_payout_account(
+    redirect_ai: &AccountInfo,
+    vault: &Pubkey,
+    stream: &Pubkey,
+    recipient: &Pubkey,
+    quote_mint: &Pubkey,
+) -> Result<(Pubkey, bool)> {
+    let redirect = load_payout_redirect(redirect_ai, vault, stream)?;
+    Ok(
+        match redirect.and_then(|redirect| redirect.destination_of(recipient)) {
+            Some(destination) => (destination, true),
+            None => (get_associated_token_address(recipient, quote_mint), false),
+        },
+    )
+}
+
+/// Returns why an active investor of the payout pass is not paid, if it is not.
+pub(crate) fn payout_skip_reason(
+    locked: u64,
+    ata_ready: bool,
+    allocation: u64,
+    min_payout_lamports: u64,
+) -> Option<SkipReason> {
+    if locked == 0 {
+        Some(SkipReason::ZeroLocked)
+    } else if !ata_ready {
+        Some(SkipReason::MissingAta)
+    } else if allocation < min_payout_lamports {
+        Some(SkipReason::BelowDust)
+    } else {
+        None
     }
-    Ok((contracts, lockeds))
 }
 
-/// Processes a single page of investors, returning (page_payouts).
+/// Processes a single page of investors of the payout pass, returning (page_payouts, investors_paid).
 /// This is the shared logic for both crank modes.
 #[allow(clippy::too_many_arguments)]
 fn process_investor_page<'info>(
-    _streams: &[AccountInfo<'info>],
+    streams: &[AccountInfo<'info>],
     atas: &[AccountInfo<'info>],
+    stats: &[AccountInfo<'info>],
+    redirects: &[AccountInfo<'info>],
     authorities: &[Option<AccountInfo<'info>>], // None for standard crank
-    contracts: &[Contract],
+    contracts: &[PageStream],
     locked_per: &[u64],
     quote_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
     quote_program: &Interface<'info, token_interface::TokenInterface>,
     policy: &Account<'info, Policy>,
+    progress: &mut Progress,
     owner: &AccountInfo<'info>,
     vault_signer: &[&[&[u8]]],
+    events: &EventEmitter<'_, 'info>,
     payer: Option<&Signer<'info>>, // Signer for init mode
     system_program: Option<&Program<'info, System>>,
     associated_token_program: Option<&Program<'info, AssociatedToken>>,
     quote_mint: &AccountInfo<'info>,
     min_payout_lamports: u64,
-    investor_fee_quote: u64,
-    locked_total: u64,
     page_size: usize,
-) -> Result<u64> {
+) -> Result<(u64, u32)> {
     let mut page_payouts = 0u64;
+    let mut investors_paid = 0u32;
+    let day = progress.last_distribution_ts;
 
     for i in 0..page_size {
-        let contract = &contracts[i];
+        let contract = match &contracts[i] {
+            PageStream::Active(contract) => contract,
+            PageStream::Skipped(reason) => {
+                // Zero-weight: nothing is allocated, so the page carries on
+                msg!("Crank::Skipping stream {}: {:?}", streams[i].key, reason);
+                events.emit(InvestorSkipped {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    vault: policy.vault,
+                    policy: policy.key(),
+                    stream: streams[i].key(),
+                    index: progress.cursor + i as u32,
+                    reason: reason.clone(),
+                })?;
+
+                // A stream closed before its first crank may have no stats
+                if stats[i].owner == &crate::ID && !stats[i].data_is_empty() {
+                    let mut investor_stats =
+                        load_investor_stats(&stats[i], &policy.vault, streams[i].key)?;
+                    investor_stats.record_unpaid_day(day)?;
+                    investor_stats.try_serialize(&mut &mut stats[i].try_borrow_mut_data()?[..])?;
+                }
+                continue;
+            }
+        };
         let recipient = contract.recipient;
-        let expected_ata = get_associated_token_address(&recipient, quote_mint.key);
+        let (payout_account, redirected) = get_payout_account(
+            &redirects[i],
+            &policy.vault,
+            streams[i].key,
+            &recipient,
+            quote_mint.key,
+        )?;
         let ata_ai = &atas[i];
-        require_keys_eq!(
-            ata_ai.key(),
-            expected_ata,
+        let payout_account_error = if redirected {
+            TollgateError::InvalidPayoutDestination
+        } else {
             TollgateError::InvalidInvestorAta
-        );
+        };
+        require_keys_eq!(ata_ai.key(), payout_account, payout_account_error);
 
         if let Some(ref inv_ai) = authorities[i] {
             require_keys_eq!(
@@ -198,15 +380,39 @@ fn process_investor_page<'info>(
             );
         }
 
+        // Allocate before any skip, an unpaid allocation is carried over
+        let allocation = progress.allocate(locked_per[i])?;
+        let investor_share = allocation.amount;
+        if allocation.capped {
+            let share =
+                math::pro_rata_share(progress.investor_pool, locked_per[i], progress.locked_total)?;
+            msg!(
+                "Crank::Capping stream {}: share {} over cap {}",
+                streams[i].key,
+                share,
+                investor_share
+            );
+            events.emit(InvestorCapped {
+                schema_version: EVENT_SCHEMA_VERSION,
+                vault: policy.vault,
+                policy: policy.key(),
+                stream: streams[i].key(),
+                index: progress.cursor + i as u32,
+                locked: locked_per[i],
+                share,
+                cap: investor_share,
+            })?;
+        }
+
+        let mut investor_stats = load_investor_stats(&stats[i], &policy.vault, streams[i].key)?;
+
         // Check if ATA needs initialization
-        if ata_ai.data_len() != token::TokenAccount::LEN {
-            if !policy.init_investor_ata {
-                continue;
-            }
-            if authorities[i].is_none() {
-                // Standard crank: skip uninitialized ATAs
-                continue;
-            }
+        let ata_ready = if ata_ai.data_len() == token::TokenAccount::LEN {
+            true
+        } else if !policy.init_investor_ata || authorities[i].is_none() || redirected {
+            // Standard crank: skip uninitialized ATAs, a redirect destination is never created
+            false
+        } else {
             // Init mode: create ATA
             let authority_ai = &authorities[i].as_ref().unwrap();
             let cpi_accounts = associated_token::Create {
@@ -222,15 +428,26 @@ fn process_investor_page<'info>(
                 cpi_accounts,
             );
             associated_token::create_idempotent(cpi_ctx)?;
-        }
+            true
+        };
 
-        let locked = locked_per[i];
-        let investor_share = if locked_total > 0 {
-            (investor_fee_quote * locked) / locked_total
+        let skip_reason = payout_skip_reason(
+            locked_per[i],
+            ata_ready,
+            investor_share,
+            min_payout_lamports,
+        );
+        if let Some(reason) = &skip_reason {
+            msg!("Crank::Skipping stream {}: {:?}", streams[i].key, reason);
+            events.emit(InvestorSkipped {
+                schema_version: EVENT_SCHEMA_VERSION,
+                vault: policy.vault,
+                policy: policy.key(),
+                stream: streams[i].key(),
+                index: progress.cursor + i as u32,
+                reason: reason.clone(),
+            })?;
         } else {
-            0
-        };
-        if investor_share >= min_payout_lamports {
             let cpi_accounts = token_interface::Transfer {
                 from: quote_account.to_account_info(),
                 to: ata_ai.clone(),
@@ -243,14 +460,44 @@ fn process_investor_page<'info>(
             );
             anchor_spl::token_interface::transfer(cpi_ctx, investor_share)?;
             page_payouts = page_payouts.saturating_add(investor_share);
+            investors_paid += 1;
+            events.emit(InvestorPaid {
+                schema_version: EVENT_SCHEMA_VERSION,
+                vault: policy.vault,
+                policy: policy.key(),
+                stream: streams[i].key(),
+                index: progress.cursor + i as u32,
+                recipient,
+                ata: ata_ai.key(),
+                locked: locked_per[i],
+                share_bps: math::pro_rata_share(
+                    MAX_BPS as u64,
+                    locked_per[i],
+                    progress.locked_total,
+                )?,
+                amount: investor_share,
+            })?;
         }
+
+        match skip_reason {
+            None => investor_stats.record_paid(investor_share, day)?,
+            Some(SkipReason::MissingAta) => {
+                investor_stats.record_skipped(investor_share)?;
+                investor_stats.record_unpaid_day(day)?;
+            }
+            Some(_) => {
+                investor_stats.record_dust(investor_share)?;
+                investor_stats.record_unpaid_day(day)?;
+            }
+        }
+        investor_stats.try_serialize(&mut &mut stats[i].try_borrow_mut_data()?[..])?;
     }
 
-    Ok(page_payouts)
+    Ok((page_payouts, investors_paid))
 }
 
 fn shared_crank_logic<'info>(
-    ctx: Context<'_, '_, '_, 'info, AccountCrank<'info>>,
+    ctx: Context<'_, '_, '_, 'info, AccountCrankPage<'info>>,
     params: &CrankParams,
     init_mode: bool,
 ) -> Result<()> {
@@ -264,11 +511,10 @@ fn shared_crank_logic<'info>(
         (None, None, None)
     };
 
-    let clock = Clock::get()?;
-    let timestamp = clock.unix_timestamp;
+    let timestamp = Clock::get()?.unix_timestamp;
 
     let investor_accounts = ctx.remaining_accounts;
-    let stride = if init_mode { 3usize } else { 2usize };
+    let stride = if init_mode { 5usize } else { 4usize };
     require_eq!(
         0,
         investor_accounts.len() % stride,
@@ -293,52 +539,22 @@ fn shared_crank_logic<'info>(
         &[ctx.accounts.policy.owner_bump],
     ];
     let vault_signer = &[&vault_seeds[..]];
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    let events = EventEmitter::new(&event_authority, ctx.bumps.event_authority);
 
-    let prev_remainder = ctx
-        .accounts
-        .quote_account
-        .amount
-        .saturating_sub(ctx.accounts.progress.carry);
     let day = if ctx.accounts.progress.is_new_day(timestamp) {
-        // New day
-        if ctx.accounts.progress.last_distribution_ts == 0
-            || !matches!(ctx.accounts.progress.day_state, DayState::New)
-        {
-            if prev_remainder != 0 {
-                let cpi_accounts = token_interface::Transfer {
-                    from: ctx.accounts.quote_account.to_account_info(),
-                    to: ctx.accounts.creator_account.to_account_info(),
-                    authority: ctx.accounts.owner.to_account_info(),
-                };
-                let cpi_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.quote_program.to_account_info(),
-                    cpi_accounts,
-                    vault_signer,
-                );
-                anchor_spl::token_interface::transfer(cpi_ctx, prev_remainder)?;
-                msg!(
-                    "Crank::Transferred previous day remainder to creator: {}",
-                    prev_remainder
-                );
-            }
-            ctx.accounts.progress.start_new_day(timestamp)?;
-        }
-        DayState::New
-    } else if matches!(ctx.accounts.progress.day_state, DayState::Closed)
-        && ctx.accounts.progress.last_distribution_ts != 0
-    {
-        // Closed day and not the first time crank is called
+        // New day, whose fees must first be claimed
+        return Err(TollgateError::DayNotOpened.into());
+    } else if matches!(ctx.accounts.progress.day_state, DayState::Closed) {
+        // Closed day
         msg!("Crank::Day is closed, skipping");
         return Ok(());
-    } else if ctx.accounts.progress.is_same_day(timestamp) {
-        // Same day
+    } else {
+        // Same day, opened by claim_fees
         if !matches!(ctx.accounts.progress.day_state, DayState::Same) {
             ctx.accounts.progress.continue_same_day()?;
         }
         DayState::Same
-    } else {
-        // Should not happen
-        return Err(TollgateError::InvalidDayState.into());
     };
 
     msg!("Crank::Processing day state: {:?}", day);
@@ -353,87 +569,19 @@ fn shared_crank_logic<'info>(
         return Err(TollgateError::PaginationCursorTooLarge.into());
     }
 
-    // Load the pool and pool config accounts
-    let (fee_a_pending, fee_b_pending, _, quote_token_order) = {
-        // Load pool and position accounts
-        let pool = &ctx.accounts.pool.load()?;
-        let position = &ctx.accounts.position.load()?;
-
-        // Determine base/quote mints
-        let (base_mint, quote_mint) = if ctx.accounts.policy.quote_mint == pool.token_a_mint {
-            (&pool.token_b_mint, &pool.token_a_mint)
-        } else {
-            (&pool.token_a_mint, &pool.token_b_mint)
-        };
-
-        // Determine base/quote token order
-        let base_token_order = utils::token::get_token_order(pool, base_mint);
-        let quote_token_order = utils::token::get_token_order(pool, quote_mint);
-
-        // Check base/quote token order is valid
-        require!(base_token_order.is_some(), TollgateError::BaseMintNotInPool);
-        require!(
-            quote_token_order.is_some(),
-            TollgateError::QuoteMintNotInPool
-        );
-
-        (
-            position.fee_a_pending,
-            position.fee_b_pending,
-            base_token_order,
-            quote_token_order,
-        )
-    };
-
-    let mut distributable = if matches!(day, DayState::New) {
-        let quote_fee = claim_position_fees(
-            &ctx,
-            quote_token_order.unwrap(),
-            fee_a_pending,
-            fee_b_pending,
-            vault_signer,
-        )?;
-
-        quote_fee.saturating_add(
-            ctx.accounts
-                .quote_account
-                .amount
-                .saturating_sub(prev_remainder),
-        )
-    } else {
-        ctx.accounts.quote_account.amount
-    };
-
+    let distributable = ctx.accounts.progress.distributable;
     msg!("Crank::Distributable amount after carry: {}", distributable);
 
-    // Optional daily cap
-    if let Some(cap) = ctx.accounts.policy.daily_cap {
-        let remaining_cap = cap.saturating_sub(ctx.accounts.progress.daily_spent);
-        distributable = distributable.min(remaining_cap);
-        msg!(
-            "Crank::Applied daily cap, remaining_cap={}, distributable={}",
-            remaining_cap,
-            distributable
-        );
-    }
-
-    if matches!(day, DayState::New) && distributable < ctx.accounts.policy.min_payout_lamports {
-        ctx.accounts.progress.carry = distributable;
-        msg!(
-            "Crank::Distributable below min payout, carrying over: {}",
-            distributable
-        );
-        return Ok(());
-    }
-
     if page_size == 0 {
         msg!("Crank::No investors to process, exiting");
         return Ok(());
     }
 
-    // Prepare streams, atas, authorities
+    // Prepare streams, atas, stats, redirects, authorities
     let mut streams = Vec::with_capacity(page_size);
     let mut atas = Vec::with_capacity(page_size);
+    let mut stats = Vec::with_capacity(page_size);
+    let mut redirects = Vec::with_capacity(page_size);
     let mut authorities = Vec::with_capacity(page_size);
     for idx in 0..page_size {
         let offset = idx * stride;
@@ -441,58 +589,150 @@ fn shared_crank_logic<'info>(
             let inv_ai = investor_accounts[offset].clone();
             let stream_ai = investor_accounts[offset + 1].clone();
             let ata_ai = investor_accounts[offset + 2].clone();
+            let stats_ai = investor_accounts[offset + 3].clone();
+            let redirect_ai = investor_accounts[offset + 4].clone();
             streams.push(stream_ai);
             atas.push(ata_ai);
+            stats.push(stats_ai);
+            redirects.push(redirect_ai);
             authorities.push(Some(inv_ai));
         } else {
             let stream_ai = investor_accounts[offset].clone();
             let ata_ai = investor_accounts[offset + 1].clone();
+            let stats_ai = investor_accounts[offset + 2].clone();
+            let redirect_ai = investor_accounts[offset + 3].clone();
             streams.push(stream_ai);
             atas.push(ata_ai);
+            stats.push(stats_ai);
+            redirects.push(redirect_ai);
             authorities.push(None);
         }
     }
 
-    let (contracts, locked_per) = compute_page_contracts_and_locked(&streams, timestamp as u64)?;
-    let locked_total: u64 = locked_per.iter().cloned().sum();
-    let f_locked = (locked_total * MAX_BPS as u64) / ctx.accounts.policy.y0;
-    let eligible_investor_share_bps =
-        (ctx.accounts.policy.investor_fee_share_bps as u64).min(f_locked);
-    let investor_fee_quote = distributable * eligible_investor_share_bps / MAX_BPS as u64;
+    // Each pass cranks every investor once, in increasing stream order
+    let stream_keys: Vec<Pubkey> = streams.iter().map(|stream| stream.key()).collect();
+    ctx.accounts.progress.record_page_streams(&stream_keys)?;
+
+    // Locked amounts are averaged over the day, so that every pass sees the
+    // same amounts whenever its pages land
+    let epoch_start = ctx.accounts.progress.last_distribution_ts as u64;
+    let (contracts, locked_per) = compute_page_contracts_and_locked(
+        &streams,
+        &stats,
+        &redirects,
+        &ctx.accounts.policy.vault,
+        epoch_start,
+    )?;
+    let page_locked = math::checked_sum(&locked_per)?;
+    bind_page_streams(
+        &streams,
+        &stats,
+        &ctx.accounts.policy.vault,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    let page_start = params.cursor as usize;
+    let page_end = (page_start + page_size).min(ctx.accounts.policy.investor_count as usize);
+
+    if ctx.accounts.progress.phase == CrankPhase::Snapshot {
+        let progress = &mut ctx.accounts.progress;
+        progress.locked_total = progress
+            .locked_total
+            .checked_add(page_locked)
+            .ok_or(TollgateError::MathOverflow)?;
+        progress.cursor += page_size as u32;
+
+        msg!(
+            "Crank::Snapshot page {} to {}, locked: {}",
+            page_start,
+            page_end,
+            page_locked
+        );
+
+        if progress.cursor >= ctx.accounts.policy.investor_count {
+            let (_, eligible_investor_share_bps, investor_pool) = compute_investor_fee_quote(
+                &ctx.accounts.policy,
+                distributable,
+                progress.locked_total,
+            )?;
+            progress.complete_snapshot(investor_pool, ctx.accounts.policy.investor_cap.as_ref())?;
+            msg!(
+                "Crank::Snapshot completed, locked total: {}, eligible bps: {}, investor pool: {}, investor cap: {:?}",
+                progress.locked_total,
+                eligible_investor_share_bps,
+                investor_pool,
+                progress.investor_cap
+            );
+        }
+
+        msg!("Crank::Completed successfully");
+        return Ok(());
+    }
+
+    if ctx.accounts.progress.phase == CrankPhase::Cap {
+        let progress = &mut ctx.accounts.progress;
+        progress.record_cap_page(&locked_per)?;
+        progress.cursor += page_size as u32;
+
+        msg!(
+            "Crank::Cap pass {} page {} to {}, capped: {}",
+            progress.cap_passes,
+            page_start,
+            page_end,
+            progress.pass_capped_count
+        );
+
+        if progress.cursor >= ctx.accounts.policy.investor_count {
+            let settled = progress.complete_cap_pass()?;
+            msg!(
+                "Crank::Cap pass completed, capped: {}, capped locked: {}, settled: {}",
+                progress.capped_count,
+                progress.capped_locked,
+                settled
+            );
+        }
+
+        msg!("Crank::Completed successfully");
+        return Ok(());
+    }
 
     msg!(
-        "Crank::Locked total: {}, eligible bps: {}, investor fee: {}",
-        locked_total,
-        eligible_investor_share_bps,
-        investor_fee_quote
+        "Crank::Locked total: {}, page locked: {}, investor pool: {}",
+        ctx.accounts.progress.locked_total,
+        page_locked,
+        ctx.accounts.progress.investor_pool
     );
 
-    let page_payouts = process_investor_page(
+    let (page_payouts, investors_paid) = process_investor_page(
         &streams,
         &atas,
+        &stats,
+        &redirects,
         &authorities,
         &contracts,
         &locked_per,
         &ctx.accounts.quote_account,
         &ctx.accounts.quote_program,
         &ctx.accounts.policy,
+        &mut ctx.accounts.progress,
         &ctx.accounts.owner.to_account_info(),
         vault_signer,
+        &events,
         payer,
         system_program,
         associated_token_program,
         &ctx.accounts.quote_mint.to_account_info(),
         ctx.accounts.policy.min_payout_lamports,
-        investor_fee_quote,
-        locked_total,
         page_size,
     )?;
 
     ctx.accounts.progress.daily_spent += page_payouts;
     ctx.accounts.progress.cursor += page_size as u32;
-
-    let page_start = params.cursor as usize;
-    let page_end = (page_start + page_size).min(ctx.accounts.policy.investor_count as usize);
+    ctx.accounts
+        .day_history
+        .load_mut()?
+        .record_investors_paid(investors_paid)?;
 
     msg!(
         "Crank::Processed page {} to {}, payouts: {}",
@@ -501,23 +741,67 @@ fn shared_crank_logic<'info>(
         page_payouts
     );
 
-    emit!(InvestorPayoutPage {
+    events.emit(InvestorPayoutPage {
+        schema_version: EVENT_SCHEMA_VERSION,
         vault: ctx.accounts.policy.vault,
         policy: ctx.accounts.policy.key(),
         progress: ctx.accounts.progress.key(),
-        pool: ctx.accounts.pool.key(),
-        position: ctx.accounts.position.key(),
+        pool: ctx.accounts.position_registry.positions()[0].pool,
+        position: ctx.accounts.position_registry.positions()[0].position,
         owner: ctx.accounts.owner.key(),
         cursor: params.cursor,
         investors: page_size as u32,
         page_start: page_start as u32,
         page_end: page_end as u32,
-        payout: page_payouts
-    });
+        payout: page_payouts,
+    })?;
 
     if ctx.accounts.progress.cursor >= ctx.accounts.policy.investor_count {
-        let creator_share = distributable.saturating_sub(investor_fee_quote);
-        if creator_share >= ctx.accounts.policy.min_payout_lamports {
+        // Streams that stopped vesting since the snapshot leave their share in the carry
+        let released_locked = ctx
+            .accounts
+            .progress
+            .locked_total
+            .checked_sub(ctx.accounts.progress.allocated_locked)
+            .ok_or(TollgateError::SnapshotMismatch)?;
+        if released_locked > 0 {
+            msg!(
+                "Crank::Locked amount no longer vesting, carrying its share: {}",
+                released_locked
+            );
+        }
+
+        let investor_payouts = ctx.accounts.progress.daily_spent;
+        // The excess over the cap and the share of the released locked amount
+        let unallocated = ctx
+            .accounts
+            .progress
+            .investor_pool
+            .checked_sub(ctx.accounts.progress.allocated)
+            .ok_or(TollgateError::DistributionInvariantViolated)?;
+        if unallocated > 0 {
+            msg!(
+                "Crank::Unallocated investor pool, carrying over: {}",
+                unallocated
+            );
+        }
+        let unpaid = ctx
+            .accounts
+            .progress
+            .allocated
+            .checked_sub(investor_payouts)
+            .ok_or(TollgateError::DistributionInvariantViolated)?;
+        if unpaid > 0 {
+            msg!(
+                "Crank::Unpaid investor allocations, carrying over: {}",
+                unpaid
+            );
+        }
+
+        let creator_share = distributable
+            .checked_sub(ctx.accounts.progress.investor_pool)
+            .ok_or(TollgateError::DistributionInvariantViolated)?;
+        let creator_payout = if creator_share >= ctx.accounts.policy.min_payout_lamports {
             let cpi_accounts = token_interface::Transfer {
                 from: ctx.accounts.quote_account.to_account_info(),
                 to: ctx.accounts.creator_account.to_account_info(),
@@ -530,56 +814,109 @@ fn shared_crank_logic<'info>(
             );
             anchor_spl::token_interface::transfer(cpi_ctx, creator_share)?;
             msg!("Crank::Transferred creator share: {}", creator_share);
+            creator_share
         } else {
-            ctx.accounts.progress.carry += creator_share;
             msg!(
                 "Crank::Creator share below min, carrying over: {}",
                 creator_share
             );
-        }
-
-        let total_distributed = distributable.saturating_add(
-            ctx.accounts
-                .progress
-                .daily_spent
-                .saturating_sub(page_payouts),
-        );
+            0
+        };
 
-        emit!(CreatorPayoutDayClosed {
-            vault: ctx.accounts.policy.vault,
-            policy: ctx.accounts.policy.key(),
-            progress: ctx.accounts.progress.key(),
-            pool: ctx.accounts.pool.key(),
-            position: ctx.accounts.position.key(),
-            owner: ctx.accounts.owner.key(),
+        let day_carry = unpaid + unallocated + (creator_share - creator_payout);
+        ctx.accounts.progress.carry = ctx
+            .accounts
+            .progress
+            .carry
+            .checked_add(day_carry)
+            .ok_or(TollgateError::MathOverflow)?;
+
+        close_day(
+            &ctx.accounts.policy,
+            &mut ctx.accounts.progress,
+            &ctx.accounts.day_history,
+            &ctx.accounts.position_registry.positions()[0],
+            ctx.accounts.owner.key(),
             timestamp,
-            total_distributed,
-            creator_payout: creator_share,
-            carry: ctx.accounts.progress.carry
-        });
+            investor_payouts,
+            creator_payout,
+            &events,
+        )?;
 
         msg!(
             "Crank::Day closed, total distributed: {}, carry: {}",
-            total_distributed,
+            investor_payouts + creator_payout,
             ctx.accounts.progress.carry
         );
-
-        ctx.accounts.progress.close_day()?;
     }
 
     msg!("Crank::Completed successfully");
     Ok(())
 }
 
+/// Closes the day once the invariant
+/// `investor_payouts + creator_payout + carry == distributable` holds,
+/// appending its record to the day history.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn close_day(
+    policy: &Account<Policy>,
+    progress: &mut Account<Progress>,
+    day_history: &AccountLoader<DayHistory>,
+    primary: &HonoraryPosition,
+    owner: Pubkey,
+    timestamp: i64,
+    investor_payouts: u64,
+    creator_payout: u64,
+    events: &EventEmitter,
+) -> Result<()> {
+    let total_distributed = investor_payouts
+        .checked_add(creator_payout)
+        .ok_or(TollgateError::MathOverflow)?;
+    require_eq!(
+        total_distributed
+            .checked_add(progress.carry)
+            .ok_or(TollgateError::MathOverflow)?,
+        progress.distributable,
+        TollgateError::DistributionInvariantViolated
+    );
+
+    let record = day_history
+        .load_mut()?
+        .close_day(progress, investor_payouts, creator_payout)?;
+    msg!(
+        "Crank::Recorded day {}, investors paid: {}",
+        record.index,
+        record.investors_paid
+    );
+
+    events.emit(CreatorPayoutDayClosed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        vault: policy.vault,
+        policy: policy.key(),
+        progress: progress.key(),
+        pool: primary.pool,
+        position: primary.position,
+        owner,
+        timestamp,
+        total_distributed,
+        creator_payout,
+        carry: progress.carry,
+        distributable: progress.distributable,
+        investor_payouts,
+    })?;
+
+    progress.close_day()
+}
+
 pub fn crank<'info>(
-    ctx: Context<'_, '_, '_, 'info, AccountCrank<'info>>,
+    ctx: Context<'_, '_, '_, 'info, AccountCrankPage<'info>>,
     params: CrankParams,
 ) -> Result<()> {
     shared_crank_logic(ctx, &params, false)
 }
 
 pub fn crank_with_init<'info>(
-    ctx: Context<'_, '_, '_, 'info, AccountCrank<'info>>,
+    ctx: Context<'_, '_, '_, 'info, AccountCrankPage<'info>>,
     params: CrankParams,
 ) -> Result<()> {
     shared_crank_logic(ctx, &params, true)
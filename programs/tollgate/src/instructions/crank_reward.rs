@@ -0,0 +1,291 @@
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::get_associated_token_address_with_program_id, token_interface};
+
+use super::crank::{
+    bind_page_streams, compute_page_contracts_and_locked, load_investor_stats,
+    load_payout_redirect, payout_skip_reason, CrankParams, PageStream,
+};
+use crate::{
+    constants::{EVENT_SCHEMA_VERSION, INVESTOR_FEE_POS_OWNER, VAULT_SEED},
+    error::TollgateError,
+    events::{RewardInvestorPaid, RewardInvestorSkipped, RewardPayoutPage, RewardRoundClosed},
+    math,
+    utils::event::EventEmitter,
+    AccountCrankReward,
+};
+
+pub fn crank_reward<'info>(
+    ctx: Context<'_, '_, '_, 'info, AccountCrankReward<'info>>,
+    params: CrankParams,
+) -> Result<()> {
+    let investor_accounts = ctx.remaining_accounts;
+    require_eq!(
+        0,
+        investor_accounts.len() % 4,
+        TollgateError::InvalidInvestorAccounts
+    );
+
+    let page_size = investor_accounts.len() / 4;
+
+    msg!(
+        "CrankReward::Starting reward {} crank with cursor={} and page_size={}",
+        ctx.accounts.reward_progress.reward_index,
+        params.cursor,
+        page_size
+    );
+
+    // Validate params
+    params.assert(ctx.accounts.policy.investor_count, page_size as u32)?;
+
+    if ctx.accounts.reward_progress.closed {
+        msg!("CrankReward::Round is closed, skipping");
+        return Ok(());
+    }
+
+    // Validate reward cursor
+    if params.cursor < ctx.accounts.reward_progress.cursor {
+        // Idempotent: nothing to do
+        msg!("CrankReward::Cursor behind progress, skipping");
+        return Ok(());
+    } else if params.cursor > ctx.accounts.reward_progress.cursor {
+        // Cannot skip ahead
+        return Err(TollgateError::PaginationCursorTooLarge.into());
+    }
+
+    if page_size == 0 {
+        msg!("CrankReward::No investors to process, exiting");
+        return Ok(());
+    }
+
+    let vault_seeds = &[
+        VAULT_SEED,
+        ctx.accounts.policy.vault.as_ref(),
+        INVESTOR_FEE_POS_OWNER,
+        &[ctx.accounts.policy.owner_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    let events = EventEmitter::new(&event_authority, ctx.bumps.event_authority);
+
+    // Each round pays every investor once, in increasing stream order
+    let streams: Vec<_> = investor_accounts.iter().step_by(4).cloned().collect();
+    let stream_keys: Vec<Pubkey> = streams.iter().map(|stream| stream.key()).collect();
+    ctx.accounts
+        .reward_progress
+        .record_page_streams(&stream_keys)?;
+
+    // Locked amounts are taken at the snapshot of the quote distribution
+    let reward_accounts: Vec<_> = investor_accounts.iter().skip(1).step_by(4).collect();
+    let stats: Vec<_> = investor_accounts
+        .iter()
+        .skip(2)
+        .step_by(4)
+        .cloned()
+        .collect();
+    let redirects: Vec<_> = investor_accounts
+        .iter()
+        .skip(3)
+        .step_by(4)
+        .cloned()
+        .collect();
+    let snapshot_ts = ctx.accounts.reward_progress.day_ts as u64;
+    let (contracts, locked_per) = compute_page_contracts_and_locked(
+        &streams,
+        &stats,
+        &redirects,
+        &ctx.accounts.policy.vault,
+        snapshot_ts,
+    )?;
+    let page_locked = math::checked_sum(&locked_per)?;
+    bind_page_streams(
+        &streams,
+        &stats,
+        &ctx.accounts.policy.vault,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    let reward_index = ctx.accounts.reward_progress.reward_index;
+    let reward_mint = ctx.accounts.reward_mint.key();
+    let reward_program = ctx.accounts.reward_program.key();
+    let mut page_payouts = 0u64;
+    for i in 0..page_size {
+        let index = params.cursor + i as u32;
+        let contract = match &contracts[i] {
+            PageStream::Active(contract) => contract,
+            PageStream::Skipped(reason) => {
+                // Zero-weight: nothing is allocated
+                events.emit(RewardInvestorSkipped {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    vault: ctx.accounts.policy.vault,
+                    policy: ctx.accounts.policy.key(),
+                    reward_index,
+                    reward_mint,
+                    stream: streams[i].key(),
+                    index,
+                    reason: reason.clone(),
+                })?;
+                continue;
+            }
+        };
+
+        // Rewards follow the payout redirect to the reward ATA of the owner of its destination
+        let recipient = contract.recipient;
+        let reward_owner =
+            load_payout_redirect(&redirects[i], &ctx.accounts.policy.vault, streams[i].key)?
+                .and_then(|redirect| redirect.reward_owner_of(&recipient));
+        let reward_account = reward_accounts[i];
+        require_keys_eq!(
+            reward_account.key(),
+            get_associated_token_address_with_program_id(
+                &reward_owner.unwrap_or(recipient),
+                &reward_mint,
+                &reward_program
+            ),
+            if reward_owner.is_some() {
+                TollgateError::InvalidPayoutDestination
+            } else {
+                TollgateError::InvalidInvestorAta
+            }
+        );
+
+        // A missing reward ATA leaves the allocation in the carry
+        let investor_share = ctx.accounts.reward_progress.allocate(locked_per[i])?;
+        let ata_ready = reward_account.owner == &reward_program && !reward_account.data_is_empty();
+        if let Some(reason) = payout_skip_reason(locked_per[i], ata_ready, investor_share, 1) {
+            events.emit(RewardInvestorSkipped {
+                schema_version: EVENT_SCHEMA_VERSION,
+                vault: ctx.accounts.policy.vault,
+                policy: ctx.accounts.policy.key(),
+                reward_index,
+                reward_mint,
+                stream: streams[i].key(),
+                index,
+                reason,
+            })?;
+            continue;
+        }
+
+        let cpi_accounts = token_interface::Transfer {
+            from: ctx.accounts.reward_account.to_account_info(),
+            to: reward_account.clone(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.reward_program.to_account_info(),
+            cpi_accounts,
+            vault_signer,
+        );
+        anchor_spl::token_interface::transfer(cpi_ctx, investor_share)?;
+        page_payouts = page_payouts
+            .checked_add(investor_share)
+            .ok_or(TollgateError::MathOverflow)?;
+
+        let mut investor_stats =
+            load_investor_stats(&stats[i], &ctx.accounts.policy.vault, streams[i].key)?;
+        investor_stats.record_reward_paid(reward_index as usize, investor_share)?;
+        investor_stats.try_serialize(&mut &mut stats[i].try_borrow_mut_data()?[..])?;
+
+        events.emit(RewardInvestorPaid {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vault: ctx.accounts.policy.vault,
+            policy: ctx.accounts.policy.key(),
+            reward_index,
+            reward_mint,
+            stream: streams[i].key(),
+            index,
+            recipient,
+            reward_account: reward_account.key(),
+            locked: locked_per[i],
+            amount: investor_share,
+        })?;
+    }
+
+    let reward_progress = &mut ctx.accounts.reward_progress;
+    reward_progress.investor_payouts = reward_progress
+        .investor_payouts
+        .checked_add(page_payouts)
+        .ok_or(TollgateError::MathOverflow)?;
+    reward_progress.cursor += page_size as u32;
+
+    let page_start = params.cursor as usize;
+    let page_end = (page_start + page_size).min(ctx.accounts.policy.investor_count as usize);
+    msg!(
+        "CrankReward::Processed page {} to {}, locked: {}, payouts: {}",
+        page_start,
+        page_end,
+        page_locked,
+        page_payouts
+    );
+
+    events.emit(RewardPayoutPage {
+        schema_version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.policy.vault,
+        policy: ctx.accounts.policy.key(),
+        reward_index,
+        reward_mint,
+        cursor: params.cursor,
+        investors: page_size as u32,
+        page_start: page_start as u32,
+        page_end: page_end as u32,
+        payout: page_payouts,
+    })?;
+
+    if ctx.accounts.reward_progress.cursor >= ctx.accounts.policy.investor_count {
+        // Streams that stopped vesting since the snapshot leave their share in the carry
+        let released_locked = ctx
+            .accounts
+            .reward_progress
+            .locked_total
+            .checked_sub(ctx.accounts.reward_progress.allocated_locked)
+            .ok_or(TollgateError::SnapshotMismatch)?;
+        if released_locked > 0 {
+            msg!(
+                "CrankReward::Locked amount no longer vesting, carrying its share: {}",
+                released_locked
+            );
+        }
+
+        let creator_share = ctx
+            .accounts
+            .reward_progress
+            .reward_pool
+            .checked_sub(ctx.accounts.reward_progress.investor_pool)
+            .ok_or(TollgateError::DistributionInvariantViolated)?;
+        if creator_share > 0 {
+            let cpi_accounts = token_interface::Transfer {
+                from: ctx.accounts.reward_account.to_account_info(),
+                to: ctx.accounts.creator_reward_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.reward_program.to_account_info(),
+                cpi_accounts,
+                vault_signer,
+            );
+            anchor_spl::token_interface::transfer(cpi_ctx, creator_share)?;
+            msg!("CrankReward::Transferred creator share: {}", creator_share);
+        }
+
+        let carry = ctx.accounts.reward_progress.close_round(creator_share)?;
+
+        events.emit(RewardRoundClosed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vault: ctx.accounts.policy.vault,
+            policy: ctx.accounts.policy.key(),
+            reward_index,
+            reward_mint,
+            reward_pool: ctx.accounts.reward_progress.reward_pool,
+            investor_payouts: ctx.accounts.reward_progress.investor_payouts,
+            creator_payout: creator_share,
+            carry,
+        })?;
+
+        msg!("CrankReward::Round closed, carry: {}", carry);
+    }
+
+    msg!("CrankReward::Completed successfully");
+    Ok(())
+}
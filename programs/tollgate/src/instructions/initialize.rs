@@ -3,7 +3,11 @@ use std::cell::Ref;
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::MAX_BPS, error::TollgateError, events::HonoraryPositionInitialized, utils,
+    constants::{EVENT_SCHEMA_VERSION, MAX_BPS},
+    error::TollgateError,
+    events::HonoraryPositionInitialized,
+    state::{CarryPolicy, HonoraryPosition, InvestorCap},
+    utils::{self, event::EventEmitter},
     AccountInitialize,
 };
 
@@ -15,6 +19,9 @@ pub struct InitializeParams {
     pub min_payout_lamports: u64,
     pub daily_cap: Option<u64>,
     pub y0: u64,
+    pub creator: Option<Pubkey>,
+    pub carry_policy: CarryPolicy,
+    pub investor_cap: Option<InvestorCap>,
 }
 
 impl InitializeParams {
@@ -44,13 +51,25 @@ impl InitializeParams {
         // assert y0 is greater than 0
         require_gt!(self.y0, 0, TollgateError::InvalidY0Allocation);
 
+        // assert creator is either None or not the default pubkey
+        if let Some(creator) = self.creator {
+            require_keys_neq!(creator, Pubkey::default(), TollgateError::InvalidCreator);
+        }
+
+        self.carry_policy.assert()?;
+
+        // assert investor cap is either None or a positive limit
+        if let Some(investor_cap) = &self.investor_cap {
+            investor_cap.assert()?;
+        }
+
         Ok(())
     }
 }
 
 pub fn initialize(ctx: Context<AccountInitialize>, params: InitializeParams) -> Result<()> {
-    msg!("Initialize::Starting initialization with params: init_investor_ata={}, investor_fee_share_bps={}, min_payout_lamports={}, daily_cap={:?}, y0={}", 
-         params.init_investor_ata, params.investor_fee_share_bps, params.min_payout_lamports, params.daily_cap, params.y0);
+    msg!("Initialize::Starting initialization with params: init_investor_ata={}, investor_fee_share_bps={}, min_payout_lamports={}, daily_cap={:?}, y0={}, creator={:?}, carry_policy={:?}, investor_cap={:?}", 
+         params.init_investor_ata, params.investor_fee_share_bps, params.min_payout_lamports, params.daily_cap, params.y0, params.creator, params.carry_policy, params.investor_cap);
 
     // Validate the initialize parameters
     params.assert()?;
@@ -78,9 +97,10 @@ pub fn initialize(ctx: Context<AccountInitialize>, params: InitializeParams) ->
     // Initialize the policy account
     msg!("Initialize::Initializing policy account");
     let policy = &mut ctx.accounts.policy;
+    let creator = params.creator.unwrap_or(ctx.accounts.pool.load()?.creator);
     policy.initialize(
         ctx.accounts.vault.key(),
-        ctx.accounts.pool.load()?.creator,
+        creator,
         ctx.accounts.quote_mint.key(),
         params.clone(),
         ctx.bumps.owner,
@@ -92,6 +112,25 @@ pub fn initialize(ctx: Context<AccountInitialize>, params: InitializeParams) ->
     let progress = &mut ctx.accounts.progress;
     progress.initialize(ctx.accounts.vault.key(), ctx.bumps.progress)?;
 
+    // Initialize the day history account
+    msg!("Initialize::Initializing day history account");
+    ctx.accounts
+        .day_history
+        .load_init()?
+        .initialize(ctx.accounts.vault.key(), ctx.bumps.day_history)?;
+
+    // Initialize the position registry with the honorary position
+    msg!("Initialize::Initializing position registry account");
+    ctx.accounts.position_registry.initialize(
+        ctx.accounts.vault.key(),
+        base_mint,
+        HonoraryPosition {
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+        },
+        ctx.bumps.position_registry,
+    );
+
     // Create a DAMM v2 position
     msg!("Initialize::Creating DAMM v2 position");
     damm_v2::cpi::create_position(CpiContext::new(
@@ -106,33 +145,38 @@ pub fn initialize(ctx: Context<AccountInitialize>, params: InitializeParams) ->
             payer: ctx.accounts.payer.to_account_info(),
             token_program: ctx.accounts.token_2022_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
-            event_authority: ctx.accounts.event_authority.to_account_info(),
+            event_authority: ctx.accounts.amm_event_authority.to_account_info(),
             program: ctx.accounts.amm_program.to_account_info(),
         },
     ))?;
 
     // Emit a HonoraryPositionInitialized event
-    emit!(HonoraryPositionInitialized {
-        vault: ctx.accounts.vault.key(),
-        policy: ctx.accounts.policy.key(),
-        progress: ctx.accounts.progress.key(),
-        pool: ctx.accounts.pool.key(),
-        pool_cfg: ctx.accounts.pool_cfg.key(),
-        position: ctx.accounts.position.key(),
-        owner: ctx.accounts.owner.key(),
-        base_mint,
-        quote_mint,
-        investor_fee_share_bps: params.investor_fee_share_bps,
-        min_payout_lamports: params.min_payout_lamports,
-        daily_cap: params.daily_cap,
-        y0: params.y0,
-    });
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    EventEmitter::new(&event_authority, ctx.bumps.event_authority).emit(
+        HonoraryPositionInitialized {
+            schema_version: EVENT_SCHEMA_VERSION,
+            vault: ctx.accounts.vault.key(),
+            policy: ctx.accounts.policy.key(),
+            progress: ctx.accounts.progress.key(),
+            pool: ctx.accounts.pool.key(),
+            pool_cfg: ctx.accounts.pool_cfg.key(),
+            position: ctx.accounts.position.key(),
+            owner: ctx.accounts.owner.key(),
+            base_mint,
+            quote_mint,
+            investor_fee_share_bps: params.investor_fee_share_bps,
+            min_payout_lamports: params.min_payout_lamports,
+            daily_cap: params.daily_cap,
+            y0: params.y0,
+        },
+    )?;
 
     msg!("Initialize::Initialization completed successfully");
     Ok(())
 }
 
-fn assert_pool(
+/// Checks that the pool and its config trade the vault pair with quote-only fees.
+pub(crate) fn assert_pool(
     pool: &Ref<'_, damm_v2::accounts::Pool>,
     pool_cfg: &Ref<'_, damm_v2::accounts::Config>,
     base_mint: &Pubkey,
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+use crate::AccountInitializeDayHistory;
+
+pub fn initialize_day_history(ctx: Context<AccountInitializeDayHistory>) -> Result<()> {
+    msg!("InitializeDayHistory::Initializing day history account");
+
+    let mut day_history = ctx.accounts.day_history.load_init()?;
+    day_history.initialize(ctx.accounts.vault.key(), ctx.bumps.day_history)?;
+
+    msg!("InitializeDayHistory::Initialization completed successfully");
+    Ok(())
+}
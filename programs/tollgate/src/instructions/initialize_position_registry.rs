@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::TollgateError, state::HonoraryPosition, utils, AccountInitializePositionRegistry,
+};
+
+pub fn initialize_position_registry(ctx: Context<AccountInitializePositionRegistry>) -> Result<()> {
+    msg!("InitializePositionRegistry::Initializing position registry account");
+
+    let base_mint = {
+        let pool = ctx.accounts.pool.load()?;
+        let quote_mint = ctx.accounts.policy.quote_mint;
+        let Some(quote_token_order) = utils::token::get_token_order(&pool, &quote_mint) else {
+            return Err(TollgateError::QuoteMintNotInPool.into());
+        };
+        match quote_token_order {
+            utils::token::TokenOrder::A => pool.token_b_mint,
+            utils::token::TokenOrder::B => pool.token_a_mint,
+        }
+    };
+
+    ctx.accounts.position_registry.initialize(
+        ctx.accounts.vault.key(),
+        base_mint,
+        HonoraryPosition {
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+        },
+        ctx.bumps.position_registry,
+    );
+
+    msg!("InitializePositionRegistry::Initialization completed successfully");
+    Ok(())
+}
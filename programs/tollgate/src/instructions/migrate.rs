@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::EVENT_SCHEMA_VERSION,
+    events::AccountsMigrated,
+    state::{Policy, Progress},
+    utils::event::EventEmitter,
+    AccountMigrate,
+};
+
+pub fn migrate(ctx: Context<AccountMigrate>) -> Result<()> {
+    msg!("Migrate::Starting migration");
+
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    // Migrate the policy account
+    let policy_from_version = Policy::migrate(&ctx.accounts.policy, &payer, &system_program)?;
+    msg!(
+        "Migrate::Policy migrated from version {} to {}",
+        policy_from_version,
+        Policy::VERSION
+    );
+
+    // Migrate the progress account
+    let progress_from_version = Progress::migrate(&ctx.accounts.progress, &payer, &system_program)?;
+    msg!(
+        "Migrate::Progress migrated from version {} to {}",
+        progress_from_version,
+        Progress::VERSION
+    );
+
+    // Emit an AccountsMigrated event
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    EventEmitter::new(&event_authority, ctx.bumps.event_authority).emit(AccountsMigrated {
+        schema_version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        policy: ctx.accounts.policy.key(),
+        progress: ctx.accounts.progress.key(),
+        policy_from_version,
+        policy_version: Policy::VERSION,
+        progress_from_version,
+        progress_version: Progress::VERSION,
+    })?;
+
+    msg!("Migrate::Migration completed successfully");
+    Ok(())
+}
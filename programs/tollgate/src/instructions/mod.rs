@@ -1,5 +1,27 @@
+mod add_locked_liquidity;
+mod add_position;
+mod claim_fees;
+mod claim_reward;
 mod crank;
+mod crank_reward;
 mod initialize;
+mod initialize_day_history;
+mod initialize_position_registry;
+mod migrate;
+mod preview;
+mod set_creator;
+mod set_payout_destination;
 
+pub use add_locked_liquidity::*;
+pub use add_position::*;
+pub use claim_fees::*;
+pub use claim_reward::*;
 pub use crank::*;
+pub use crank_reward::*;
 pub use initialize::*;
+pub use initialize_day_history::*;
+pub use initialize_position_registry::*;
+pub use migrate::*;
+pub use preview::*;
+pub use set_creator::*;
+pub use set_payout_destination::*;
@@ -0,0 +1,346 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id, token, token_interface,
+};
+
+use super::{
+    claim_fees::{pending_fees_of, settle_remainder, split_pending_fees},
+    crank::{
+        compute_investor_fee_quote, compute_page_contracts_and_locked, get_payout_account,
+        payout_skip_reason, CrankParams, PageStream,
+    },
+};
+use crate::{
+    constants::MAX_PREVIEW_PAGE_SIZE,
+    error::TollgateError,
+    events::SkipReason,
+    math,
+    state::{Allocation, CrankPhase, DayState, Progress},
+    utils::pool::is_disabled_pool,
+    AccountPreviewCrank,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum PreviewStatus {
+    Payout,       // The page would pay its investors
+    Snapshot,     // The page would be added to the day snapshot, paying nothing yet
+    CarriedOver,  // The distributable is below the minimum payout and would be carried over
+    CursorBehind, // The page was already processed, the crank would skip it
+    DayClosed,    // The day is closed, the crank would skip
+    Cap,          // The page would be checked against the investor cap, paying nothing yet
+}
+
+/// Projected payout of a single investor of the page, in page order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct InvestorPreview {
+    pub locked: u64,                     // Average locked amount over the day
+    pub allocation: u64,                 // Cumulative floor allocation
+    pub capped: bool,                    // Whether the investor cap applied
+    pub payout: u64,                     // Amount that would be transferred
+    pub ata_initialized: bool,           // Whether the payout account exists
+    pub skip_reason: Option<SkipReason>, // Why the investor would not be paid
+}
+
+/// Breakdown of what a crank of the page would pay, returned by `preview_crank`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct CrankPreview {
+    pub status: PreviewStatus,         // What the crank would do with the page
+    pub day_state: DayState,           // Day state the crank would process
+    pub phase: CrankPhase,             // Pass the page belongs to
+    pub cursor: u32,                   // Pagination cursor of the page
+    pub page_size: u32,                // Number of investors in the page
+    pub claimable_quote_fee: u64,      // Quote fees claimed when a new day starts
+    pub prev_remainder: u64,           // Previous day remainder swept to the creator
+    pub distributable: u64,            // Distributable amount of the day after carry and cap
+    pub page_locked: u64,              // Locked total of the page
+    pub locked_total: u64,             // Locked total of the snapshot after the page
+    pub f_locked: u64,                 // Locked fraction of Y0, in bps, once the snapshot completes
+    pub eligible_bps: u64,             // Investor share bps after f_locked
+    pub investor_pool: u64,            // Investor share of the distributable
+    pub page_payouts: u64,             // Sum of the investor payouts
+    pub closes_day: bool,              // Whether the page is the last of the day
+    pub creator_payout: u64,           // Creator payout if the page closes the day
+    pub carry: u64,                    // Carry after the page
+    pub payouts: Vec<InvestorPreview>, // Per-investor breakdown, in page order
+}
+
+impl CrankPreview {
+    fn new(day_state: DayState, progress: &Progress, cursor: u32, page_size: u32) -> Self {
+        Self {
+            status: PreviewStatus::Payout,
+            day_state,
+            phase: progress.phase.clone(),
+            cursor,
+            page_size,
+            claimable_quote_fee: 0,
+            prev_remainder: 0,
+            distributable: progress.distributable,
+            page_locked: 0,
+            locked_total: progress.locked_total,
+            f_locked: 0,
+            eligible_bps: 0,
+            investor_pool: progress.investor_pool,
+            page_payouts: 0,
+            closes_day: false,
+            creator_payout: 0,
+            carry: progress.carry,
+            payouts: vec![],
+        }
+    }
+}
+
+/// Returns the balance of the owner quote account, which is only created by
+/// the first `claim_fees`.
+fn owner_quote_balance(accounts: &AccountPreviewCrank) -> Result<u64> {
+    let quote_account = get_associated_token_address_with_program_id(
+        &accounts.owner.key(),
+        &accounts.quote_mint.key(),
+        &accounts.quote_program.key(),
+    );
+    require_keys_eq!(
+        accounts.quote_account.key(),
+        quote_account,
+        ErrorCode::ConstraintAssociated
+    );
+    if accounts.quote_account.data_is_empty() {
+        return Ok(0);
+    }
+
+    let data = accounts.quote_account.try_borrow_data()?;
+    Ok(token_interface::TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// Runs the crank math of a page without transferring anything or updating
+/// the progress, and returns the breakdown as return data.
+pub fn preview_crank<'info>(
+    ctx: Context<'_, '_, '_, 'info, AccountPreviewCrank<'info>>,
+    params: CrankParams,
+) -> Result<CrankPreview> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let policy = &ctx.accounts.policy;
+    let quote_balance = owner_quote_balance(ctx.accounts)?;
+    // The day transitions are applied to a copy of the progress
+    let mut progress = (*ctx.accounts.progress).clone();
+
+    let investor_accounts = ctx.remaining_accounts;
+    require_eq!(
+        0,
+        investor_accounts.len() % 4,
+        TollgateError::InvalidInvestorAccounts
+    );
+    let page_size = investor_accounts.len() / 4;
+    require_gte!(
+        MAX_PREVIEW_PAGE_SIZE,
+        page_size as u32,
+        TollgateError::PreviewPageTooLarge
+    );
+
+    msg!(
+        "Preview::Starting preview with cursor={} and page_size={}",
+        params.cursor,
+        page_size
+    );
+
+    // Validate params
+    params.assert(policy.investor_count, page_size as u32)?;
+
+    // Mirror the day opened by claim_fees, followed by the page
+    let mut prev_remainder = 0;
+    let day = if progress.is_new_day(timestamp) {
+        // The pending fees of the extra positions are only known to claim_fees
+        require!(
+            ctx.accounts.position_registry.position_count <= 1,
+            TollgateError::DayNotOpened
+        );
+        prev_remainder = settle_remainder(quote_balance, policy, &mut progress)?.swept;
+        progress.start_new_day(timestamp)?;
+        DayState::New
+    } else if progress.day_state == DayState::Closed && progress.last_distribution_ts != 0 {
+        let mut preview =
+            CrankPreview::new(DayState::Closed, &progress, params.cursor, page_size as u32);
+        preview.status = PreviewStatus::DayClosed;
+        return Ok(preview);
+    } else {
+        DayState::Same
+    };
+
+    let mut preview = CrankPreview::new(day.clone(), &progress, params.cursor, page_size as u32);
+    preview.prev_remainder = prev_remainder;
+
+    // Validate progress cursor
+    if params.cursor < progress.cursor {
+        preview.status = PreviewStatus::CursorBehind;
+        return Ok(preview);
+    }
+    require_eq!(
+        params.cursor,
+        progress.cursor,
+        TollgateError::PaginationCursorTooLarge
+    );
+
+    // Load the pending position fees
+    let pool = ctx.accounts.pool.load()?;
+    let (fee_a_pending, fee_b_pending, quote_token_order) =
+        pending_fees_of(&policy.quote_mint, &pool, &*ctx.accounts.position.load()?)?;
+
+    let distributable = if day == DayState::New {
+        // A disabled pool is not claimed, its fees stay pending on the position
+        let quote_fee = if is_disabled_pool(&pool) {
+            0
+        } else {
+            let (base_fee, quote_fee) =
+                split_pending_fees(quote_token_order, fee_a_pending, fee_b_pending);
+            require_eq!(base_fee, 0, TollgateError::BaseDenominatedFees);
+            quote_fee
+        };
+        preview.claimable_quote_fee = quote_fee;
+
+        let distributable = quote_fee.saturating_add(
+            quote_balance
+                .saturating_sub(progress.reserve)
+                .saturating_sub(prev_remainder),
+        );
+        // Optional daily cap
+        policy
+            .daily_cap
+            .map_or(distributable, |cap| distributable.min(cap))
+    } else {
+        progress.distributable
+    };
+    preview.distributable = distributable;
+
+    if day == DayState::New && distributable < policy.min_payout_lamports {
+        preview.status = PreviewStatus::CarriedOver;
+        preview.closes_day = true;
+        preview.carry = distributable;
+        return Ok(preview);
+    }
+
+    if page_size == 0 {
+        return Ok(preview);
+    }
+
+    let streams: Vec<AccountInfo<'info>> = investor_accounts.iter().step_by(4).cloned().collect();
+    let stats: Vec<AccountInfo<'info>> = investor_accounts
+        .iter()
+        .skip(2)
+        .step_by(4)
+        .cloned()
+        .collect();
+    let redirects: Vec<AccountInfo<'info>> = investor_accounts
+        .iter()
+        .skip(3)
+        .step_by(4)
+        .cloned()
+        .collect();
+    let stream_keys: Vec<Pubkey> = streams.iter().map(|stream| stream.key()).collect();
+    progress.record_page_streams(&stream_keys)?;
+    let epoch_start = progress.last_distribution_ts as u64;
+    let (contracts, locked_per) = compute_page_contracts_and_locked(
+        &streams,
+        &stats,
+        &redirects,
+        &policy.vault,
+        epoch_start,
+    )?;
+    preview.page_locked = math::checked_sum(&locked_per)?;
+
+    if progress.phase == CrankPhase::Snapshot {
+        preview.status = PreviewStatus::Snapshot;
+        preview.locked_total = progress
+            .locked_total
+            .checked_add(preview.page_locked)
+            .ok_or(TollgateError::MathOverflow)?;
+        if progress.cursor + page_size as u32 >= policy.investor_count {
+            let (f_locked, eligible_bps, investor_pool) =
+                compute_investor_fee_quote(policy, distributable, preview.locked_total)?;
+            preview.f_locked = f_locked;
+            preview.eligible_bps = eligible_bps;
+            preview.investor_pool = investor_pool;
+        }
+        return Ok(preview);
+    }
+
+    if progress.phase == CrankPhase::Cap {
+        preview.status = PreviewStatus::Cap;
+        return Ok(preview);
+    }
+
+    for (i, contract) in contracts.iter().enumerate() {
+        let ata_ai = &investor_accounts[i * 4 + 1];
+        let ata_initialized = ata_ai.data_len() == token::TokenAccount::LEN;
+        let contract = match contract {
+            PageStream::Active(contract) => contract,
+            PageStream::Skipped(reason) => {
+                preview.payouts.push(InvestorPreview {
+                    locked: 0,
+                    allocation: 0,
+                    capped: false,
+                    payout: 0,
+                    ata_initialized,
+                    skip_reason: Some(reason.clone()),
+                });
+                continue;
+            }
+        };
+        let (payout_account, redirected) = get_payout_account(
+            &investor_accounts[i * 4 + 3],
+            &policy.vault,
+            streams[i].key,
+            &contract.recipient,
+            &ctx.accounts.quote_mint.key(),
+        )?;
+        let payout_account_error = if redirected {
+            TollgateError::InvalidPayoutDestination
+        } else {
+            TollgateError::InvalidInvestorAta
+        };
+        require_keys_eq!(ata_ai.key(), payout_account, payout_account_error);
+
+        let locked = locked_per[i];
+        let Allocation {
+            amount: allocation,
+            capped,
+        } = progress.allocate(locked)?;
+        let skip_reason = payout_skip_reason(
+            locked,
+            ata_initialized,
+            allocation,
+            policy.min_payout_lamports,
+        );
+        let payout = if skip_reason.is_none() { allocation } else { 0 };
+
+        preview.page_payouts = preview.page_payouts.saturating_add(payout);
+        preview.payouts.push(InvestorPreview {
+            locked,
+            allocation,
+            capped,
+            payout,
+            ata_initialized,
+            skip_reason,
+        });
+    }
+
+    preview.closes_day = progress.cursor.saturating_add(page_size as u32) >= policy.investor_count;
+    if preview.closes_day {
+        let investor_payouts = progress.daily_spent.saturating_add(preview.page_payouts);
+        // The excess of the capped investors left unallocated joins the carry
+        let unpaid = progress.investor_pool.saturating_sub(investor_payouts);
+        let creator_share = distributable.saturating_sub(progress.investor_pool);
+        if creator_share >= policy.min_payout_lamports {
+            preview.creator_payout = creator_share;
+        }
+        preview.carry = progress
+            .carry
+            .saturating_add(unpaid)
+            .saturating_add(creator_share - preview.creator_payout);
+    }
+
+    msg!(
+        "Preview::Distributable: {}, investor pool: {}, page payouts: {}",
+        distributable,
+        progress.investor_pool,
+        preview.page_payouts
+    );
+    Ok(preview)
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::EVENT_SCHEMA_VERSION, events::CreatorUpdated, utils::event::EventEmitter,
+    AccountSetCreator,
+};
+
+pub fn set_creator(ctx: Context<AccountSetCreator>, creator: Pubkey) -> Result<()> {
+    msg!("SetCreator::Setting creator");
+
+    let policy = &mut ctx.accounts.policy;
+    let previous_creator = policy.creator;
+    policy.set_creator(creator)?;
+
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    EventEmitter::new(&event_authority, ctx.bumps.event_authority).emit(CreatorUpdated {
+        schema_version: EVENT_SCHEMA_VERSION,
+        vault: policy.vault,
+        policy: policy.key(),
+        previous_creator,
+        creator,
+    })?;
+
+    msg!(
+        "SetCreator::Creator payouts moved from {} to {}",
+        previous_creator,
+        creator
+    );
+    Ok(())
+}
@@ -0,0 +1,45 @@
+#![allow(deprecated)]
+
+use anchor_lang::{prelude::*, solana_program::borsh::try_from_slice_unchecked};
+use streamflow_sdk::state::Contract;
+
+use crate::{
+    constants::EVENT_SCHEMA_VERSION, error::TollgateError, events::PayoutDestinationSet,
+    utils::event::EventEmitter, AccountSetPayoutDestination,
+};
+
+pub fn set_payout_destination(ctx: Context<AccountSetPayoutDestination>) -> Result<()> {
+    msg!("SetPayoutDestination::Setting payout destination");
+
+    let contract = try_from_slice_unchecked::<Contract>(&ctx.accounts.stream.try_borrow_data()?)?;
+    require_keys_eq!(
+        contract.recipient,
+        ctx.accounts.recipient.key(),
+        TollgateError::PayoutRedirectUnauthorized
+    );
+
+    ctx.accounts.payout_redirect.set(
+        ctx.accounts.vault.key(),
+        ctx.accounts.stream.key(),
+        ctx.accounts.recipient.key(),
+        ctx.accounts.destination.key(),
+        ctx.accounts.destination.owner,
+        ctx.bumps.payout_redirect,
+    );
+
+    let event_authority = ctx.accounts.event_authority.to_account_info();
+    EventEmitter::new(&event_authority, ctx.bumps.event_authority).emit(PayoutDestinationSet {
+        schema_version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        stream: ctx.accounts.stream.key(),
+        recipient: ctx.accounts.recipient.key(),
+        destination: ctx.accounts.destination.key(),
+    })?;
+
+    msg!(
+        "SetPayoutDestination::Payouts of stream {} redirected to {}",
+        ctx.accounts.stream.key(),
+        ctx.accounts.destination.key()
+    );
+    Ok(())
+}
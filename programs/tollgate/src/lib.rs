@@ -5,6 +5,7 @@ pub mod constants;
 pub mod error;
 pub mod events;
 pub mod instructions;
+pub mod math;
 pub mod state;
 pub mod utils;
 
@@ -23,17 +24,74 @@ pub mod tollgate {
         instructions::initialize(ctx, params)
     }
 
+    pub fn claim_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, AccountClaimFees<'info>>,
+    ) -> Result<()> {
+        instructions::claim_fees(ctx)
+    }
+
     pub fn crank<'info>(
-        ctx: Context<'_, '_, '_, 'info, AccountCrank<'info>>,
+        ctx: Context<'_, '_, '_, 'info, AccountCrankPage<'info>>,
         params: instructions::CrankParams,
     ) -> Result<()> {
         instructions::crank(ctx, params)
     }
 
     pub fn crank_with_init<'info>(
-        ctx: Context<'_, '_, '_, 'info, AccountCrank<'info>>,
+        ctx: Context<'_, '_, '_, 'info, AccountCrankPage<'info>>,
         params: instructions::CrankParams,
     ) -> Result<()> {
         instructions::crank_with_init(ctx, params)
     }
+
+    pub fn claim_reward(ctx: Context<AccountClaimReward>, reward_index: u8) -> Result<()> {
+        instructions::claim_reward(ctx, reward_index)
+    }
+
+    pub fn crank_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, AccountCrankReward<'info>>,
+        params: instructions::CrankParams,
+    ) -> Result<()> {
+        instructions::crank_reward(ctx, params)
+    }
+
+    pub fn migrate(ctx: Context<AccountMigrate>) -> Result<()> {
+        instructions::migrate(ctx)
+    }
+
+    pub fn initialize_day_history(ctx: Context<AccountInitializeDayHistory>) -> Result<()> {
+        instructions::initialize_day_history(ctx)
+    }
+
+    pub fn preview_crank<'info>(
+        ctx: Context<'_, '_, '_, 'info, AccountPreviewCrank<'info>>,
+        params: instructions::CrankParams,
+    ) -> Result<instructions::CrankPreview> {
+        instructions::preview_crank(ctx, params)
+    }
+
+    pub fn set_creator(ctx: Context<AccountSetCreator>, creator: Pubkey) -> Result<()> {
+        instructions::set_creator(ctx, creator)
+    }
+
+    pub fn set_payout_destination(ctx: Context<AccountSetPayoutDestination>) -> Result<()> {
+        instructions::set_payout_destination(ctx)
+    }
+
+    pub fn initialize_position_registry(
+        ctx: Context<AccountInitializePositionRegistry>,
+    ) -> Result<()> {
+        instructions::initialize_position_registry(ctx)
+    }
+
+    pub fn add_position(ctx: Context<AccountAddPosition>) -> Result<()> {
+        instructions::add_position(ctx)
+    }
+
+    pub fn add_locked_liquidity(
+        ctx: Context<AccountAddLockedLiquidity>,
+        params: instructions::AddLockedLiquidityParams,
+    ) -> Result<()> {
+        instructions::add_locked_liquidity(ctx, params)
+    }
 }
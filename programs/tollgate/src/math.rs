@@ -0,0 +1,171 @@
+//! Distribution math on u64 amounts with u128 intermediates.
+//!
+//! Every product is computed in u128 so that `amount * amount` cannot
+//! overflow, and every division states its rounding mode. Shares paid out of
+//! a pool always round down, so the sum of the shares never exceeds the pool.
+
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_BPS, error::TollgateError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down, // Floor, used for anything paid out of a pool
+    Up,   // Ceiling
+}
+
+/// Returns `a * b / denominator`, rounded as requested.
+pub fn mul_div(a: u64, b: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+    require_neq!(denominator, 0, TollgateError::MathDivisionByZero);
+
+    let product = (a as u128)
+        .checked_mul(b as u128)
+        .ok_or(TollgateError::MathOverflow)?;
+    let denominator = denominator as u128;
+    let quotient = match rounding {
+        Rounding::Down => product / denominator,
+        Rounding::Up => product.div_ceil(denominator),
+    };
+    u64::try_from(quotient).map_err(|_| error!(TollgateError::MathOverflow))
+}
+
+/// Returns the sum of the amounts, failing on overflow.
+pub fn checked_sum(amounts: &[u64]) -> Result<u64> {
+    amounts.iter().try_fold(0u64, |total, amount| {
+        total
+            .checked_add(*amount)
+            .ok_or_else(|| error!(TollgateError::MathOverflow))
+    })
+}
+
+/// Returns `amount * bps / MAX_BPS`, rounded down. `bps` is capped at
+/// `MAX_BPS`, so the result never exceeds `amount`.
+pub fn bps_share(amount: u64, bps: u64) -> Result<u64> {
+    mul_div(
+        amount,
+        bps.min(MAX_BPS as u64),
+        MAX_BPS as u64,
+        Rounding::Down,
+    )
+}
+
+/// Returns the locked fraction `locked_total / y0` in bps, rounded down and
+/// capped at `MAX_BPS`.
+pub fn locked_fraction_bps(locked_total: u64, y0: u64) -> Result<u64> {
+    require_neq!(y0, 0, TollgateError::MathDivisionByZero);
+
+    let fraction = (locked_total as u128) * (MAX_BPS as u128) / (y0 as u128);
+    Ok(fraction.min(MAX_BPS as u128) as u64)
+}
+
+/// Returns the share of `pool` owed to `weight` out of `total_weight`,
+/// rounded down. An empty total weight gets nothing.
+pub fn pro_rata_share(pool: u64, weight: u64, total_weight: u64) -> Result<u64> {
+    if total_weight == 0 {
+        return Ok(0);
+    }
+    mul_div(pool, weight, total_weight, Rounding::Down)
+}
+
+/// Returns whether `locked` reaches `cap` at the water level
+/// `level_pool / level_locked`, i.e. `level_pool * locked >= cap * level_locked`.
+/// A zero weight never reaches the cap, any other weight does once the level
+/// has no weight left.
+pub fn reaches_cap(locked: u64, cap: u64, level_pool: u64, level_locked: u64) -> bool {
+    if locked == 0 {
+        return false;
+    }
+    (level_pool as u128) * (locked as u128) >= (cap as u128) * (level_locked as u128)
+}
+
+/// Unlock schedule of a vesting stream: nothing is unlocked before `start`,
+/// then `base` plus `amount_per_period` every full `period` elapsed since
+/// `steps_start`, and the whole `amount` from `end` on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnlockSchedule {
+    pub amount: u64,            // Total amount of the stream
+    pub start: u64,             // Time of the first unlock
+    pub end: u64,               // Time the whole amount is unlocked
+    pub base: u64,              // Amount unlocked at `start`
+    pub steps_start: u64,       // Time the periodic unlocks are counted from
+    pub period: u64,            // Seconds between two periodic unlocks
+    pub amount_per_period: u64, // Amount unlocked every period
+}
+
+impl UnlockSchedule {
+    /// Returns the amount unlocked at `t`.
+    pub fn unlocked_at(&self, t: u64) -> u64 {
+        if t < self.start {
+            return 0;
+        }
+        if t >= self.end {
+            return self.amount;
+        }
+        let steps = t.saturating_sub(self.steps_start) / self.period.max(1);
+        steps
+            .saturating_mul(self.amount_per_period)
+            .saturating_add(self.base)
+            .min(self.amount)
+    }
+
+    /// Returns the integral of `floor((t - steps_start) / period)` over
+    /// `[steps_start, t)`, `0` before `steps_start`.
+    fn steps_integral(&self, t: u64) -> Result<u128> {
+        let period = self.period.max(1) as u128;
+        let elapsed = t.saturating_sub(self.steps_start) as u128;
+        let (steps, rest) = (elapsed / period, elapsed % period);
+        let full = steps
+            .checked_mul(steps.saturating_sub(1))
+            .and_then(|n| (n / 2).checked_mul(period))
+            .ok_or(TollgateError::MathOverflow)?;
+        full.checked_add(steps * rest)
+            .ok_or(TollgateError::MathOverflow.into())
+    }
+
+    /// Returns the integral of the unlocked amount over `[from, to)`.
+    fn unlocked_integral(&self, from: u64, to: u64) -> Result<u128> {
+        let amount = self.amount as u128;
+        // Fully unlocked from `end` on
+        let vesting_end = to.min(self.end);
+        let mut integral = amount * to.saturating_sub(from.max(self.end)) as u128;
+
+        // Stepping between `start` and `end`, until the steps reach the amount
+        let from = from.max(self.start);
+        if from >= vesting_end {
+            return Ok(integral);
+        }
+        if self.base >= self.amount || self.amount_per_period == 0 {
+            let level = self.base.min(self.amount) as u128;
+            return Ok(integral + level * (vesting_end - from) as u128);
+        }
+        let full_steps = (self.amount - self.base).div_ceil(self.amount_per_period) as u128;
+        let full_ts = (self.steps_start as u128)
+            .saturating_add(full_steps.saturating_mul(self.period.max(1) as u128))
+            .clamp(from as u128, vesting_end as u128) as u64;
+
+        if full_ts > from {
+            let steps = self.steps_integral(full_ts)? - self.steps_integral(from)?;
+            integral = steps
+                .checked_mul(self.amount_per_period as u128)
+                .and_then(|steps| steps.checked_add(self.base as u128 * (full_ts - from) as u128))
+                .and_then(|stepping| stepping.checked_add(integral))
+                .ok_or(TollgateError::MathOverflow)?;
+        }
+        Ok(integral + amount * (vesting_end - full_ts) as u128)
+    }
+}
+
+/// Returns the average locked amount of a stream over `[epoch_start, epoch_end)`,
+/// rounded down. The locked amount is a step function of time, so the average
+/// is its exact integral over the epoch divided by the epoch length.
+pub fn average_locked(schedule: &UnlockSchedule, epoch_start: u64, epoch_end: u64) -> Result<u64> {
+    require_gt!(epoch_end, epoch_start, TollgateError::MathDivisionByZero);
+
+    let duration = (epoch_end - epoch_start) as u128;
+    let unlocked = schedule.unlocked_integral(epoch_start, epoch_end)?;
+    let locked = (schedule.amount as u128 * duration)
+        .checked_sub(unlocked)
+        .ok_or(TollgateError::MathOverflow)?;
+
+    Ok((locked / duration) as u64)
+}
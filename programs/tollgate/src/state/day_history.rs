@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ACCOUNT_RESERVED_SPACE, DAY_HISTORY_LEN},
+    error::TollgateError,
+    state::Progress,
+};
+
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DayRecord {
+    pub index: u64,            // Sequential index of the day
+    pub day_ts: i64,           // Start timestamp of the day
+    pub fees_claimed: u64,     // Quote fees claimed during the day
+    pub distributable: u64,    // Distributable amount of the day
+    pub investor_payouts: u64, // Total paid to investors
+    pub creator_payout: u64,   // Paid to the creator at day close
+    pub carry: u64,            // Carried over to the next day
+    pub locked_total: u64,     // Locked total of the day snapshot
+    pub investors_paid: u32,   // Number of investors paid
+    pub padding: [u8; 4],      // Explicit padding for alignment
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct DayHistory {
+    pub vault: Pubkey,                          // Associated vault
+    pub days_closed: u64,                       // Number of days closed so far
+    pub current: DayRecord,                     // Record of the day being distributed
+    pub records: [DayRecord; DAY_HISTORY_LEN],  // Ring buffer of the last closed days
+    pub version: u8,                            // Account layout version
+    pub bump: u8,                               // PDA bump
+    pub padding: [u8; 6],                       // Explicit padding for alignment
+    pub reserved: [u8; ACCOUNT_RESERVED_SPACE], // Spare space for future fields
+}
+
+impl DayHistory {
+    pub const SPACE: usize = Self::DISCRIMINATOR.len() + std::mem::size_of::<Self>();
+    pub const VERSION: u8 = 1;
+
+    /// Initializes the DayHistory account.
+    pub fn initialize(&mut self, vault: Pubkey, bump: u8) -> Result<()> {
+        // assert history is not already initialized
+        require!(
+            self.version == 0,
+            TollgateError::DayHistoryAlreadyInitialized
+        );
+
+        self.vault = vault;
+        self.days_closed = 0;
+        self.current = DayRecord::default();
+        self.version = Self::VERSION;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    /// Starts the record of a new day.
+    pub fn open_day(&mut self) {
+        self.current = DayRecord::default();
+    }
+
+    /// Adds the quote fees claimed to the record of the current day.
+    pub fn record_fees_claimed(&mut self, quote_fee: u64) -> Result<()> {
+        self.current.fees_claimed = self
+            .current
+            .fees_claimed
+            .checked_add(quote_fee)
+            .ok_or(TollgateError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Adds the investors paid by a page to the record of the current day.
+    pub fn record_investors_paid(&mut self, investors_paid: u32) -> Result<()> {
+        self.current.investors_paid = self
+            .current
+            .investors_paid
+            .checked_add(investors_paid)
+            .ok_or(TollgateError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Completes the record of the current day from the closing progress and
+    /// appends it to the ring buffer, overwriting the oldest record when full.
+    pub fn close_day(
+        &mut self,
+        progress: &Progress,
+        investor_payouts: u64,
+        creator_payout: u64,
+    ) -> Result<DayRecord> {
+        let mut record = self.current;
+        record.index = self.days_closed;
+        record.day_ts = progress.last_distribution_ts;
+        record.distributable = progress.distributable;
+        record.investor_payouts = investor_payouts;
+        record.creator_payout = creator_payout;
+        record.carry = progress.carry;
+        record.locked_total = progress.locked_total;
+
+        self.records[(self.days_closed % DAY_HISTORY_LEN as u64) as usize] = record;
+        self.days_closed = self
+            .days_closed
+            .checked_add(1)
+            .ok_or(TollgateError::MathOverflow)?;
+        self.current = DayRecord::default();
+
+        Ok(record)
+    }
+
+    /// Returns the closed days held by the ring buffer, oldest first.
+    pub fn closed_days(&self) -> Vec<DayRecord> {
+        let len = self.days_closed.min(DAY_HISTORY_LEN as u64);
+        (self.days_closed - len..self.days_closed)
+            .map(|index| self.records[(index % DAY_HISTORY_LEN as u64) as usize])
+            .collect()
+    }
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::damm_v2_constants, error::TollgateError};
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct InvestorStats {
+    pub version: u8,                                        // Account layout version
+    pub vault: Pubkey,                                      // Associated vault
+    pub stream: Pubkey,                                     // Streamflow contract of the investor
+    pub recipient: Pubkey,                                  // Stream recipient
+    pub total_paid: u64,                                    // Lifetime amount paid
+    pub last_paid_day: Option<i64>,                         // Day timestamp of the last payout
+    pub last_seen_day: Option<i64>,                         // Day timestamp of the last payout pass
+    pub skipped_days: u32,                                  // Payout passes without a payout
+    pub dust: u64,                                          // Allocations below min payout
+    pub skipped: u64,                                       // Allocations skipped for a missing ATA
+    pub reward_paid: [u64; damm_v2_constants::NUM_REWARDS], // Lifetime rewards paid per reward slot
+    pub bump: u8,                                           // PDA bump
+    pub reserved: [u8; 35],                                 // Spare space for future fields
+}
+
+impl InvestorStats {
+    pub const SPACE: usize = Self::DISCRIMINATOR.len() + Self::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Returns fresh stats for the investor of a stream.
+    pub fn new(vault: Pubkey, stream: Pubkey, recipient: Pubkey, bump: u8) -> Self {
+        Self {
+            version: Self::VERSION,
+            vault,
+            stream,
+            recipient,
+            total_paid: 0,
+            last_paid_day: None,
+            last_seen_day: None,
+            skipped_days: 0,
+            dust: 0,
+            skipped: 0,
+            reward_paid: [0; damm_v2_constants::NUM_REWARDS],
+            bump,
+            reserved: [0; 35],
+        }
+    }
+
+    /// Records a payout made on the day starting at `day` (`last_distribution_ts`).
+    pub fn record_paid(&mut self, amount: u64, day: i64) -> Result<()> {
+        self.total_paid = self
+            .total_paid
+            .checked_add(amount)
+            .ok_or(TollgateError::MathOverflow)?;
+        self.last_paid_day = Some(day);
+        self.last_seen_day = Some(day);
+        Ok(())
+    }
+
+    /// Records a payout pass of the day starting at `day` that did not pay the
+    /// investor, so that a skipped day can be told apart from a missing one.
+    pub fn record_unpaid_day(&mut self, day: i64) -> Result<()> {
+        self.skipped_days = self
+            .skipped_days
+            .checked_add(1)
+            .ok_or(TollgateError::MathOverflow)?;
+        self.last_seen_day = Some(day);
+        Ok(())
+    }
+
+    /// Records a reward payout, the dust and skipped counters only track the quote payouts.
+    pub fn record_reward_paid(&mut self, reward_index: usize, amount: u64) -> Result<()> {
+        let paid = self
+            .reward_paid
+            .get_mut(reward_index)
+            .ok_or(TollgateError::InvalidRewardIndex)?;
+        *paid = paid
+            .checked_add(amount)
+            .ok_or(TollgateError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Records an allocation below the minimum payout, carried over.
+    pub fn record_dust(&mut self, amount: u64) -> Result<()> {
+        self.dust = self
+            .dust
+            .checked_add(amount)
+            .ok_or(TollgateError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Records an allocation skipped because the investor ATA is missing.
+    pub fn record_skipped(&mut self, amount: u64) -> Result<()> {
+        self.skipped = self
+            .skipped
+            .checked_add(amount)
+            .ok_or(TollgateError::MathOverflow)?;
+        Ok(())
+    }
+}
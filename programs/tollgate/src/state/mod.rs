@@ -1,5 +1,15 @@
+pub mod day_history;
+pub mod investor_stats;
+pub mod payout_redirect;
 pub mod policy;
+pub mod position_registry;
 pub mod progress;
+pub mod reward_progress;
 
+pub use day_history::*;
+pub use investor_stats::*;
+pub use payout_redirect::*;
 pub use policy::*;
+pub use position_registry::*;
 pub use progress::*;
+pub use reward_progress::*;
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PayoutRedirect {
+    pub version: u8,               // Account layout version
+    pub vault: Pubkey,             // Associated vault
+    pub stream: Pubkey,            // Streamflow contract of the investor
+    pub recipient: Pubkey,         // Stream recipient that set the destination
+    pub destination: Pubkey,       // Quote token account receiving the payouts
+    pub destination_owner: Pubkey, // Owner of the destination, receiving the rewards
+    pub bump: u8,                  // PDA bump
+    pub reserved: [u8; 32],        // Spare space for future fields
+}
+
+impl PayoutRedirect {
+    pub const SPACE: usize = Self::DISCRIMINATOR.len() + Self::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Sets the payout destination of the stream on behalf of its recipient.
+    pub fn set(
+        &mut self,
+        vault: Pubkey,
+        stream: Pubkey,
+        recipient: Pubkey,
+        destination: Pubkey,
+        destination_owner: Pubkey,
+        bump: u8,
+    ) {
+        self.version = Self::VERSION;
+        self.vault = vault;
+        self.stream = stream;
+        self.recipient = recipient;
+        self.destination = destination;
+        self.destination_owner = destination_owner;
+        self.bump = bump;
+    }
+
+    /// Returns the destination of the payouts of `recipient`, if redirected.
+    /// A redirect set by a previous recipient of the stream is ignored.
+    pub fn destination_of(&self, recipient: &Pubkey) -> Option<Pubkey> {
+        (self.recipient == *recipient).then_some(self.destination)
+    }
+
+    /// Returns the owner of the reward accounts of `recipient`, if redirected:
+    /// rewards go to the reward ATA of the owner of the quote destination.
+    pub fn reward_owner_of(&self, recipient: &Pubkey) -> Option<Pubkey> {
+        (self.recipient == *recipient).then_some(self.destination_owner)
+    }
+}
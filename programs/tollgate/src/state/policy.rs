@@ -1,26 +1,156 @@
 use anchor_lang::prelude::*;
 
-use crate::{constants::MAX_BPS, error::TollgateError, instructions::InitializeParams};
+use crate::{
+    constants::{ACCOUNT_RESERVED_SPACE, MAX_BPS},
+    error::TollgateError,
+    instructions::InitializeParams,
+    math, utils,
+};
 
-#[account]
-#[derive(Debug, InitSpace)]
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Default, PartialEq)]
+pub enum CarryPolicy {
+    #[default]
+    SweepToCreator, // The previous day remainder is swept to the creator
+    RollOver, // The previous day remainder is rolled into the distributable of the day
+    Reserve {
+        release_days: u16,
+    }, // The previous day remainder is released over `release_days` days
+}
+
+impl CarryPolicy {
+    pub fn assert(&self) -> Result<()> {
+        // assert the reserve is released over at least one day
+        if let Self::Reserve { release_days } = self {
+            require_gt!(*release_days, 0, TollgateError::InvalidReleaseDays);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq)]
+pub enum CapLimit {
+    Absolute { amount: u64 }, // Quote amount an investor can receive per day
+    Bps { bps: u16 },         // Share of the investor pool of the day an investor can receive
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq)]
+pub enum CapExcess {
+    Redistribute, // The excess is water-filled over the uncapped investors
+    Carry,        // The excess is carried over to the next day
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq)]
+pub struct InvestorCap {
+    pub limit: CapLimit,   // Daily payout limit of a single investor
+    pub excess: CapExcess, // Handling of the allocations above the limit
+}
+
+impl InvestorCap {
+    pub fn assert(&self) -> Result<()> {
+        // assert the limit is greater than 0 and at most 100%
+        match self.limit {
+            CapLimit::Absolute { amount } => {
+                require_gt!(amount, 0, TollgateError::InvalidInvestorCap);
+            }
+            CapLimit::Bps { bps } => {
+                require_gt!(bps, 0, TollgateError::InvalidInvestorCap);
+                require_gte!(MAX_BPS, bps, TollgateError::InvalidInvestorCap);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the cap of an investor for a day of `investor_pool`.
+    pub fn resolve(&self, investor_pool: u64) -> Result<u64> {
+        match self.limit {
+            CapLimit::Absolute { amount } => Ok(amount),
+            CapLimit::Bps { bps } => math::bps_share(investor_pool, bps as u64),
+        }
+    }
+}
+
+/// The account traits are implemented below instead of by `#[account]`, so
+/// that accounts of an older layout are refused before they are decoded.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
 pub struct Policy {
-    pub vault: Pubkey,               // Associated vault
-    pub creator: Pubkey,             // Creator pubkey that receives remainder
-    pub quote_mint: Pubkey,          // Quote mint of the associated pool
-    pub investor_count: u32,         // Investor count
-    pub init_investor_ata: bool,     // Initialize investor ATA if needed
-    pub investor_fee_share_bps: u16, // e.g., 7000 for 70%
-    pub min_payout_lamports: u64,    // Dust threshold
-    pub daily_cap: Option<u64>,      // Optional total daily distributable
-    pub y0: u64,                     // Total investor allocation at TGE
-    pub is_initialized: bool,        // Whether initialized
-    pub owner_bump: u8,              // Position owner bump
-    pub bump: u8,                    // PDA bump
+    pub version: u8,                            // Account layout version
+    pub vault: Pubkey,                          // Associated vault
+    pub creator: Pubkey,                        // Creator pubkey that receives remainder
+    pub quote_mint: Pubkey,                     // Quote mint of the associated pool
+    pub investor_count: u32,                    // Investor count
+    pub init_investor_ata: bool,                // Initialize investor ATA if needed
+    pub investor_fee_share_bps: u16,            // e.g., 7000 for 70%
+    pub min_payout_lamports: u64,               // Dust threshold
+    pub daily_cap: Option<u64>,                 // Optional total daily distributable
+    pub y0: u64,                                // Total investor allocation at TGE
+    pub is_initialized: bool,                   // Whether initialized
+    pub owner_bump: u8,                         // Position owner bump
+    pub bump: u8,                               // PDA bump
+    pub carry_policy: CarryPolicy,              // Handling of the previous day remainder
+    pub investor_cap: Option<InvestorCap>,      // Optional daily payout cap of a single investor
+    pub reserved: [u8; ACCOUNT_RESERVED_SPACE], // Spare space for future fields
+}
+
+impl Discriminator for Policy {
+    // sha256("account:Policy")[..8], as derived by `#[account]`
+    const DISCRIMINATOR: &'static [u8] = &[222, 135, 7, 163, 235, 177, 33, 68];
+}
+
+impl Owner for Policy {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl AccountSerialize for Policy {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        writer
+            .write_all(Self::DISCRIMINATOR)
+            .map_err(|_| ErrorCode::AccountDidNotSerialize)?;
+        AnchorSerialize::serialize(self, writer).map_err(|_| ErrorCode::AccountDidNotSerialize)?;
+
+        Ok(())
+    }
+}
+
+impl AccountDeserialize for Policy {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        utils::account::try_deserialize_versioned(buf, PolicyV0::SPACE, Self::VERSION)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let mut data = &buf[Self::DISCRIMINATOR.len()..];
+        AnchorDeserialize::deserialize(&mut data)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+/// Pre-versioning layout of the Policy account (version 0).
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct PolicyV0 {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub quote_mint: Pubkey,
+    pub investor_count: u32,
+    pub init_investor_ata: bool,
+    pub investor_fee_share_bps: u16,
+    pub min_payout_lamports: u64,
+    pub daily_cap: Option<u64>,
+    pub y0: u64,
+    pub is_initialized: bool,
+    pub owner_bump: u8,
+    pub bump: u8,
+}
+
+impl PolicyV0 {
+    pub const SPACE: usize = Policy::DISCRIMINATOR.len() + Self::INIT_SPACE;
 }
 
 impl Policy {
     pub const SPACE: usize = Self::DISCRIMINATOR.len() + Self::INIT_SPACE;
+    pub const VERSION: u8 = 1;
 
     /// Initializes the Policy account.
     pub fn initialize(
@@ -60,6 +190,12 @@ impl Policy {
         // assert y0 is greater than 0
         require_gt!(params.y0, 0, TollgateError::InvalidY0Allocation);
 
+        params.carry_policy.assert()?;
+        if let Some(investor_cap) = &params.investor_cap {
+            investor_cap.assert()?;
+        }
+
+        self.version = Self::VERSION;
         self.vault = vault;
         self.creator = creator;
         self.quote_mint = quote_mint;
@@ -72,7 +208,74 @@ impl Policy {
         self.is_initialized = true;
         self.owner_bump = owner_bump;
         self.bump = bump;
+        self.carry_policy = params.carry_policy;
+        self.investor_cap = params.investor_cap;
+
+        Ok(())
+    }
+
+    /// Sets the creator whose quote account receives the creator payouts.
+    pub fn set_creator(&mut self, creator: Pubkey) -> Result<()> {
+        require_keys_neq!(creator, Pubkey::default(), TollgateError::InvalidCreator);
+        self.creator = creator;
 
         Ok(())
     }
+
+    /// Upgrades the raw Policy account data to the current version.
+    /// Returns the version the account was migrated from.
+    pub fn migrate<'info>(
+        info: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<u8> {
+        let from_version = utils::account::read_version::<Self>(info, PolicyV0::SPACE)?;
+        require_gte!(
+            Self::VERSION,
+            from_version,
+            TollgateError::UnsupportedAccountVersion
+        );
+        if from_version == Self::VERSION {
+            return Ok(from_version);
+        }
+
+        let policy = {
+            let data = info.try_borrow_data()?;
+            let mut body = &data[Self::DISCRIMINATOR.len()..];
+            match from_version {
+                0 => Self::from(PolicyV0::deserialize(&mut body)?),
+                _ => return Err(TollgateError::UnsupportedAccountVersion.into()),
+            }
+        };
+
+        utils::account::realloc(info, Self::SPACE, payer, system_program)?;
+        let mut data = info.try_borrow_mut_data()?;
+        policy.try_serialize(&mut &mut data[..])?;
+
+        Ok(from_version)
+    }
+}
+
+impl From<PolicyV0> for Policy {
+    fn from(v0: PolicyV0) -> Self {
+        Self {
+            version: Self::VERSION,
+            vault: v0.vault,
+            creator: v0.creator,
+            quote_mint: v0.quote_mint,
+            investor_count: v0.investor_count,
+            init_investor_ata: v0.init_investor_ata,
+            investor_fee_share_bps: v0.investor_fee_share_bps,
+            min_payout_lamports: v0.min_payout_lamports,
+            daily_cap: v0.daily_cap,
+            y0: v0.y0,
+            is_initialized: v0.is_initialized,
+            owner_bump: v0.owner_bump,
+            bump: v0.bump,
+            // Policies created before the carry policy swept the remainder
+            carry_policy: CarryPolicy::SweepToCreator,
+            investor_cap: None,
+            reserved: [0; ACCOUNT_RESERVED_SPACE],
+        }
+    }
 }
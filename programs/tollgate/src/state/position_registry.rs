@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ACCOUNT_RESERVED_SPACE, MAX_HONORARY_POSITIONS},
+    error::TollgateError,
+};
+
+/// A honorary position held by the vault owner.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq)]
+pub struct HonoraryPosition {
+    pub pool: Pubkey,     // DAMM v2 pool of the position
+    pub position: Pubkey, // DAMM v2 position account
+}
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PositionRegistry {
+    pub version: u8,                                           // Account layout version
+    pub vault: Pubkey,                                         // Associated vault
+    pub base_mint: Pubkey,                                     // Base mint of every pool
+    pub position_count: u8,                                    // Registered positions
+    pub positions: [HonoraryPosition; MAX_HONORARY_POSITIONS], // Primary position first
+    pub bump: u8,                                              // PDA bump
+    pub reserved: [u8; ACCOUNT_RESERVED_SPACE],                // Spare space for future fields
+}
+
+impl PositionRegistry {
+    pub const SPACE: usize = Self::DISCRIMINATOR.len() + Self::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Initializes the registry with the primary position of the vault.
+    pub fn initialize(
+        &mut self,
+        vault: Pubkey,
+        base_mint: Pubkey,
+        primary: HonoraryPosition,
+        bump: u8,
+    ) {
+        self.version = Self::VERSION;
+        self.vault = vault;
+        self.base_mint = base_mint;
+        self.position_count = 1;
+        self.positions = [HonoraryPosition::default(); MAX_HONORARY_POSITIONS];
+        self.positions[0] = primary;
+        self.bump = bump;
+    }
+
+    /// Registers a position on a pool that has none yet, returning its index.
+    pub fn register(&mut self, position: HonoraryPosition) -> Result<u8> {
+        require!(
+            !self.positions().iter().any(|p| p.pool == position.pool),
+            TollgateError::PoolAlreadyRegistered
+        );
+        require_gt!(
+            MAX_HONORARY_POSITIONS,
+            self.position_count as usize,
+            TollgateError::PositionRegistryFull
+        );
+
+        let index = self.position_count;
+        self.positions[index as usize] = position;
+        self.position_count += 1;
+
+        Ok(index)
+    }
+
+    /// Returns the registered positions, the primary one first.
+    pub fn positions(&self) -> &[HonoraryPosition] {
+        &self.positions[..self.position_count as usize]
+    }
+
+    /// Returns whether the pool and position are a registered position.
+    pub fn is_registered(&self, pool: &Pubkey, position: &Pubkey) -> bool {
+        self.positions()
+            .iter()
+            .any(|registered| registered.pool == *pool && registered.position == *position)
+    }
+
+    /// Returns whether the pool and position are the primary position, the
+    /// one passed to `claim_fees` and `claim_reward`.
+    pub fn is_primary(&self, pool: &Pubkey, position: &Pubkey) -> bool {
+        self.positions()
+            .first()
+            .is_some_and(|primary| primary.pool == *pool && primary.position == *position)
+    }
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{constants::TWENTY_FOUR_HOURS, error::TollgateError};
+use super::{CapExcess, InvestorCap};
+use crate::{
+    constants::{ACCOUNT_RESERVED_SPACE, TWENTY_FOUR_HOURS},
+    error::TollgateError,
+    math, utils,
+};
 
 #[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq)]
 pub enum DayState {
@@ -9,20 +14,105 @@ pub enum DayState {
     Closed, // Closed for the day, no more distributions
 }
 
-#[account]
-#[derive(Debug, InitSpace)]
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq)]
+pub enum CrankPhase {
+    Snapshot, // Pages accumulate the locked total of the day
+    Payout,   // Pages pay the investor pool out of the snapshot
+    Cap,      // Pages find the investors capped at the water level of the day
+}
+
+/// Allocation of an investor of the payout pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Allocation {
+    pub amount: u64,  // Amount allocated to the investor
+    pub capped: bool, // Whether the investor cap applied
+}
+
+/// The account traits are implemented below instead of by `#[account]`, so
+/// that accounts of an older layout are refused before they are decoded.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
 pub struct Progress {
-    pub vault: Pubkey,             // Associated vault
-    pub last_distribution_ts: i64, // Timestamp of last distribution
-    pub daily_spent: u64,          // Amount spent in current day
-    pub carry: u64,                // Carryover from prev day
-    pub cursor: u32,               // Pagination index in remaining_accounts
-    pub day_state: DayState,       // State of the current day
-    pub bump: u8,                  // PDA bump
+    pub version: u8,                            // Account layout version
+    pub vault: Pubkey,                          // Associated vault
+    pub last_distribution_ts: i64,              // Timestamp of last distribution
+    pub daily_spent: u64,                       // Amount spent in current day
+    pub carry: u64,                             // Carryover from prev day
+    pub cursor: u32,                            // Pagination index in remaining_accounts
+    pub day_state: DayState,                    // State of the current day
+    pub bump: u8,                               // PDA bump
+    pub phase: CrankPhase,                      // Pass of the current day
+    pub distributable: u64,                     // Distributable amount of the day
+    pub locked_total: u64,                      // Locked total of all investors
+    pub investor_pool: u64,                     // Investor share of the distributable
+    pub allocated_locked: u64,                  // Locked amount of the allocated investors
+    pub allocated: u64,                         // Investor pool allocated so far
+    pub reserve: u64,                           // Remainder held back for the next days
+    pub reserve_release: u64,                   // Reserve released into each new day
+    pub investor_cap: Option<u64>,              // Cap of a single investor for the day
+    pub cap_passes: u8,                         // Cap passes run in the day
+    pub capped_count: u32,                      // Investors capped at the water level
+    pub capped_locked: u64,                     // Locked amount of the capped investors
+    pub pass_capped_count: u32,                 // Investors capped so far in the cap pass
+    pub pass_capped_locked: u64,                // Locked amount capped so far in the cap pass
+    pub uncapped_allocated_locked: u64,         // Locked amount of the allocated uncapped investors
+    pub uncapped_allocated: u64,                // Water level pool allocated so far
+    pub last_stream: Pubkey,                    // Last stream of the pass, in increasing order
+    pub reserved: [u8; ACCOUNT_RESERVED_SPACE], // Spare space for future fields
+}
+
+impl Discriminator for Progress {
+    // sha256("account:Progress")[..8], as derived by `#[account]`
+    const DISCRIMINATOR: &'static [u8] = &[125, 4, 195, 102, 134, 179, 253, 6];
+}
+
+impl Owner for Progress {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl AccountSerialize for Progress {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        writer
+            .write_all(Self::DISCRIMINATOR)
+            .map_err(|_| ErrorCode::AccountDidNotSerialize)?;
+        AnchorSerialize::serialize(self, writer).map_err(|_| ErrorCode::AccountDidNotSerialize)?;
+
+        Ok(())
+    }
+}
+
+impl AccountDeserialize for Progress {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        utils::account::try_deserialize_versioned(buf, ProgressV0::SPACE, Self::VERSION)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let mut data = &buf[Self::DISCRIMINATOR.len()..];
+        AnchorDeserialize::deserialize(&mut data)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+/// Pre-versioning layout of the Progress account (version 0).
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct ProgressV0 {
+    pub vault: Pubkey,
+    pub last_distribution_ts: i64,
+    pub daily_spent: u64,
+    pub carry: u64,
+    pub cursor: u32,
+    pub day_state: DayState,
+    pub bump: u8,
+}
+
+impl ProgressV0 {
+    pub const SPACE: usize = Progress::DISCRIMINATOR.len() + Self::INIT_SPACE;
 }
 
 impl Progress {
     pub const SPACE: usize = Self::DISCRIMINATOR.len() + Self::INIT_SPACE;
+    pub const VERSION: u8 = 1;
 
     /// Initializes the Progress account.
     pub fn initialize(&mut self, vault: Pubkey, bump: u8) -> Result<()> {
@@ -32,13 +122,17 @@ impl Progress {
             TollgateError::ProgressAlreadyInitialized
         );
 
+        self.version = Self::VERSION;
         self.vault = vault;
         self.last_distribution_ts = 0;
         self.daily_spent = 0;
         self.carry = 0;
-        self.cursor = 0;
+        self.rewind();
         self.day_state = DayState::Closed;
         self.bump = bump;
+        self.reserve = 0;
+        self.reserve_release = 0;
+        self.reset_snapshot();
 
         Ok(())
     }
@@ -67,11 +161,212 @@ impl Progress {
         self.last_distribution_ts = now_ts;
         self.daily_spent = 0;
         self.carry = 0;
+        self.rewind();
+        self.reset_snapshot();
+
+        Ok(())
+    }
+
+    /// Adds the previous day remainder to the reserve, spreading the whole
+    /// reserve over the next `release_days` days.
+    pub fn hold_reserve(&mut self, remainder: u64, release_days: u16) -> Result<()> {
+        self.reserve = self
+            .reserve
+            .checked_add(remainder)
+            .ok_or(TollgateError::MathOverflow)?;
+        self.reserve_release = self.reserve.div_ceil(release_days as u64);
+
+        Ok(())
+    }
+
+    /// Releases the reserve share of a new day, returning the released amount.
+    pub fn release_reserve(&mut self) -> u64 {
+        let released = self.reserve.min(self.reserve_release);
+        self.reserve -= released;
+        if self.reserve == 0 {
+            self.reserve_release = 0;
+        }
+
+        released
+    }
+
+    /// Rewinds the cursor to the first page of a new pass.
+    fn rewind(&mut self) {
         self.cursor = 0;
+        self.last_stream = Pubkey::default();
+    }
+
+    /// Checks that the streams of a page continue the pass in strictly
+    /// increasing order, so that no investor is cranked twice in a pass.
+    pub fn record_page_streams(&mut self, streams: &[Pubkey]) -> Result<()> {
+        record_ordered_streams(&mut self.last_stream, streams)
+    }
+
+    /// Clears the day snapshot, starting over with the snapshot pass.
+    fn reset_snapshot(&mut self) {
+        self.phase = CrankPhase::Snapshot;
+        self.distributable = 0;
+        self.locked_total = 0;
+        self.investor_pool = 0;
+        self.allocated_locked = 0;
+        self.allocated = 0;
+        self.investor_cap = None;
+        self.cap_passes = 0;
+        self.capped_count = 0;
+        self.capped_locked = 0;
+        self.pass_capped_count = 0;
+        self.pass_capped_locked = 0;
+        self.uncapped_allocated_locked = 0;
+        self.uncapped_allocated = 0;
+    }
+
+    /// Completes the snapshot pass and rewinds the cursor for the next pass.
+    /// A cap whose excess is redistributed first runs the cap passes, unless
+    /// no investor can reach it.
+    pub fn complete_snapshot(
+        &mut self,
+        investor_pool: u64,
+        investor_cap: Option<&InvestorCap>,
+    ) -> Result<()> {
+        require!(
+            self.phase == CrankPhase::Snapshot,
+            TollgateError::InvalidCrankPhase
+        );
+
+        self.investor_pool = investor_pool;
+        self.investor_cap = investor_cap
+            .map(|investor_cap| investor_cap.resolve(investor_pool))
+            .transpose()?;
+        self.phase = match (investor_cap, self.investor_cap) {
+            (Some(investor_cap), Some(cap))
+                if investor_cap.excess == CapExcess::Redistribute && cap < investor_pool =>
+            {
+                CrankPhase::Cap
+            }
+            _ => CrankPhase::Payout,
+        };
+        self.rewind();
+
+        Ok(())
+    }
+
+    /// Returns the (pool, locked) water level of the day, left to the
+    /// investors below the cap.
+    fn water_level(&self) -> Result<(u64, u64)> {
+        let capped_pool = self
+            .investor_cap
+            .unwrap_or(0)
+            .checked_mul(self.capped_count as u64)
+            .ok_or(TollgateError::MathOverflow)?;
+        let level_pool = self
+            .investor_pool
+            .checked_sub(capped_pool)
+            .ok_or(TollgateError::DistributionInvariantViolated)?;
+        let level_locked = self
+            .locked_total
+            .checked_sub(self.capped_locked)
+            .ok_or(TollgateError::SnapshotMismatch)?;
+
+        Ok((level_pool, level_locked))
+    }
+
+    /// Checks whether an investor reaches the cap at the water level of the day.
+    pub fn reaches_cap(&self, locked: u64) -> Result<bool> {
+        let Some(cap) = self.investor_cap else {
+            return Ok(false);
+        };
+        let (level_pool, level_locked) = self.water_level()?;
+
+        Ok(math::reaches_cap(locked, cap, level_pool, level_locked))
+    }
+
+    /// Adds the investors of a cap pass page reaching the cap at the current
+    /// water level.
+    pub fn record_cap_page(&mut self, locked_per: &[u64]) -> Result<()> {
+        for locked in locked_per {
+            if self.reaches_cap(*locked)? {
+                self.pass_capped_count += 1;
+                self.pass_capped_locked = self
+                    .pass_capped_locked
+                    .checked_add(*locked)
+                    .ok_or(TollgateError::MathOverflow)?;
+            }
+        }
 
         Ok(())
     }
 
+    /// Completes a cap pass and rewinds the cursor. The water level is raised
+    /// over the investors capped by the pass, and the payout pass starts once
+    /// a pass caps no new investor. Returns whether the level is final.
+    pub fn complete_cap_pass(&mut self) -> Result<bool> {
+        require!(
+            self.phase == CrankPhase::Cap,
+            TollgateError::InvalidCrankPhase
+        );
+
+        // A higher level only caps more investors, so an equal count is the same set
+        let settled = self.pass_capped_count == self.capped_count;
+        self.capped_count = self.pass_capped_count;
+        self.capped_locked = self.pass_capped_locked;
+        self.pass_capped_count = 0;
+        self.pass_capped_locked = 0;
+        self.cap_passes = self.cap_passes.saturating_add(1);
+        self.rewind();
+        if settled {
+            self.phase = CrankPhase::Payout;
+        }
+
+        Ok(settled)
+    }
+
+    /// Allocates the investor pool to the next investor of the payout pass.
+    /// An investor reaching the cap is allocated the cap, the others share the
+    /// water level with a cumulative floor over their locked total, so that
+    /// the allocations sum up to the investor pool. Without cap passes the
+    /// level is the uncapped one, and the excess of the capped investors is
+    /// left unallocated. A lock grown since the snapshot, by a topup or a rate
+    /// change, is held to the locked amount left in the snapshot, so that the
+    /// allocations never exceed the investor pool.
+    pub fn allocate(&mut self, locked: u64) -> Result<Allocation> {
+        let locked = locked.min(self.locked_total.saturating_sub(self.allocated_locked));
+        let allocated_locked = self
+            .allocated_locked
+            .checked_add(locked)
+            .ok_or(TollgateError::MathOverflow)?;
+
+        let capped = self.reaches_cap(locked)?;
+        let amount = match self.investor_cap {
+            Some(cap) if capped => cap,
+            _ => {
+                let (level_pool, level_locked) = self.water_level()?;
+                let uncapped_allocated_locked = self
+                    .uncapped_allocated_locked
+                    .checked_add(locked)
+                    .ok_or(TollgateError::MathOverflow)?
+                    .min(level_locked);
+
+                let uncapped_allocated =
+                    math::pro_rata_share(level_pool, uncapped_allocated_locked, level_locked)?;
+                let amount = uncapped_allocated
+                    .checked_sub(self.uncapped_allocated)
+                    .ok_or(TollgateError::MathOverflow)?;
+
+                self.uncapped_allocated_locked = uncapped_allocated_locked;
+                self.uncapped_allocated = uncapped_allocated;
+                amount
+            }
+        };
+
+        self.allocated_locked = allocated_locked;
+        self.allocated = self
+            .allocated
+            .checked_add(amount)
+            .ok_or(TollgateError::MathOverflow)?;
+
+        Ok(Allocation { amount, capped })
+    }
+
     /// Switches active day from New to Same.
     pub fn continue_same_day(&mut self) -> Result<()> {
         require!(
@@ -95,4 +390,90 @@ impl Progress {
 
         Ok(())
     }
+
+    /// Upgrades the raw Progress account data to the current version.
+    /// Returns the version the account was migrated from.
+    pub fn migrate<'info>(
+        info: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<u8> {
+        let from_version = utils::account::read_version::<Self>(info, ProgressV0::SPACE)?;
+        require_gte!(
+            Self::VERSION,
+            from_version,
+            TollgateError::UnsupportedAccountVersion
+        );
+        if from_version == Self::VERSION {
+            return Ok(from_version);
+        }
+
+        let progress = {
+            let data = info.try_borrow_data()?;
+            let mut body = &data[Self::DISCRIMINATOR.len()..];
+            match from_version {
+                0 => Self::from(ProgressV0::deserialize(&mut body)?),
+                _ => return Err(TollgateError::UnsupportedAccountVersion.into()),
+            }
+        };
+
+        utils::account::realloc(info, Self::SPACE, payer, system_program)?;
+        let mut data = info.try_borrow_mut_data()?;
+        progress.try_serialize(&mut &mut data[..])?;
+
+        Ok(from_version)
+    }
+}
+
+/// Checks that `streams` follow `last_stream` in strictly increasing order,
+/// advancing it to the last of them.
+pub(crate) fn record_ordered_streams(last_stream: &mut Pubkey, streams: &[Pubkey]) -> Result<()> {
+    for stream in streams {
+        require_keys_neq!(
+            *stream,
+            *last_stream,
+            TollgateError::DuplicateInvestorStream
+        );
+        require!(
+            *stream > *last_stream,
+            TollgateError::InvestorStreamsOutOfOrder
+        );
+        *last_stream = *stream;
+    }
+
+    Ok(())
+}
+
+impl From<ProgressV0> for Progress {
+    fn from(v0: ProgressV0) -> Self {
+        Self {
+            version: Self::VERSION,
+            vault: v0.vault,
+            last_distribution_ts: v0.last_distribution_ts,
+            daily_spent: v0.daily_spent,
+            carry: v0.carry,
+            cursor: v0.cursor,
+            // An open day has no snapshot, it stays closed until the next day
+            day_state: DayState::Closed,
+            bump: v0.bump,
+            phase: CrankPhase::Snapshot,
+            distributable: 0,
+            locked_total: 0,
+            investor_pool: 0,
+            allocated_locked: 0,
+            allocated: 0,
+            reserve: 0,
+            reserve_release: 0,
+            investor_cap: None,
+            cap_passes: 0,
+            capped_count: 0,
+            capped_locked: 0,
+            pass_capped_count: 0,
+            pass_capped_locked: 0,
+            uncapped_allocated_locked: 0,
+            uncapped_allocated: 0,
+            last_stream: Pubkey::default(),
+            reserved: [0; ACCOUNT_RESERVED_SPACE],
+        }
+    }
 }
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+
+use super::progress::record_ordered_streams;
+use crate::{error::TollgateError, math};
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct RewardProgress {
+    pub version: u8,            // Account layout version
+    pub vault: Pubkey,          // Associated vault
+    pub reward_index: u8,       // DAMM v2 reward index of the pool
+    pub reward_mint: Pubkey,    // Mint of the reward token
+    pub day_ts: i64,            // Start of the day whose snapshot the round splits
+    pub cursor: u32,            // Pagination index of the round
+    pub closed: bool,           // Whether the round is closed
+    pub reward_pool: u64,       // Rewards split over the round
+    pub carry: u64,             // Rewards carried into the round
+    pub locked_total: u64,      // Locked total of the day snapshot
+    pub investor_pool: u64,     // Investor share of the reward pool
+    pub allocated_locked: u64,  // Locked amount of the allocated investors
+    pub allocated: u64,         // Investor pool allocated so far
+    pub investor_payouts: u64,  // Investor pool paid so far
+    pub total_claimed: u64,     // Lifetime rewards claimed
+    pub total_distributed: u64, // Lifetime rewards paid out
+    pub bump: u8,               // PDA bump
+    pub last_stream: Pubkey,    // Last stream of the round, in increasing order
+    pub reserved: [u8; 32],     // Spare space for future fields
+}
+
+impl RewardProgress {
+    pub const SPACE: usize = Self::DISCRIMINATOR.len() + Self::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Initializes the RewardProgress account with no round open.
+    pub fn initialize(&mut self, vault: Pubkey, reward_index: u8, reward_mint: Pubkey, bump: u8) {
+        self.version = Self::VERSION;
+        self.vault = vault;
+        self.reward_index = reward_index;
+        self.reward_mint = reward_mint;
+        self.closed = true;
+        self.bump = bump;
+    }
+
+    /// Opens the round of the day, splitting the reward pool over the day
+    /// snapshot of the quote distribution.
+    pub fn open_round(
+        &mut self,
+        day_ts: i64,
+        reward_pool: u64,
+        carry: u64,
+        locked_total: u64,
+        investor_pool: u64,
+    ) -> Result<()> {
+        require_neq!(self.day_ts, day_ts, TollgateError::RewardAlreadyClaimed);
+
+        // A round left open is dropped, its unpaid rewards are carried
+        if !self.closed {
+            self.total_distributed = self
+                .total_distributed
+                .checked_add(self.investor_payouts)
+                .ok_or(TollgateError::MathOverflow)?;
+        }
+
+        let reward_claimed = reward_pool
+            .checked_sub(carry)
+            .ok_or(TollgateError::MathOverflow)?;
+
+        self.day_ts = day_ts;
+        self.cursor = 0;
+        self.last_stream = Pubkey::default();
+        self.closed = false;
+        self.reward_pool = reward_pool;
+        self.carry = carry;
+        self.locked_total = locked_total;
+        self.investor_pool = investor_pool;
+        self.allocated_locked = 0;
+        self.allocated = 0;
+        self.investor_payouts = 0;
+        self.total_claimed = self
+            .total_claimed
+            .checked_add(reward_claimed)
+            .ok_or(TollgateError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Checks that the streams of a page continue the round in strictly
+    /// increasing order, so that no investor is paid twice in a round.
+    pub fn record_page_streams(&mut self, streams: &[Pubkey]) -> Result<()> {
+        record_ordered_streams(&mut self.last_stream, streams)
+    }
+
+    /// Allocates the investor pool to the next investor of the round, with a
+    /// cumulative floor over the locked total of the snapshot.
+    pub fn allocate(&mut self, locked: u64) -> Result<u64> {
+        let allocated_locked = self
+            .allocated_locked
+            .checked_add(locked)
+            .ok_or(TollgateError::MathOverflow)?;
+        require_gte!(
+            self.locked_total,
+            allocated_locked,
+            TollgateError::SnapshotMismatch
+        );
+
+        let allocated =
+            math::pro_rata_share(self.investor_pool, allocated_locked, self.locked_total)?;
+        let allocation = allocated
+            .checked_sub(self.allocated)
+            .ok_or(TollgateError::MathOverflow)?;
+
+        self.allocated_locked = allocated_locked;
+        self.allocated = allocated;
+
+        Ok(allocation)
+    }
+
+    /// Closes the round, returning the rewards carried into the next one.
+    pub fn close_round(&mut self, creator_payout: u64) -> Result<u64> {
+        require!(!self.closed, TollgateError::InvalidCrankPhase);
+
+        let distributed = self
+            .investor_payouts
+            .checked_add(creator_payout)
+            .ok_or(TollgateError::MathOverflow)?;
+        let carry = self
+            .reward_pool
+            .checked_sub(distributed)
+            .ok_or(TollgateError::DistributionInvariantViolated)?;
+
+        self.total_distributed = self
+            .total_distributed
+            .checked_add(distributed)
+            .ok_or(TollgateError::MathOverflow)?;
+        self.closed = true;
+
+        Ok(carry)
+    }
+}
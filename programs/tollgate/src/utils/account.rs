@@ -0,0 +1,143 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+use crate::error::TollgateError;
+
+/// Reads the layout version of a raw program account.
+/// Accounts created before versioning was introduced have no version byte and
+/// are identified by their legacy size, in which case version 0 is returned.
+pub fn read_version<T: Discriminator>(info: &AccountInfo, legacy_space: usize) -> Result<u8> {
+    version_of::<T>(&info.try_borrow_data()?, legacy_space)
+}
+
+/// Deserializes a versioned program account, refusing accounts that are not
+/// at `version` before their data is decoded with the current layout.
+pub fn try_deserialize_versioned<T: Discriminator + AnchorDeserialize>(
+    buf: &[u8],
+    legacy_space: usize,
+    version: u8,
+) -> Result<T> {
+    let account_version = version_of::<T>(buf, legacy_space)?;
+    require_gte!(account_version, version, TollgateError::StaleAccountVersion);
+    require_eq!(
+        account_version,
+        version,
+        TollgateError::UnsupportedAccountVersion
+    );
+
+    let mut data = &buf[T::DISCRIMINATOR.len()..];
+    T::deserialize(&mut data).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
+
+fn version_of<T: Discriminator>(data: &[u8], legacy_space: usize) -> Result<u8> {
+    // Check account discriminator
+    require!(
+        data.len() > T::DISCRIMINATOR.len() && data.starts_with(T::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    if data.len() == legacy_space {
+        return Ok(0);
+    }
+
+    // Versioned accounts never carry version 0
+    let version = data[T::DISCRIMINATOR.len()];
+    require_neq!(version, 0, TollgateError::UnsupportedAccountVersion);
+
+    Ok(version)
+}
+
+/// Resizes an account to `new_space` bytes, topping up rent from the payer.
+/// A shrunk account keeps its lamports.
+pub fn realloc<'info>(
+    info: &AccountInfo<'info>,
+    new_space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if info.data_len() == new_space {
+        return Ok(());
+    }
+
+    let rent_exempt = Rent::get()?.minimum_balance(new_space);
+    let lamports_needed = rent_exempt.saturating_sub(info.lamports());
+    if lamports_needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            lamports_needed,
+        )?;
+    }
+
+    info.realloc(new_space, true)?;
+
+    Ok(())
+}
+
+/// Creates a program owned PDA of `space` bytes, funded by the payer.
+/// Lamports already sent to the address are kept, so the creation cannot be
+/// blocked by pre-funding it.
+pub fn create_pda<'info>(
+    info: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+    let signer = &[signer_seeds];
+
+    if info.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+                signer,
+            ),
+            rent_exempt,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let lamports_needed = rent_exempt.saturating_sub(info.lamports());
+    if lamports_needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            lamports_needed,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: info.clone(),
+            },
+            signer,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: info.clone(),
+            },
+            signer,
+        ),
+        &crate::ID,
+    )
+}
@@ -0,0 +1,42 @@
+use anchor_lang::{
+    event::EVENT_IX_TAG_LE,
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    Event,
+};
+
+/// Emits events through a self-CPI signed by the Tollgate event authority,
+/// as `emit_cpi!` does, without holding the instruction context. Every
+/// instruction emits its events through it, helpers included.
+pub struct EventEmitter<'a, 'info> {
+    event_authority: &'a AccountInfo<'info>,
+    bump: u8,
+}
+
+impl<'a, 'info> EventEmitter<'a, 'info> {
+    pub fn new(event_authority: &'a AccountInfo<'info>, bump: u8) -> Self {
+        Self {
+            event_authority,
+            bump,
+        }
+    }
+
+    pub fn emit<E: Event>(&self, event: E) -> Result<()> {
+        let data: Vec<u8> = EVENT_IX_TAG_LE
+            .iter()
+            .copied()
+            .chain(event.data())
+            .collect();
+        let ix = Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![AccountMeta::new_readonly(*self.event_authority.key, true)],
+        );
+        invoke_signed(
+            &ix,
+            std::slice::from_ref(self.event_authority),
+            &[&[b"__event_authority", &[self.bump]]],
+        )?;
+        Ok(())
+    }
+}
@@ -1,2 +1,4 @@
+pub mod account;
+pub mod event;
 pub mod pool;
 pub mod token;
@@ -1,6 +1,17 @@
 use std::cell::Ref;
 
-pub fn is_valid_pool(
+pub fn is_valid_pool(pool: &Option<Ref<'_, damm_v2::accounts::Pool>>) -> bool {
+    // Check pool is initialized and enabled
+    is_initialized_pool(pool) && pool.as_ref().is_some_and(|pool| !is_disabled_pool(pool))
+}
+
+/// Returns whether the pool was disabled by the DAMM v2 admin, fees can no
+/// longer be claimed from its positions.
+pub fn is_disabled_pool(pool: &damm_v2::accounts::Pool) -> bool {
+    pool.pool_status != 0
+}
+
+pub fn is_initialized_pool(
     pool: &Option<Ref<'_, damm_v2::accounts::Pool>>,
     // base_mint: Pubkey,
     // quote_mint: Pubkey,
@@ -26,10 +37,6 @@ pub fn is_valid_pool(
     if pool.sqrt_min_price == 0 || pool.sqrt_max_price <= pool.sqrt_min_price {
         return false;
     }
-    // Check pool is enabled
-    if pool.pool_status != 0 {
-        return false;
-    }
     true
 }
 
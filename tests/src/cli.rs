@@ -0,0 +1,262 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use clap::Parser;
+use tollgate::{
+    constants::{ACCOUNT_RESERVED_SPACE, TWENTY_FOUR_HOURS},
+    state::{CapExcess, CapLimit, CarryPolicy, CrankPhase, DayState, Progress},
+};
+use tollgate_cli::{
+    args::{Cli, Command},
+    commands::plan_lines,
+    config::VaultConfig,
+};
+
+const DAY_TS: i64 = 1_700_000_000;
+
+/// Returns a vault config of `streams` with the `policy` table appended.
+fn vault_config(streams: &[Pubkey], policy: &str) -> String {
+    let streams = streams
+        .iter()
+        .map(|stream| format!("\"{}\"", stream))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"
+vault_keypair = "./vault.json"
+position_nft_mint_keypair = "./position-nft-mint.json"
+pool = "{}"
+pool_config = "{}"
+quote_mint = "{}"
+streams = [{}]
+
+[policy]
+init_investor_ata = true
+investor_fee_share_bps = 7000
+min_payout_lamports = 1000
+y0 = 100000
+{}"#,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        streams,
+        policy
+    )
+}
+
+/// Returns the progress of a day started at `DAY_TS`, in `phase` at `cursor`.
+fn open_day_progress(phase: CrankPhase, cursor: u32) -> Progress {
+    Progress {
+        version: Progress::VERSION,
+        vault: Pubkey::default(),
+        last_distribution_ts: DAY_TS,
+        daily_spent: 0,
+        carry: 0,
+        cursor,
+        day_state: DayState::Same,
+        bump: 0,
+        phase,
+        distributable: 0,
+        locked_total: 0,
+        investor_pool: 0,
+        allocated_locked: 0,
+        allocated: 0,
+        reserve: 0,
+        reserve_release: 0,
+        investor_cap: None,
+        cap_passes: 0,
+        capped_count: 0,
+        capped_locked: 0,
+        pass_capped_count: 0,
+        pass_capped_locked: 0,
+        uncapped_allocated_locked: 0,
+        uncapped_allocated: 0,
+        last_stream: Pubkey::default(),
+        reserved: [0; ACCOUNT_RESERVED_SPACE],
+    }
+}
+
+#[test]
+fn test_parse_crank_defaults() {
+    let cli = Cli::try_parse_from(["tollgate", "crank", "--config", "vault.toml"]).unwrap();
+
+    assert_eq!(cli.url, "localnet");
+    assert_eq!(cli.keypair, "~/.config/solana/id.json");
+    match cli.command {
+        Command::Crank {
+            config,
+            page_size,
+            with_init,
+            compute_unit_limit,
+        } => {
+            assert_eq!(config.to_str(), Some("vault.toml"));
+            assert_eq!(page_size, 10);
+            assert!(!with_init);
+            assert_eq!(compute_unit_limit, 1_400_000);
+        }
+        command => panic!("Unexpected command {:?}", command),
+    }
+}
+
+#[test]
+fn test_parse_global_args_after_subcommand() {
+    let cli = Cli::try_parse_from([
+        "tollgate",
+        "plan-pages",
+        "-c",
+        "vault.toml",
+        "--page-size",
+        "4",
+        "-u",
+        "devnet",
+        "-k",
+        "payer.json",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.url, "devnet");
+    assert_eq!(cli.keypair, "payer.json");
+    match cli.command {
+        Command::PlanPages { config, page_size } => {
+            assert_eq!(config.to_str(), Some("vault.toml"));
+            assert_eq!(page_size, 4);
+        }
+        command => panic!("Unexpected command {:?}", command),
+    }
+}
+
+#[test]
+fn test_parse_rejects_invalid_args() {
+    // Missing required argument
+    assert!(Cli::try_parse_from(["tollgate", "plan-pages"]).is_err());
+    // Not a number
+    assert!(Cli::try_parse_from([
+        "tollgate",
+        "crank",
+        "-c",
+        "vault.toml",
+        "--page-size",
+        "ten"
+    ])
+    .is_err());
+    // Unknown subcommand
+    assert!(Cli::try_parse_from(["tollgate", "unknown"]).is_err());
+}
+
+#[test]
+fn test_config_policy_defaults() {
+    let streams = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let config: VaultConfig = vault_config(&streams, "").parse().unwrap();
+
+    assert_eq!(config.streams().unwrap(), streams);
+    let params = config.initialize_params().unwrap();
+    assert_eq!(params.investor_count, 2);
+    assert_eq!(params.investor_fee_share_bps, 7000);
+    assert_eq!(params.daily_cap, None);
+    assert_eq!(params.creator, None);
+    assert_eq!(params.carry_policy, CarryPolicy::SweepToCreator);
+    assert_eq!(params.investor_cap, None);
+}
+
+#[test]
+fn test_config_policy_options() {
+    let creator = Pubkey::new_unique();
+    let config: VaultConfig = vault_config(
+        &[Pubkey::new_unique()],
+        &format!(
+            r#"daily_cap = 10000000
+creator = "{}"
+carry_policy = "reserve"
+release_days = 7
+investor_cap_bps = 2000
+cap_excess = "carry"
+"#,
+            creator
+        ),
+    )
+    .parse()
+    .unwrap();
+
+    let params = config.initialize_params().unwrap();
+    assert_eq!(params.daily_cap, Some(10_000_000));
+    assert_eq!(params.creator, Some(creator));
+    assert_eq!(
+        params.carry_policy,
+        CarryPolicy::Reserve { release_days: 7 }
+    );
+    let investor_cap = params.investor_cap.unwrap();
+    assert_eq!(investor_cap.limit, CapLimit::Bps { bps: 2000 });
+    assert_eq!(investor_cap.excess, CapExcess::Carry);
+}
+
+#[test]
+fn test_config_policy_errors() {
+    let streams = [Pubkey::new_unique()];
+    for policy in [
+        // The reserve without release_days
+        "carry_policy = \"reserve\"\n",
+        "carry_policy = \"burn\"\n",
+        "investor_cap = 10\ninvestor_cap_bps = 2000\n",
+        "investor_cap = 10\ncap_excess = \"burn\"\n",
+        "creator = \"not a pubkey\"\n",
+    ] {
+        let config: VaultConfig = vault_config(&streams, policy).parse().unwrap();
+        assert!(config.initialize_params().is_err(), "{}", policy);
+    }
+
+    // Missing policy field
+    assert!(vault_config(&streams, "")
+        .replace("y0 = 100000", "")
+        .parse::<VaultConfig>()
+        .is_err());
+}
+
+#[test]
+fn test_plan_lines_new_day() {
+    let streams = (0..5).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let progress = open_day_progress(CrankPhase::Payout, 5);
+
+    let lines = plan_lines(&progress, 5, &streams, DAY_TS + TWENTY_FOUR_HOURS, 2);
+
+    let mut expected = vec![
+        "3 page(s) of the Snapshot pass from cursor 0 of 5 investors".to_string(),
+        "The next pass repeats the pages from cursor 0".to_string(),
+    ];
+    for (idx, page) in streams.chunks(2).enumerate() {
+        expected.push(format!(
+            "Page {}: cursor={} investors={}",
+            idx,
+            idx * 2,
+            page.len()
+        ));
+        expected.extend(page.iter().map(|stream| format!("  {}", stream)));
+    }
+    assert_eq!(lines, expected);
+}
+
+#[test]
+fn test_plan_lines_resume_payout_pass() {
+    let streams = (0..5).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let progress = open_day_progress(CrankPhase::Payout, 3);
+
+    let lines = plan_lines(&progress, 5, &streams, DAY_TS + 60, 10);
+
+    assert_eq!(
+        lines,
+        vec![
+            "1 page(s) of the Payout pass from cursor 3 of 5 investors".to_string(),
+            "Page 0: cursor=3 investors=2".to_string(),
+            format!("  {}", streams[3]),
+            format!("  {}", streams[4]),
+        ]
+    );
+}
+
+#[test]
+fn test_plan_lines_closed_day() {
+    let mut progress = open_day_progress(CrankPhase::Payout, 5);
+    progress.day_state = DayState::Closed;
+
+    assert_eq!(
+        plan_lines(&progress, 5, &[], DAY_TS + 60, 10),
+        vec!["Day is closed, nothing to crank".to_string()]
+    );
+}
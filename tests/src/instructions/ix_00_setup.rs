@@ -152,6 +152,8 @@ pub fn add_investors(ctx: &mut TestContext, key: &str, num_rng: Range<u32>) -> V
         investors.push(investor);
     }
 
+    // Investors are cranked in increasing stream order
+    investors.sort_by_key(|investor| investor.stream.pubkey());
     investors
 }
 
@@ -1,62 +1,12 @@
-use anchor_client::{
-    anchor_lang::{InstructionData, ToAccountMetas},
-    solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer, system_program},
-};
-use anchor_spl::token_2022;
-use tollgate::{
-    accounts::AccountInitialize,
-    constants::{
-        damm_v2_constants, INVESTOR_FEE_POS_OWNER, POLICY_SEED, PROGRESS_SEED, VAULT_SEED,
-    },
-    error::TollgateError,
-};
+use anchor_client::solana_sdk::signer::Signer;
+use tollgate::{error::TollgateError, state::CarryPolicy};
+use tollgate_client::InitializeBuilder;
 
 use crate::utils::{
-    damm_v2::{get_pool_with_config_pda, get_position_nft_account_pda, get_position_pda},
-    find_program_address, find_program_event_authority,
+    damm_v2::get_pool_with_config_pda,
     svm::{demand_instruction_error, demand_logs_contain, get_ix_err, get_payer, TestContext},
 };
 
-#[allow(clippy::too_many_arguments)]
-pub fn get_initialize_ix_accs(
-    vault: Pubkey,
-    pool: Pubkey,
-    pool_cfg: Pubkey,
-    position_nft_mint: Pubkey,
-    position_nft_account: Pubkey,
-    position: Pubkey,
-    pool_authority: Pubkey,
-    quote_mint: Pubkey,
-    payer: Pubkey,
-    event_authority: Pubkey,
-) -> AccountInitialize {
-    AccountInitialize {
-        vault,
-        policy: find_program_address(&[POLICY_SEED, vault.as_ref()], None).0,
-        progress: find_program_address(&[PROGRESS_SEED, vault.as_ref()], None).0,
-        pool,
-        pool_cfg,
-        position_nft_mint,
-        position_nft_account,
-        position,
-        pool_authority,
-        owner: find_program_address(&[VAULT_SEED, vault.as_ref(), INVESTOR_FEE_POS_OWNER], None).0,
-        quote_mint,
-        payer,
-        event_authority,
-        amm_program: damm_v2::ID,
-        token_2022_program: token_2022::ID,
-        system_program: system_program::ID,
-    }
-}
-
-pub fn initialize_ix(
-    accounts: impl ToAccountMetas,
-    args: tollgate::instruction::Initialize,
-) -> Instruction {
-    Instruction::new_with_bytes(tollgate::ID, &args.data(), accounts.to_account_metas(None))
-}
-
 #[test]
 fn test_01_should_failed_base_fee_detected() {
     let mut ctx = TestContext::default();
@@ -69,35 +19,29 @@ fn test_01_should_failed_base_fee_detected() {
         .pos_mints
         .get("initialize")
         .expect("Position mint not found in context");
-    let (position_nft_account, _) = get_position_nft_account_pda(pos_mint.pubkey());
     let (pool, _) = get_pool_with_config_pda(token.pool_config, base_mint, quote_mint);
-    let (position, _) = get_position_pda(pos_mint.pubkey());
 
     let result = ctx.send_transaction(
-        &[initialize_ix(
-            get_initialize_ix_accs(
-                token.vault.pubkey(),
-                pool,
-                token.pool_config,
-                pos_mint.pubkey(),
-                position_nft_account,
-                position,
-                damm_v2_constants::pool_authority::ID,
-                quote_mint,
-                payer.pubkey(),
-                find_program_event_authority(&damm_v2::ID).0,
-            ),
-            tollgate::instruction::Initialize {
-                params: tollgate::instructions::InitializeParams {
-                    investor_count: (token.investors.len() as u32).max(1),
-                    init_investor_ata: false,
-                    investor_fee_share_bps: 10,
-                    min_payout_lamports: 1,
-                    daily_cap: None,
-                    y0: 100,
-                },
-            },
-        )],
+        &[InitializeBuilder::new(
+            token.vault.pubkey(),
+            pool,
+            token.pool_config,
+            pos_mint.pubkey(),
+            quote_mint,
+            payer.pubkey(),
+        )
+        .params(tollgate::instructions::InitializeParams {
+            investor_count: (token.investors.len() as u32).max(1),
+            init_investor_ata: false,
+            investor_fee_share_bps: 10,
+            min_payout_lamports: 1,
+            daily_cap: None,
+            y0: 100,
+            creator: None,
+            carry_policy: CarryPolicy::SweepToCreator,
+            investor_cap: None,
+        })
+        .instruction()],
         Some(&payer.pubkey()),
         &[
             &token.vault.insecure_clone(),
@@ -121,35 +65,29 @@ fn test_02_initialize() {
         .pos_mints
         .get("initialize")
         .expect("Position mint not found in context");
-    let (position_nft_account, _) = get_position_nft_account_pda(pos_mint.pubkey());
     let (pool, _) = get_pool_with_config_pda(token.pool_config, base_mint, quote_mint);
-    let (position, _) = get_position_pda(pos_mint.pubkey());
 
     let result = ctx.send_transaction(
-        &[initialize_ix(
-            get_initialize_ix_accs(
-                token.vault.pubkey(),
-                pool,
-                token.pool_config,
-                pos_mint.pubkey(),
-                position_nft_account,
-                position,
-                damm_v2_constants::pool_authority::ID,
-                quote_mint,
-                payer.pubkey(),
-                find_program_event_authority(&damm_v2::ID).0,
-            ),
-            tollgate::instruction::Initialize {
-                params: tollgate::instructions::InitializeParams {
-                    investor_count: token.investors.len() as u32,
-                    init_investor_ata: true,
-                    investor_fee_share_bps: 10,
-                    min_payout_lamports: 1,
-                    daily_cap: None,
-                    y0: 100,
-                },
-            },
-        )],
+        &[InitializeBuilder::new(
+            token.vault.pubkey(),
+            pool,
+            token.pool_config,
+            pos_mint.pubkey(),
+            quote_mint,
+            payer.pubkey(),
+        )
+        .params(tollgate::instructions::InitializeParams {
+            investor_count: token.investors.len() as u32,
+            init_investor_ata: true,
+            investor_fee_share_bps: 10,
+            min_payout_lamports: 1,
+            daily_cap: None,
+            y0: 100,
+            creator: None,
+            carry_policy: CarryPolicy::SweepToCreator,
+            investor_cap: None,
+        })
+        .instruction()],
         Some(&payer.pubkey()),
         &[
             &token.vault.insecure_clone(),
@@ -160,5 +98,9 @@ fn test_02_initialize() {
 
     demand_logs_contain("Initialize::Initializing policy account", &result);
     demand_logs_contain("Initialize::Initializing progress account", &result);
+    demand_logs_contain(
+        "Initialize::Initializing position registry account",
+        &result,
+    );
     demand_logs_contain("Initialize::Initialization completed successfully", &result);
 }
@@ -2,37 +2,25 @@ use anchor_client::{
     anchor_lang::{prelude::AccountMeta, InstructionData, ToAccountMetas},
     solana_sdk::{
         compute_budget::ComputeBudgetInstruction, instruction::Instruction,
-        native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer, system_program,
+        native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer,
     },
 };
 use anchor_spl::{
-    associated_token::{
-        get_associated_token_address, get_associated_token_address_with_program_id,
-        spl_associated_token_account::{
-            self, instruction::create_associated_token_account_idempotent,
-        },
-    },
+    associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent,
     token::spl_token,
 };
 use tollgate::{
-    accounts::AccountCrank,
-    constants::{
-        damm_v2_constants, INVESTOR_FEE_POS_OWNER, POLICY_SEED, PROGRESS_SEED, TWENTY_FOUR_HOURS,
-        VAULT_SEED,
-    },
+    constants::{damm_v2_constants, TWENTY_FOUR_HOURS},
     error::TollgateError,
     state::Policy,
 };
+use tollgate_client::{pda::get_policy_pda, CrankBuilder, InvestorStream};
 
 use crate::{
     instructions::ix_00_setup::add_investors,
     utils::{
-        damm_v2::{
-            get_pool_with_config_pda, get_position_nft_account_pda, get_position_pda,
-            get_token_vault_pda, set_damm_v2_position_fees,
-        },
-        find_program_address, find_program_event_authority, log_policy_account,
-        log_progress_account,
+        damm_v2::{get_pool_with_config_pda, set_damm_v2_position_fees},
+        log_policy_account, log_progress_account,
         svm::{
             demand_instruction_error, demand_instruction_one_of_errors, demand_logs_contain,
             get_ix_err, get_payer, TestContext, Token,
@@ -40,69 +28,6 @@ use crate::{
     },
 };
 
-#[allow(clippy::too_many_arguments)]
-pub fn get_crank_ix_accs(
-    ctx: &TestContext,
-    vault: Pubkey,
-    pool: Pubkey,
-    position_nft_account: Pubkey,
-    position: Pubkey,
-    pool_authority: Pubkey,
-    base_mint: Pubkey,
-    quote_mint: Pubkey,
-    payer: Pubkey,
-    event_authority: Pubkey,
-) -> AccountCrank {
-    let policy = find_program_address(&[POLICY_SEED, vault.as_ref()], None).0;
-    let owner = find_program_address(&[VAULT_SEED, vault.as_ref(), INVESTOR_FEE_POS_OWNER], None).0;
-
-    let policy_program_acc = ctx.get_program_account::<Policy>(&policy);
-    let base_mint_acc = ctx
-        .svm
-        .get_account(&base_mint)
-        .expect("Base mint account not found");
-    let quote_mint_acc = ctx
-        .svm
-        .get_account(&quote_mint)
-        .expect("Quote mint account not found");
-
-    AccountCrank {
-        policy,
-        progress: find_program_address(&[PROGRESS_SEED, vault.as_ref()], None).0,
-        pool,
-        position_nft_account,
-        position,
-        pool_authority,
-        owner,
-        base_account: get_associated_token_address_with_program_id(
-            &owner,
-            &base_mint,
-            &base_mint_acc.owner,
-        ),
-        quote_account: get_associated_token_address_with_program_id(
-            &owner,
-            &quote_mint,
-            &quote_mint_acc.owner,
-        ),
-        base_vault: get_token_vault_pda(base_mint, pool).0,
-        quote_vault: get_token_vault_pda(quote_mint, pool).0,
-        base_mint,
-        quote_mint,
-        base_program: base_mint_acc.owner,
-        quote_program: quote_mint_acc.owner,
-        creator_account: get_associated_token_address_with_program_id(
-            &policy_program_acc.creator,
-            &quote_mint,
-            &quote_mint_acc.owner,
-        ),
-        payer,
-        event_authority,
-        amm_program: damm_v2::ID,
-        associated_token_program: spl_associated_token_account::ID,
-        system_program: system_program::ID,
-    }
-}
-
 pub fn crank_ix(
     accounts: impl ToAccountMetas,
     args: tollgate::instruction::Crank,
@@ -125,58 +50,132 @@ pub fn crank_with_init_ix(
     Instruction::new_with_bytes(tollgate::ID, &args.data(), accounts)
 }
 
-pub fn compute_crank_ix_accs<'a>(
-    ctx: &'a TestContext,
-    key: &str,
-    pos_key: &str,
-    init_mode: bool,
-    payer: Pubkey,
-    start_page: u32,
-    end_page: u32,
-) -> (&'a Token, (impl ToAccountMetas, Vec<AccountMeta>)) {
-    let key = String::from(key);
-    let token = ctx.tokens.get(&key).expect("Token not found in context");
+/// Builds the crank instruction builder of a token position, without investors.
+pub fn crank_builder(ctx: &TestContext, key: &str, pos_key: &str, payer: Pubkey) -> CrankBuilder {
+    let token = ctx.tokens.get(key).expect("Token not found in context");
     let base_mint = token.base_mint.pubkey();
     let quote_mint = token.quote_mint;
     let pos_mint = token
         .pos_mints
         .get(pos_key)
         .expect("Position mint not found in context");
-    let (position_nft_account, _) = get_position_nft_account_pda(pos_mint.pubkey());
     let (pool, _) = get_pool_with_config_pda(token.pool_config, base_mint, quote_mint);
-    let (position, _) = get_position_pda(pos_mint.pubkey());
-    let pool_authority = damm_v2_constants::pool_authority::ID;
 
-    let mut remaining_accounts = vec![];
-    for idx in start_page..end_page {
-        let investor = token
-            .investors
-            .get(idx as usize)
-            .expect("Investor not found in token investors");
-        if init_mode {
-            remaining_accounts.push(AccountMeta::new_readonly(investor.key.pubkey(), false));
-        }
-        remaining_accounts.push(AccountMeta::new_readonly(investor.stream.pubkey(), false));
-        remaining_accounts.push(AccountMeta::new(
-            get_associated_token_address(&investor.key.pubkey(), &quote_mint),
-            false,
-        ));
-    }
+    let policy = get_policy_pda(token.vault.pubkey()).0;
+    let policy_program_acc = ctx.get_program_account::<Policy>(&policy);
+    let base_mint_acc = ctx
+        .svm
+        .get_account(&base_mint)
+        .expect("Base mint account not found");
+    let quote_mint_acc = ctx
+        .svm
+        .get_account(&quote_mint)
+        .expect("Quote mint account not found");
 
-    let accounts = get_crank_ix_accs(
-        ctx,
+    CrankBuilder::new(
         token.vault.pubkey(),
         pool,
-        position_nft_account,
-        position,
-        pool_authority,
+        pos_mint.pubkey(),
         base_mint,
         quote_mint,
+        policy_program_acc.creator,
         payer,
-        find_program_event_authority(&damm_v2::ID).0,
-    );
+    )
+    .base_program(base_mint_acc.owner)
+    .quote_program(quote_mint_acc.owner)
+}
+
+/// Builds the `claim_fees` instruction opening the day of a token position.
+pub fn claim_fees_ix(ctx: &TestContext, key: &str, pos_key: &str, payer: Pubkey) -> Instruction {
+    crank_builder(ctx, key, pos_key, payer).claim_fees()
+}
 
-    (token, (accounts, remaining_accounts))
+/// Opens the day of a token position with `claim_fees`.
+pub fn open_day(ctx: &mut TestContext, key: &str, pos_key: &str) {
+    let payer = get_payer();
+    let instruction = claim_fees_ix(ctx, key, pos_key, payer.pubkey());
+    ctx.send_transaction(&[instruction], Some(&payer.pubkey()), &[payer])
+        .expect("Claim fees should succeed");
+}
+
+pub fn compute_crank_ix_accs<'a>(
+    ctx: &'a TestContext,
+    key: &str,
+    pos_key: &str,
+    init_mode: bool,
+    payer: Pubkey,
+    start_page: u32,
+    end_page: u32,
+) -> (&'a Token, (impl ToAccountMetas, Vec<AccountMeta>)) {
+    let token = ctx.tokens.get(key).expect("Token not found in context");
+    let investors = (start_page..end_page)
+        .map(|idx| {
+            let investor = token
+                .investors
+                .get(idx as usize)
+                .expect("Investor not found in token investors");
+            InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey())
+        })
+        .collect();
+
+    let builder = crank_builder(ctx, key, pos_key, payer).investors(investors);
+
+    (
+        token,
+        (builder.accounts(), builder.remaining_accounts(init_mode)),
+    )
+}
+
+/// Runs the payout pass of the day in pages of 10, once the snapshot pass completed.
+fn crank_payout_pass(ctx: &mut TestContext, key: &str, pos_key: &str, compute_unit_limit: u32) {
+    let payer = get_payer();
+    let investor_count = ctx
+        .tokens
+        .get(key)
+        .expect("Token not found")
+        .investors
+        .len() as u32;
+
+    for start_page in (0..investor_count).step_by(10) {
+        let end_page = (start_page + 10).min(investor_count);
+        let (_, accs) = compute_crank_ix_accs(
+            ctx,
+            key,
+            pos_key,
+            true,
+            payer.pubkey(),
+            start_page,
+            end_page,
+        );
+
+        let result = ctx.send_transaction(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit),
+                crank_with_init_ix(
+                    accs.0,
+                    tollgate::instruction::CrankWithInit {
+                        params: tollgate::instructions::CrankParams { cursor: start_page },
+                    },
+                    accs.1,
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[payer],
+        );
+
+        demand_logs_contain(
+            format!(
+                "Crank::Processed page {} to {}, payouts: ",
+                start_page, end_page
+            )
+            .as_str(),
+            &result,
+        );
+        if end_page == investor_count {
+            demand_logs_contain("Crank::Day closed, total distributed: ", &result);
+        }
+        result.expect("Crank payout pass should succeed");
+    }
 }
 
 #[test]
@@ -185,7 +184,7 @@ fn test_01_crank_below_min_payout() {
     let key = "tollgate";
     let pos_key = "initialize";
     let payer = get_payer();
-    let (token, accs) = compute_crank_ix_accs(&ctx, key, pos_key, false, payer.pubkey(), 0, 10);
+    let token = ctx.tokens.get(key).expect("Token not found in context");
     let base_mint = token.base_mint.pubkey();
     let quote_mint = token.quote_mint;
     let pool_authority = damm_v2_constants::pool_authority::ID;
@@ -204,24 +203,18 @@ fn test_01_crank_below_min_payout() {
                 &quote_mint,
                 &spl_token::ID,
             ),
-            crank_ix(
-                accs.0,
-                tollgate::instruction::Crank {
-                    params: tollgate::instructions::CrankParams { cursor: 0 },
-                },
-                accs.1,
-            ),
+            claim_fees_ix(&ctx, key, pos_key, payer.pubkey()),
         ],
         Some(&payer.pubkey()),
         &[payer],
     );
 
-    demand_logs_contain("Crank::Processing day state: New", &result);
-    demand_logs_contain("Crank::Distributable amount after carry: 0", &result);
+    demand_logs_contain("ClaimFees::Distributable amount after carry: 0", &result);
     demand_logs_contain(
-        "Crank::Distributable below min payout, carrying over: 0",
+        "ClaimFees::Distributable below min payout, carrying over: 0",
         &result,
     );
+    demand_logs_contain("ClaimFees::Completed successfully", &result);
 }
 
 #[test]
@@ -230,18 +223,11 @@ fn test_02_should_failed_base_fee_detected() {
     let key = "tollgate";
     let pos_key = "initialize";
     let payer = get_payer();
-    let (_, accs) = compute_crank_ix_accs(&ctx, key, pos_key, false, payer.pubkey(), 0, 10);
 
     set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(1), None);
 
     let result = ctx.send_transaction(
-        &[crank_ix(
-            accs.0,
-            tollgate::instruction::Crank {
-                params: tollgate::instructions::CrankParams { cursor: 0 },
-            },
-            accs.1,
-        )],
+        &[claim_fees_ix(&ctx, key, pos_key, payer.pubkey())],
         Some(&payer.pubkey()),
         &[payer],
     );
@@ -250,7 +236,7 @@ fn test_02_should_failed_base_fee_detected() {
 }
 
 #[test]
-fn test_03_crank_claim_quote_fees() {
+fn test_03_claim_fees_quote_fees() {
     let mut ctx = TestContext::default();
     let key = "tollgate";
     let pos_key = "initialize";
@@ -259,38 +245,27 @@ fn test_03_crank_claim_quote_fees() {
 
     ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
     set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(quote_fee));
-    let (_, accs) = compute_crank_ix_accs(&ctx, key, pos_key, false, payer.pubkey(), 0, 0);
 
     let result = ctx.send_transaction(
-        &[crank_ix(
-            accs.0,
-            tollgate::instruction::Crank {
-                params: tollgate::instructions::CrankParams { cursor: 0 },
-            },
-            accs.1,
-        )],
+        &[claim_fees_ix(&ctx, key, pos_key, payer.pubkey())],
         Some(&payer.pubkey()),
         &[payer],
     );
 
-    demand_logs_contain(
-        "Crank::Starting crank with cursor=0 and page_size=0",
-        &result,
-    );
-    demand_logs_contain("Crank::Processing day state: New", &result);
+    demand_logs_contain("ClaimFees::Claiming fees of 1 positions", &result);
     demand_logs_contain(
         format!(
-            "Crank::Claiming DAMM v2 position fee: quote_fee={}",
+            "ClaimFees::Claiming DAMM v2 position fee: quote_fee={}",
             quote_fee
         )
         .as_str(),
         &result,
     );
     demand_logs_contain(
-        format!("Crank::Distributable amount after carry: {}", quote_fee).as_str(),
+        format!("ClaimFees::Distributable amount after carry: {}", quote_fee).as_str(),
         &result,
     );
-    demand_logs_contain("Crank::No investors to process, exiting", &result);
+    demand_logs_contain("ClaimFees::Completed successfully", &result);
 
     log_policy_account(&ctx, key);
     log_progress_account(&ctx, key);
@@ -352,7 +327,7 @@ fn test_05_crank_page_0_to_10() {
         .as_str(),
         &result,
     );
-    demand_logs_contain("Crank::Processed page 0 to 10", &result);
+    demand_logs_contain("Crank::Snapshot page 0 to 10, locked: ", &result);
     demand_logs_contain("Crank::Completed successfully", &result);
 
     log_progress_account(&ctx, key);
@@ -420,36 +395,60 @@ fn test_07_crank_page_10_to_20() {
 }
 
 #[test]
-fn test_08_crank_day_two_page_1_to_5_invalid_cursor() {
+fn test_08_crank_day_two_opened_by_claim_fees() {
     let mut ctx = TestContext::default();
     let key = "tollgate";
     let pos_key = "initialize";
     let payer = get_payer();
+    let quote_fee = LAMPORTS_PER_SOL / 2;
 
     ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
     let (_, accs) = compute_crank_ix_accs(&ctx, key, pos_key, true, payer.pubkey(), 1, 6);
+    let crank_instruction = crank_with_init_ix(
+        accs.0,
+        tollgate::instruction::CrankWithInit {
+            params: tollgate::instructions::CrankParams { cursor: 1 },
+        },
+        accs.1,
+    );
 
+    // A page cannot open the day
     let result = ctx.send_transaction(
-        &[crank_with_init_ix(
-            accs.0,
-            tollgate::instruction::CrankWithInit {
-                params: tollgate::instructions::CrankParams { cursor: 1 },
-            },
-            accs.1,
-        )],
+        std::slice::from_ref(&crank_instruction),
         Some(&payer.pubkey()),
         &[payer],
     );
+    demand_instruction_error(get_ix_err(TollgateError::DayNotOpened), &result);
 
+    let result = ctx.send_transaction(
+        &[claim_fees_ix(&ctx, key, pos_key, payer.pubkey())],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
     demand_logs_contain(
-        "Crank::Starting crank with cursor=1 and page_size=5",
+        "ClaimFees::Transferred previous day remainder to creator: ",
+        &result,
+    );
+    demand_logs_contain(
+        format!(
+            "ClaimFees::Claiming DAMM v2 position fee: quote_fee={}",
+            quote_fee
+        )
+        .as_str(),
         &result,
     );
     demand_logs_contain(
-        "Crank::Transferred previous day remainder to creator: ",
+        format!("ClaimFees::Distributable amount after carry: {}", quote_fee).as_str(),
+        &result,
+    );
+
+    // The pages of the day start at cursor 0
+    ctx.svm.expire_blockhash();
+    let result = ctx.send_transaction(&[crank_instruction], Some(&payer.pubkey()), &[payer]);
+    demand_logs_contain(
+        "Crank::Starting crank with cursor=1 and page_size=5",
         &result,
     );
-    demand_logs_contain("Crank::Processing day state: New", &result);
     demand_instruction_error(get_ix_err(TollgateError::PaginationCursorTooLarge), &result);
 
     log_progress_account(&ctx, key);
@@ -484,24 +483,12 @@ fn test_09_crank_day_two_page_0_to_8() {
         "Crank::Starting crank with cursor=0 and page_size=8",
         &result,
     );
-    demand_logs_contain(
-        "Crank::Transferred previous day remainder to creator: ",
-        &result,
-    );
-    demand_logs_contain("Crank::Processing day state: New", &result);
-    demand_logs_contain(
-        format!(
-            "Crank::Claiming DAMM v2 position fee: quote_fee={}",
-            quote_fee
-        )
-        .as_str(),
-        &result,
-    );
+    demand_logs_contain("Crank::Processing day state: Same", &result);
     demand_logs_contain(
         format!("Crank::Distributable amount after carry: {}", quote_fee).as_str(),
         &result,
     );
-    demand_logs_contain("Crank::Processed page 0 to 8, payouts: ", &result);
+    demand_logs_contain("Crank::Snapshot page 0 to 8, locked: ", &result);
     demand_logs_contain("Crank::Completed successfully", &result);
 
     log_progress_account(&ctx, key);
@@ -564,9 +551,14 @@ fn test_10_crank_day_two_full() {
         );
         demand_logs_contain("Crank::Processing day state: Same", &result);
         demand_logs_contain("Crank::Distributable amount after carry: ", &result);
+        if end_page == token.investors.len() {
+            demand_logs_contain("Crank::Snapshot completed, locked total: ", &result);
+        }
         result.expect("Crank day two full should succeed");
     }
 
+    crank_payout_pass(&mut ctx, key, pos_key, 700_000);
+
     log_progress_account(&ctx, key);
 }
 
@@ -629,6 +621,7 @@ fn test_12_crank_day_three_full() {
 
     ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
     set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(quote_fee));
+    open_day(&mut ctx, key, pos_key);
 
     let tokens = ctx.tokens.clone();
     let token = tokens.get(key).expect("Token not found in context");
@@ -678,15 +671,13 @@ fn test_12_crank_day_three_full() {
             .as_str(),
             &result,
         );
-        if idx == 0 {
-            demand_logs_contain("Crank::Processing day state: New", &result);
-        } else {
-            demand_logs_contain("Crank::Processing day state: Same", &result);
-        }
+        demand_logs_contain("Crank::Processing day state: Same", &result);
         demand_logs_contain("Crank::Distributable amount after carry: ", &result);
         result.expect("Crank day three full should succeed");
     }
 
+    crank_payout_pass(&mut ctx, key, pos_key, 900_000);
+
     log_progress_account(&ctx, key);
 }
 
@@ -700,6 +691,7 @@ fn test_13_crank_day_four_full_with_more_investor() {
 
     ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
     set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(quote_fee));
+    open_day(&mut ctx, key, pos_key);
 
     let tokens = ctx.tokens.clone();
     let token = tokens.get(key).expect("Token not found in context");
@@ -769,11 +761,7 @@ fn test_13_crank_day_four_full_with_more_investor() {
                 .as_str(),
                 &result,
             );
-            if idx == 0 {
-                demand_logs_contain("Crank::Processing day state: New", &result);
-            } else {
-                demand_logs_contain("Crank::Processing day state: Same", &result);
-            }
+            demand_logs_contain("Crank::Processing day state: Same", &result);
             demand_logs_contain("Crank::Distributable amount after carry: ", &result);
             result.expect("Crank day three full should succeed");
         }
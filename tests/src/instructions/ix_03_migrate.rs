@@ -0,0 +1,272 @@
+use anchor_client::{
+    anchor_lang::{AnchorSerialize, Discriminator, InstructionData, ToAccountMetas},
+    solana_sdk::{
+        account::Account, instruction::Instruction, pubkey::Pubkey, signer::Signer, system_program,
+    },
+};
+use tollgate::{
+    accounts::AccountMigrate,
+    constants::{ACCOUNT_RESERVED_SPACE, POLICY_SEED, PROGRESS_SEED},
+    error::TollgateError,
+    state::{CarryPolicy, CrankPhase, DayState, Policy, PolicyV0, Progress, ProgressV0},
+};
+
+use crate::{
+    instructions::ix_02_crank::{compute_crank_ix_accs, crank_ix},
+    utils::{
+        find_program_address, find_program_event_authority,
+        svm::{demand_instruction_error, demand_logs_contain, get_ix_err, get_payer, TestContext},
+    },
+};
+
+pub fn get_migrate_ix_accs(vault: Pubkey, payer: Pubkey) -> AccountMigrate {
+    AccountMigrate {
+        vault,
+        policy: find_program_address(&[POLICY_SEED, vault.as_ref()], None).0,
+        progress: find_program_address(&[PROGRESS_SEED, vault.as_ref()], None).0,
+        payer,
+        system_program: system_program::ID,
+        event_authority: find_program_event_authority(&tollgate::ID).0,
+        program: tollgate::ID,
+    }
+}
+
+pub fn migrate_ix(
+    accounts: impl ToAccountMetas,
+    args: tollgate::instruction::Migrate,
+) -> Instruction {
+    Instruction::new_with_bytes(tollgate::ID, &args.data(), accounts.to_account_metas(None))
+}
+
+/// Rewrites the policy and progress accounts in their pre-versioning layout.
+fn downgrade_to_legacy_accounts(ctx: &mut TestContext, vault: Pubkey) {
+    let policy = find_program_address(&[POLICY_SEED, vault.as_ref()], None).0;
+    let progress = find_program_address(&[PROGRESS_SEED, vault.as_ref()], None).0;
+
+    let policy_acc = ctx.get_program_account::<Policy>(&policy);
+    let legacy_policy = PolicyV0 {
+        vault: policy_acc.vault,
+        creator: policy_acc.creator,
+        quote_mint: policy_acc.quote_mint,
+        investor_count: policy_acc.investor_count,
+        init_investor_ata: policy_acc.init_investor_ata,
+        investor_fee_share_bps: policy_acc.investor_fee_share_bps,
+        min_payout_lamports: policy_acc.min_payout_lamports,
+        daily_cap: policy_acc.daily_cap,
+        y0: policy_acc.y0,
+        is_initialized: policy_acc.is_initialized,
+        owner_bump: policy_acc.owner_bump,
+        bump: policy_acc.bump,
+    };
+    let mut policy_data = Policy::DISCRIMINATOR.to_vec();
+    legacy_policy.serialize(&mut policy_data).unwrap();
+    policy_data.resize(PolicyV0::SPACE, 0);
+
+    let progress_acc = ctx.get_program_account::<Progress>(&progress);
+    let legacy_progress = ProgressV0 {
+        vault: progress_acc.vault,
+        last_distribution_ts: progress_acc.last_distribution_ts,
+        daily_spent: progress_acc.daily_spent,
+        carry: progress_acc.carry,
+        cursor: progress_acc.cursor,
+        day_state: progress_acc.day_state,
+        bump: progress_acc.bump,
+    };
+    let mut progress_data = Progress::DISCRIMINATOR.to_vec();
+    legacy_progress.serialize(&mut progress_data).unwrap();
+    progress_data.resize(ProgressV0::SPACE, 0);
+
+    for (address, data) in [(policy, policy_data), (progress, progress_data)] {
+        let acc = ctx.svm.get_account(&address).unwrap();
+        ctx.svm
+            .set_account(
+                address,
+                Account {
+                    lamports: acc.lamports,
+                    data,
+                    owner: acc.owner,
+                    executable: acc.executable,
+                    rent_epoch: acc.rent_epoch,
+                },
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_01_should_failed_crank_legacy_accounts() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+
+    let (_, accs) = compute_crank_ix_accs(&ctx, key, pos_key, false, payer.pubkey(), 0, 0);
+    let accs = (accs.0.to_account_metas(None), accs.1);
+    downgrade_to_legacy_accounts(&mut ctx, vault);
+
+    let result = ctx.send_transaction(
+        &[crank_ix(
+            accs.0,
+            tollgate::instruction::Crank {
+                params: tollgate::instructions::CrankParams { cursor: 0 },
+            },
+            accs.1,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+
+    demand_instruction_error(get_ix_err(TollgateError::StaleAccountVersion), &result);
+}
+
+#[test]
+fn test_02_migrate_legacy_accounts() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+
+    let result = ctx.send_transaction(
+        &[migrate_ix(
+            get_migrate_ix_accs(vault, payer.pubkey()),
+            tollgate::instruction::Migrate {},
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+
+    demand_logs_contain(
+        format!(
+            "Migrate::Policy migrated from version 0 to {}",
+            Policy::VERSION
+        )
+        .as_str(),
+        &result,
+    );
+    demand_logs_contain(
+        format!(
+            "Migrate::Progress migrated from version 0 to {}",
+            Progress::VERSION
+        )
+        .as_str(),
+        &result,
+    );
+    demand_logs_contain("Migrate::Migration completed successfully", &result);
+
+    let policy = find_program_address(&[POLICY_SEED, vault.as_ref()], None).0;
+    let progress = find_program_address(&[PROGRESS_SEED, vault.as_ref()], None).0;
+    assert_eq!(
+        ctx.svm.get_account(&policy).unwrap().data.len(),
+        Policy::SPACE
+    );
+    assert_eq!(
+        ctx.svm.get_account(&progress).unwrap().data.len(),
+        Progress::SPACE
+    );
+    assert_eq!(
+        ctx.get_program_account::<Policy>(&policy).version,
+        Policy::VERSION
+    );
+    assert_eq!(
+        ctx.get_program_account::<Progress>(&progress).version,
+        Progress::VERSION
+    );
+}
+
+#[test]
+fn test_03_migrate_idempotent() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+
+    let result = ctx.send_transaction(
+        &[migrate_ix(
+            get_migrate_ix_accs(vault, payer.pubkey()),
+            tollgate::instruction::Migrate {},
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+
+    demand_logs_contain(
+        format!(
+            "Migrate::Policy migrated from version {} to {}",
+            Policy::VERSION,
+            Policy::VERSION
+        )
+        .as_str(),
+        &result,
+    );
+    demand_logs_contain("Migrate::Migration completed successfully", &result);
+}
+
+#[test]
+fn test_04_migrate_legacy_accounts_defaults() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+    let policy = find_program_address(&[POLICY_SEED, vault.as_ref()], None).0;
+    let progress = find_program_address(&[PROGRESS_SEED, vault.as_ref()], None).0;
+
+    // Rewrite the progress account in its legacy layout, with an open day
+    let progress_acc = ctx.get_program_account::<Progress>(&progress);
+    let legacy_progress = ProgressV0 {
+        vault: progress_acc.vault,
+        last_distribution_ts: progress_acc.last_distribution_ts,
+        daily_spent: progress_acc.daily_spent,
+        carry: progress_acc.carry,
+        cursor: 10,
+        day_state: DayState::Same,
+        bump: progress_acc.bump,
+    };
+    let mut progress_data = Progress::DISCRIMINATOR.to_vec();
+    legacy_progress.serialize(&mut progress_data).unwrap();
+    progress_data.resize(ProgressV0::SPACE, 0);
+    let acc = ctx.svm.get_account(&progress).unwrap();
+    ctx.svm
+        .set_account(
+            progress,
+            Account {
+                data: progress_data,
+                ..acc
+            },
+        )
+        .unwrap();
+
+    let result = ctx.send_transaction(
+        &[migrate_ix(
+            get_migrate_ix_accs(vault, payer.pubkey()),
+            tollgate::instruction::Migrate {},
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+
+    demand_logs_contain(
+        format!(
+            "Migrate::Progress migrated from version 0 to {}",
+            Progress::VERSION
+        )
+        .as_str(),
+        &result,
+    );
+
+    let migrated = ctx.get_program_account::<Progress>(&progress);
+    assert_eq!(migrated.version, Progress::VERSION);
+    assert_eq!(migrated.cursor, 10);
+    // The open day has no snapshot and is closed until the next one
+    assert_eq!(migrated.day_state, DayState::Closed);
+    assert_eq!(migrated.phase, CrankPhase::Snapshot);
+    assert_eq!(migrated.last_stream, Pubkey::default());
+    assert_eq!(migrated.reserved, [0; ACCOUNT_RESERVED_SPACE]);
+
+    let migrated = ctx.get_program_account::<Policy>(&policy);
+    assert_eq!(migrated.version, Policy::VERSION);
+    // Policies created before the carry policy sweep the remainder, uncapped
+    assert_eq!(migrated.carry_policy, CarryPolicy::SweepToCreator);
+    assert_eq!(migrated.investor_cap, None);
+    assert_eq!(migrated.reserved, [0; ACCOUNT_RESERVED_SPACE]);
+}
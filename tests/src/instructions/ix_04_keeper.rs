@@ -0,0 +1,241 @@
+use std::{fs, path::PathBuf};
+
+use anchor_client::{
+    anchor_lang::ToAccountMetas,
+    solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer},
+};
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    state::{DayState, Policy, Progress},
+};
+use tollgate_client::pda::{get_policy_pda, get_progress_pda};
+use tollgate_keeper::{Keeper, KeeperConfig, KeeperState, PassPhase, TickOutcome};
+
+use crate::{
+    instructions::ix_02_crank::{claim_fees_ix, compute_crank_ix_accs, crank_ix},
+    utils::{
+        damm_v2::{get_pool_with_config_pda, set_damm_v2_position_fees},
+        keeper::SvmKeeperRpc,
+        svm::{demand_logs_contain, get_payer, TestContext},
+    },
+};
+
+fn keeper_config(ctx: &TestContext, key: &str, pos_key: &str) -> KeeperConfig {
+    let token = ctx.tokens.get(key).expect("Token not found in context");
+    let base_mint = token.base_mint.pubkey();
+    let pos_mint = token
+        .pos_mints
+        .get(pos_key)
+        .expect("Position mint not found in context");
+    let (pool, _) = get_pool_with_config_pda(token.pool_config, base_mint, token.quote_mint);
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(token.vault.pubkey()).0);
+
+    KeeperConfig::new(
+        token.vault.pubkey(),
+        pool,
+        pos_mint.pubkey(),
+        token.quote_mint,
+        token.investors[..policy.investor_count as usize]
+            .iter()
+            .map(|investor| investor.stream.pubkey())
+            .collect(),
+    )
+}
+
+fn keeper_state_path(vault: &Pubkey) -> PathBuf {
+    std::env::temp_dir().join(format!("tollgate-keeper-{}.json", vault))
+}
+
+fn start_day(ctx: &mut TestContext, key: &str, pos_key: &str) {
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+}
+
+/// Opens the day then cranks the snapshot pages up to `end_page`.
+fn crank_pages(ctx: &mut TestContext, key: &str, pos_key: &str, end_page: u32) {
+    let payer = get_payer();
+    let result = ctx.send_transaction(
+        &[claim_fees_ix(ctx, key, pos_key, payer.pubkey())],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+    demand_logs_contain("ClaimFees::Completed successfully", &result);
+    for start_page in (0..end_page).step_by(10) {
+        let (_, accs) = compute_crank_ix_accs(
+            ctx,
+            key,
+            pos_key,
+            false,
+            payer.pubkey(),
+            start_page,
+            start_page + 10,
+        );
+        let accs = (accs.0.to_account_metas(None), accs.1);
+        let result = ctx.send_transaction(
+            &[crank_ix(
+                accs.0,
+                tollgate::instruction::Crank {
+                    params: tollgate::instructions::CrankParams { cursor: start_page },
+                },
+                accs.1,
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+        );
+        demand_logs_contain("Crank::Completed successfully", &result);
+    }
+}
+
+#[test]
+fn test_01_keeper_closes_day() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    start_day(&mut ctx, key, pos_key);
+
+    let config = keeper_config(&ctx, key, pos_key);
+    let vault = config.vault;
+    let investor_count = config.streams.len() as u32;
+    let state_path = keeper_state_path(&vault);
+    let _ = fs::remove_file(&state_path);
+
+    let mut keeper = Keeper::new(
+        SvmKeeperRpc { ctx: &mut ctx },
+        payer.insecure_clone(),
+        config,
+    )
+    .state_path(&state_path)
+    .unwrap();
+
+    let outcome = keeper.tick().expect("Keeper pass should succeed");
+    let progress = keeper
+        .rpc()
+        .ctx
+        .get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.day_state, DayState::Closed);
+    assert_eq!(
+        outcome,
+        TickOutcome::DayClosed {
+            day_ts: progress.last_distribution_ts,
+            // Fee claim, snapshot pass then payout pass
+            pages: 1 + 2 * investor_count.div_ceil(10),
+        }
+    );
+
+    // The day is closed until the next one starts
+    assert_eq!(
+        keeper.tick().expect("Keeper pass should succeed"),
+        TickOutcome::Idle {
+            next_day_ts: progress.last_distribution_ts + TWENTY_FOUR_HOURS,
+        }
+    );
+
+    let state = KeeperState::load(&state_path).unwrap();
+    assert_eq!(&state, keeper.state());
+    assert_eq!(state.day_ts, progress.last_distribution_ts);
+    assert_eq!(state.cursor, investor_count);
+    assert_eq!(state.pass(), (PassPhase::Payout, 0));
+    assert_eq!(state.days_closed, 1);
+    assert!(state.last_signature.is_some());
+}
+
+#[test]
+fn test_02_keeper_resyncs_cursor_ahead_of_progress() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    start_day(&mut ctx, key, pos_key);
+    crank_pages(&mut ctx, key, pos_key, 10);
+
+    let config = keeper_config(&ctx, key, pos_key);
+    let vault = config.vault;
+    let investor_count = config.streams.len() as u32;
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.cursor, 10);
+
+    // The persisted cursor claims a page the program never processed
+    let state_path = keeper_state_path(&vault);
+    KeeperState {
+        day_ts: progress.last_distribution_ts,
+        cursor: 20,
+        phase: PassPhase::Snapshot,
+        cap_passes: 0,
+        days_closed: 1,
+        last_signature: None,
+    }
+    .save(&state_path)
+    .unwrap();
+
+    let mut keeper = Keeper::new(
+        SvmKeeperRpc { ctx: &mut ctx },
+        payer.insecure_clone(),
+        config,
+    )
+    .state_path(&state_path)
+    .unwrap();
+
+    let outcome = keeper.tick().expect("Keeper pass should succeed");
+    assert_eq!(
+        outcome,
+        TickOutcome::DayClosed {
+            day_ts: progress.last_distribution_ts,
+            pages: (investor_count - 10).div_ceil(10) + investor_count.div_ceil(10),
+        }
+    );
+    assert_eq!(keeper.state().days_closed, 2);
+}
+
+#[test]
+fn test_03_keeper_skips_cursor_behind_progress() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    start_day(&mut ctx, key, pos_key);
+    crank_pages(&mut ctx, key, pos_key, 20);
+
+    let config = keeper_config(&ctx, key, pos_key);
+    let vault = config.vault;
+    let investor_count = config.streams.len() as u32;
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.cursor, 20);
+
+    // The persisted cursor lags one page behind, e.g. after a crash
+    let state_path = keeper_state_path(&vault);
+    KeeperState {
+        day_ts: progress.last_distribution_ts,
+        cursor: 10,
+        phase: PassPhase::Snapshot,
+        cap_passes: 0,
+        days_closed: 2,
+        last_signature: None,
+    }
+    .save(&state_path)
+    .unwrap();
+
+    let mut keeper = Keeper::new(
+        SvmKeeperRpc { ctx: &mut ctx },
+        payer.insecure_clone(),
+        config,
+    )
+    .state_path(&state_path)
+    .unwrap();
+
+    let outcome = keeper.tick().expect("Keeper pass should succeed");
+    assert_eq!(
+        outcome,
+        TickOutcome::DayClosed {
+            day_ts: progress.last_distribution_ts,
+            // The lagging page is not sent again
+            pages: (investor_count - 20).div_ceil(10) + investor_count.div_ceil(10),
+        }
+    );
+    assert_eq!(keeper.state().days_closed, 3);
+
+    let _ = fs::remove_file(&state_path);
+}
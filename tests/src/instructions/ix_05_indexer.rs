@@ -0,0 +1,150 @@
+use std::fs;
+
+use anchor_client::{
+    anchor_lang::{event::EVENT_IX_TAG_LE, Event},
+    solana_sdk::{
+        instruction::CompiledInstruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+        signer::Signer,
+    },
+};
+use tollgate::{
+    constants::{EVENT_SCHEMA_VERSION, TWENTY_FOUR_HOURS},
+    state::{Policy, Progress},
+};
+use tollgate_client::pda::{get_event_authority_pda, get_policy_pda, get_progress_pda};
+use tollgate_indexer::{
+    build_ledger, export_vault, parse_inner_instructions, EventRecord, ExportFormat, TollgateEvent,
+};
+
+use crate::{
+    instructions::ix_07_day_history::crank_day,
+    utils::{damm_v2::set_damm_v2_position_fees, svm::TestContext},
+};
+
+#[test]
+fn test_01_index_crank_day() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let quote_fee = LAMPORTS_PER_SOL / 4;
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(quote_fee));
+
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+    let investor_count = ctx
+        .get_program_account::<Policy>(&get_policy_pda(vault).0)
+        .investor_count;
+
+    // Fee claim, snapshot pass then payout pass
+    let records = crank_day(&mut ctx, key, pos_key);
+
+    let fees: Vec<_> = records
+        .iter()
+        .filter_map(|r| match &r.event {
+            TollgateEvent::QuoteFeesClaimed(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(fees.len(), 1);
+    assert_eq!(fees[0].quote_fee_claimed, quote_fee);
+
+    let page_payouts: Vec<u64> = records
+        .iter()
+        .filter_map(|r| match &r.event {
+            TollgateEvent::InvestorPayoutPage(e) => Some(e.payout),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(page_payouts.len() as u32, investor_count.div_ceil(10));
+
+    let Some(TollgateEvent::CreatorPayoutDayClosed(closed)) = records.last().map(|r| &r.event)
+    else {
+        panic!("Expected the day to close on the last page");
+    };
+
+    let ledger = build_ledger(&vault, &records);
+    assert_eq!(ledger.len(), 1);
+    let day = &ledger[0];
+    assert!(day.is_closed());
+    assert_eq!(day.fees_claimed, quote_fee);
+    assert_eq!(day.investor_payouts, page_payouts.iter().sum::<u64>());
+    assert_eq!(day.pages, page_payouts.len() as u32);
+    assert_eq!(day.distributable, closed.distributable);
+    assert_eq!(day.investor_payouts, closed.investor_payouts);
+    assert_eq!(
+        closed.investor_payouts + closed.creator_payout + closed.carry,
+        closed.distributable
+    );
+    assert_eq!(day.creator_payout, closed.creator_payout);
+    assert_eq!(day.carry, closed.carry);
+    assert_eq!(day.closed_ts, Some(closed.timestamp));
+    assert_eq!(
+        day.carry,
+        ctx.get_program_account::<Progress>(&get_progress_pda(vault).0)
+            .carry
+    );
+
+    // Events of other vaults are left out of the ledger
+    assert!(build_ledger(&Pubkey::new_unique(), &records).is_empty());
+
+    let out_dir = std::env::temp_dir().join(format!("tollgate-indexer-{}", vault));
+    let paths = export_vault(&out_dir, &vault, &records, ExportFormat::Jsonl).unwrap();
+    let exported: Vec<EventRecord> = fs::read_to_string(&paths[0])
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(exported, records);
+
+    let paths = export_vault(&out_dir, &vault, &records, ExportFormat::Csv).unwrap();
+    let ledger_csv = fs::read_to_string(paths.last().unwrap()).unwrap();
+    assert_eq!(ledger_csv.lines().count(), 1 + ledger.len());
+
+    let _ = fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn test_02_ignore_events_of_other_programs() {
+    let event = |schema_version| tollgate::events::AccountsMigrated {
+        schema_version,
+        vault: Pubkey::new_unique(),
+        policy: Pubkey::new_unique(),
+        progress: Pubkey::new_unique(),
+        policy_from_version: 0,
+        policy_version: Policy::VERSION,
+        progress_from_version: 0,
+        progress_version: Progress::VERSION,
+    };
+    let current = event(EVENT_SCHEMA_VERSION);
+    let event_data = |event: &tollgate::events::AccountsMigrated| {
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend(event.data());
+        data
+    };
+    let other_program = Pubkey::new_unique();
+    let account_keys = [
+        tollgate::ID,
+        other_program,
+        get_event_authority_pda(&tollgate::ID).0,
+    ];
+    let instruction = |program_id_index, data| CompiledInstruction {
+        program_id_index,
+        accounts: vec![2],
+        data,
+    };
+
+    let inner_instructions = vec![
+        instruction(0, event_data(&current)),
+        // The same event invoked on another program
+        instruction(1, event_data(&current)),
+        // Event data without the event-CPI tag
+        instruction(0, current.data()),
+        // An event of another schema version
+        instruction(0, event_data(&event(EVENT_SCHEMA_VERSION + 1))),
+    ];
+
+    let events = parse_inner_instructions(&account_keys, &inner_instructions);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].vault(), current.vault);
+}
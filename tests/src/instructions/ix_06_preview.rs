@@ -0,0 +1,147 @@
+use anchor_client::solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, signer::Signer, transaction::Transaction,
+};
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    instructions::{CrankParams, CrankPreview, PreviewStatus},
+    state::{CrankPhase, DayState, Policy, Progress},
+};
+use tollgate_client::{
+    decode_crank_preview,
+    pda::{get_policy_pda, get_progress_pda},
+    InvestorStream,
+};
+
+use crate::{
+    instructions::ix_02_crank::{claim_fees_ix, compute_crank_ix_accs, crank_builder, crank_ix},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{demand_logs_contain, get_payer, TestContext},
+    },
+};
+
+pub fn simulate_preview(ctx: &TestContext, key: &str, pos_key: &str, cursor: u32) -> CrankPreview {
+    let payer = get_payer();
+    let token = ctx.tokens.get(key).expect("Token not found in context");
+    let investors = token.investors[cursor as usize..cursor as usize + 10]
+        .iter()
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+    // The preview takes the claim_fees keys, to mirror the opening of a new day
+    let preview_ix = crank_builder(ctx, key, pos_key, payer.pubkey())
+        .cursor(cursor)
+        .investors(investors)
+        .preview_crank();
+    // Read-only and unsigned, so the simulation cannot create or change accounts
+    assert!(preview_ix
+        .accounts
+        .iter()
+        .all(|account| !account.is_writable && !account.is_signer));
+
+    let mut transaction = Transaction::new_with_payer(&[preview_ix], Some(&payer.pubkey()));
+    transaction.sign(&[payer], ctx.svm.latest_blockhash());
+    let simulated = ctx
+        .svm
+        .simulate_transaction(transaction)
+        .expect("Preview should succeed");
+    decode_crank_preview(&simulated.meta.return_data.data)
+        .expect("Preview return data should decode")
+}
+
+fn crank_page(ctx: &mut TestContext, key: &str, pos_key: &str, cursor: u32) -> String {
+    let payer = get_payer();
+    let (_, accs) = compute_crank_ix_accs(
+        ctx,
+        key,
+        pos_key,
+        false,
+        payer.pubkey(),
+        cursor,
+        cursor + 10,
+    );
+    let result = ctx.send_transaction(
+        &[crank_ix(
+            accs.0,
+            tollgate::instruction::Crank {
+                params: CrankParams { cursor },
+            },
+            accs.1,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+    demand_logs_contain("Crank::Completed successfully", &result);
+    result.unwrap().logs.join("\n")
+}
+
+#[test]
+fn test_01_preview_matches_crank() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let quote_fee = LAMPORTS_PER_SOL / 4;
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(quote_fee));
+
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+    let progress_pda = get_progress_pda(vault).0;
+    let investor_count = ctx
+        .get_program_account::<Policy>(&get_policy_pda(vault).0)
+        .investor_count;
+
+    // The first page of the day only adds to the snapshot
+    let progress_before = ctx.svm.get_account(&progress_pda).unwrap();
+    let preview = simulate_preview(&ctx, key, pos_key, 0);
+    assert_eq!(preview.status, PreviewStatus::Snapshot);
+    assert_eq!(preview.day_state, DayState::New);
+    assert_eq!(preview.phase, CrankPhase::Snapshot);
+    assert_eq!(preview.claimable_quote_fee, quote_fee);
+    assert!(preview.payouts.is_empty());
+
+    // The preview leaves the progress untouched
+    assert_eq!(ctx.svm.get_account(&progress_pda).unwrap(), progress_before);
+
+    // claim_fees opens the day with the previewed distributable
+    let payer = get_payer();
+    let result = ctx.send_transaction(
+        &[claim_fees_ix(&ctx, key, pos_key, payer.pubkey())],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+    demand_logs_contain("ClaimFees::Completed successfully", &result);
+    assert_eq!(
+        ctx.get_program_account::<Progress>(&progress_pda)
+            .distributable,
+        preview.distributable
+    );
+
+    for cursor in (0..investor_count).step_by(10) {
+        crank_page(&mut ctx, key, pos_key, cursor);
+    }
+    let progress = ctx.get_program_account::<Progress>(&progress_pda);
+    assert_eq!(progress.phase, CrankPhase::Payout);
+    assert_eq!(progress.cursor, 0);
+
+    let preview = simulate_preview(&ctx, key, pos_key, 0);
+    assert_eq!(preview.status, PreviewStatus::Payout);
+    assert_eq!(preview.day_state, DayState::Same);
+    assert_eq!(preview.payouts.len(), 10);
+    assert_eq!(preview.investor_pool, progress.investor_pool);
+    assert_eq!(
+        preview.page_payouts,
+        preview.payouts.iter().map(|p| p.payout).sum::<u64>()
+    );
+    assert!(preview.page_payouts <= preview.investor_pool);
+    assert!(preview.investor_pool <= preview.distributable);
+
+    let logs = crank_page(&mut ctx, key, pos_key, 0);
+    assert!(logs.contains(&format!(
+        "Crank::Processed page 0 to 10, payouts: {}",
+        preview.page_payouts
+    )));
+    assert_eq!(
+        ctx.get_program_account::<Progress>(&progress_pda).cursor,
+        10
+    );
+}
@@ -0,0 +1,176 @@
+use anchor_client::solana_sdk::{
+    account::Account, clock::Clock, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer,
+    system_instruction::SystemError,
+};
+use tollgate::{
+    constants::{DAY_HISTORY_LEN, TWENTY_FOUR_HOURS},
+    instructions::CrankParams,
+    state::{DayHistory, Policy, Progress},
+};
+use tollgate_client::{
+    decode_day_history,
+    pda::{get_day_history_pda, get_policy_pda, get_progress_pda},
+    InitializeDayHistoryBuilder,
+};
+use tollgate_indexer::{EventRecord, TollgateEvent};
+
+use crate::{
+    instructions::ix_02_crank::{claim_fees_ix, compute_crank_ix_accs, crank_ix},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{demand_logs_contain, demand_system_error, get_payer, TestContext},
+    },
+};
+
+fn get_day_history(ctx: &TestContext, vault: Pubkey) -> DayHistory {
+    let account = ctx
+        .svm
+        .get_account(&get_day_history_pda(vault).0)
+        .expect("Day history not found");
+    decode_day_history(&account.data).expect("Day history should decode")
+}
+
+/// Opens a new day with `claim_fees` unless it is already open, then runs
+/// its snapshot and payout passes, returning the events.
+pub fn crank_day(ctx: &mut TestContext, key: &str, pos_key: &str) -> Vec<EventRecord> {
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+    let investor_count = ctx
+        .get_program_account::<Policy>(&get_policy_pda(vault).0)
+        .investor_count;
+
+    let mut records = vec![];
+    let now_ts = ctx.svm.get_sysvar::<Clock>().unix_timestamp;
+    if ctx
+        .get_program_account::<Progress>(&get_progress_pda(vault).0)
+        .is_new_day(now_ts)
+    {
+        let meta = ctx
+            .send_transaction(
+                &[claim_fees_ix(ctx, key, pos_key, payer.pubkey())],
+                Some(&payer.pubkey()),
+                &[payer],
+            )
+            .expect("Claim fees should succeed");
+        records.extend(ctx.get_records(&meta, 0));
+    }
+
+    let pages = (0..investor_count).step_by(10);
+    for (slot, cursor) in pages.clone().chain(pages).enumerate() {
+        let (_, accs) = compute_crank_ix_accs(
+            ctx,
+            key,
+            pos_key,
+            false,
+            payer.pubkey(),
+            cursor,
+            (cursor + 10).min(investor_count),
+        );
+        let meta = ctx
+            .send_transaction(
+                &[crank_ix(
+                    accs.0,
+                    tollgate::instruction::Crank {
+                        params: CrankParams { cursor },
+                    },
+                    accs.1,
+                )],
+                Some(&payer.pubkey()),
+                &[payer],
+            )
+            .expect("Crank should succeed");
+        records.extend(ctx.get_records(&meta, slot as u64 + 1));
+    }
+    records
+}
+
+#[test]
+fn test_01_day_close_is_recorded() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let quote_fee = LAMPORTS_PER_SOL / 4;
+
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+    let days_closed = get_day_history(&ctx, vault).days_closed;
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(quote_fee));
+    let records = crank_day(&mut ctx, key, pos_key);
+
+    let Some(TollgateEvent::CreatorPayoutDayClosed(closed)) = records.last().map(|r| &r.event)
+    else {
+        panic!("Expected the day to close on the last page");
+    };
+
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    let day_history = get_day_history(&ctx, vault);
+    assert_eq!(day_history.days_closed, days_closed + 1);
+
+    let days = day_history.closed_days();
+    assert_eq!(
+        days.len() as u64,
+        day_history.days_closed.min(DAY_HISTORY_LEN as u64)
+    );
+    let day = days.last().expect("Expected a closed day");
+    assert_eq!(day.index, days_closed);
+    assert_eq!(day.day_ts, progress.last_distribution_ts);
+    assert_eq!(day.fees_claimed, quote_fee);
+    assert_eq!(day.distributable, closed.distributable);
+    assert_eq!(day.investor_payouts, closed.investor_payouts);
+    assert_eq!(day.creator_payout, closed.creator_payout);
+    assert_eq!(day.carry, closed.carry);
+    assert_eq!(day.locked_total, progress.locked_total);
+    assert!(day.investors_paid > 0);
+    assert!(day.investors_paid <= progress.cursor);
+
+    // The record of the next day starts empty
+    assert_eq!(day_history.current, Default::default());
+}
+
+#[test]
+fn test_02_initialize_day_history_of_existing_vault() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+    let day_history_pda = get_day_history_pda(vault).0;
+
+    // Vaults initialized before the day history existed have no account
+    ctx.svm
+        .set_account(day_history_pda, Account::default())
+        .unwrap();
+
+    let instruction = InitializeDayHistoryBuilder::new(vault, payer.pubkey()).instruction();
+    let result = ctx.send_transaction(
+        std::slice::from_ref(&instruction),
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+    demand_logs_contain(
+        "InitializeDayHistory::Initialization completed successfully",
+        &result,
+    );
+
+    let day_history = get_day_history(&ctx, vault);
+    assert_eq!(day_history.vault, vault);
+    assert_eq!(day_history.version, DayHistory::VERSION);
+    assert_eq!(day_history.days_closed, 0);
+    assert!(day_history.closed_days().is_empty());
+
+    // The day history can only be initialized once
+    ctx.svm.expire_blockhash();
+    let result = ctx.send_transaction(&[instruction], Some(&payer.pubkey()), &[payer]);
+    demand_system_error(SystemError::AccountAlreadyInUse, &result);
+
+    // The next day is recorded from the first slot
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    crank_day(&mut ctx, key, pos_key);
+
+    let day_history = get_day_history(&ctx, vault);
+    assert_eq!(day_history.days_closed, 1);
+    assert_eq!(day_history.closed_days()[0].index, 0);
+}
@@ -0,0 +1,202 @@
+use anchor_client::solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL, program_pack::Pack, pubkey::Pubkey, signer::Signer,
+};
+use anchor_spl::token::spl_token;
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    error::TollgateError,
+    instructions::CrankParams,
+    state::{CrankPhase, InvestorStats, Policy, Progress},
+};
+use tollgate_client::{
+    decode_investor_stats,
+    pda::{get_investor_stats_pda, get_policy_pda, get_progress_pda},
+};
+
+use crate::{
+    instructions::ix_02_crank::{compute_crank_ix_accs, crank_ix, crank_with_init_ix, open_day},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{demand_instruction_error, get_ix_err, get_payer, TestContext},
+    },
+};
+
+fn get_investor_stats(ctx: &TestContext, vault: Pubkey, stream: Pubkey) -> Option<InvestorStats> {
+    let account = ctx
+        .svm
+        .get_account(&get_investor_stats_pda(vault, stream).0)?;
+    decode_investor_stats(&account.data).ok()
+}
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+fn get_investor_count(ctx: &TestContext, key: &str) -> u32 {
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+    ctx.get_program_account::<Policy>(&get_policy_pda(vault).0)
+        .investor_count
+}
+
+/// Builds the crank instruction of a page in the given mode.
+fn crank_page_ix(
+    ctx: &TestContext,
+    key: &str,
+    pos_key: &str,
+    init_mode: bool,
+    cursor: u32,
+) -> Vec<Instruction> {
+    let payer = get_payer();
+    let investor_count = get_investor_count(ctx, key);
+    let (_, accs) = compute_crank_ix_accs(
+        ctx,
+        key,
+        pos_key,
+        init_mode,
+        payer.pubkey(),
+        cursor,
+        (cursor + 10).min(investor_count),
+    );
+    let params = CrankParams { cursor };
+    let instruction = if init_mode {
+        crank_with_init_ix(
+            accs.0,
+            tollgate::instruction::CrankWithInit { params },
+            accs.1,
+        )
+    } else {
+        crank_ix(accs.0, tollgate::instruction::Crank { params }, accs.1)
+    };
+    vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+        instruction,
+    ]
+}
+
+/// Runs a whole pass of the day in pages of 10.
+fn crank_pass(ctx: &mut TestContext, key: &str, pos_key: &str, init_mode: bool) {
+    let payer = get_payer();
+    let investor_count = get_investor_count(ctx, key);
+    for cursor in (0..investor_count).step_by(10) {
+        let instructions = crank_page_ix(ctx, key, pos_key, init_mode, cursor);
+        ctx.send_transaction(&instructions, Some(&payer.pubkey()), &[payer])
+            .expect("Crank should succeed");
+    }
+}
+
+#[test]
+fn test_01_payouts_are_tracked_per_investor() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let investor_count = get_investor_count(&ctx, key);
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let quote_mint = token.quote_mint;
+    let investors: Vec<(Pubkey, Pubkey)> = token
+        .investors
+        .iter()
+        .take(investor_count as usize)
+        .map(|investor| (investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+
+    let before: Vec<(Option<InvestorStats>, u64)> = investors
+        .iter()
+        .map(|(recipient, stream)| {
+            let ata =
+                anchor_spl::associated_token::get_associated_token_address(recipient, &quote_mint);
+            (
+                get_investor_stats(&ctx, vault, *stream),
+                get_token_balance(&ctx, &ata),
+            )
+        })
+        .collect();
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+    crank_pass(&mut ctx, key, pos_key, false);
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    let (investor_pool, day_ts) = (progress.investor_pool, progress.last_distribution_ts);
+    crank_pass(&mut ctx, key, pos_key, true);
+
+    let mut allocated = 0u64;
+    for ((recipient, stream), (stats_before, balance_before)) in investors.iter().zip(before) {
+        let stats = get_investor_stats(&ctx, vault, *stream).expect("Stats should be created");
+        assert_eq!(stats.vault, vault);
+        assert_eq!(stats.stream, *stream);
+        assert_eq!(stats.recipient, *recipient);
+        assert_eq!(stats.version, InvestorStats::VERSION);
+
+        let (paid_before, dust_before, skipped_before, skipped_days_before) =
+            stats_before.map_or((0, 0, 0, 0), |stats| {
+                (
+                    stats.total_paid,
+                    stats.dust,
+                    stats.skipped,
+                    stats.skipped_days,
+                )
+            });
+        let ata =
+            anchor_spl::associated_token::get_associated_token_address(recipient, &quote_mint);
+        let paid = stats.total_paid - paid_before;
+        assert_eq!(paid, get_token_balance(&ctx, &ata) - balance_before);
+        // Every investor records the day by its timestamp, paid or not
+        assert_eq!(stats.last_seen_day, Some(day_ts));
+        if paid > 0 {
+            assert_eq!(stats.last_paid_day, Some(day_ts));
+            assert_eq!(stats.skipped_days, skipped_days_before);
+        } else {
+            assert_eq!(stats.skipped_days, skipped_days_before + 1);
+        }
+        allocated += paid + (stats.dust - dust_before) + (stats.skipped - skipped_before);
+    }
+
+    // Every allocation of the day lands in the stats of an investor
+    assert_eq!(allocated, investor_pool);
+}
+
+#[test]
+fn test_02_stats_of_another_stream_are_rejected() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+    crank_pass(&mut ctx, key, pos_key, false);
+    assert_eq!(
+        ctx.get_program_account::<Progress>(&get_progress_pda(vault).0)
+            .phase,
+        CrankPhase::Payout
+    );
+
+    // Swap the stats of the first two investors of the page
+    let mut instructions = crank_page_ix(&ctx, key, pos_key, false, 0);
+    let page_size = get_investor_count(&ctx, key).min(10) as usize;
+    let accounts = &mut instructions[1].accounts;
+    let first_stats = accounts.len() - page_size * 4 + 2;
+    let first = accounts[first_stats].pubkey;
+    accounts[first_stats].pubkey = accounts[first_stats + 4].pubkey;
+    accounts[first_stats + 4].pubkey = first;
+
+    let result = ctx.send_transaction(&instructions, Some(&payer.pubkey()), &[payer]);
+    demand_instruction_error(get_ix_err(TollgateError::InvalidInvestorStats), &result);
+
+    // The day carries on with the right stats
+    crank_pass(&mut ctx, key, pos_key, false);
+    assert_eq!(
+        ctx.get_program_account::<Progress>(&get_progress_pda(vault).0)
+            .cursor,
+        get_investor_count(&ctx, key)
+    );
+}
@@ -0,0 +1,213 @@
+use std::borrow::Cow;
+
+use anchor_client::solana_sdk::{
+    account::Account, clock::Clock, message::AddressLookupTableAccount,
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer,
+};
+use solana_address_lookup_table_interface::{
+    program::ID as LOOKUP_TABLE_PROGRAM_ID,
+    state::{AddressLookupTable, LookupTableMeta},
+};
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    state::{CrankPhase, DayState, Policy, Progress},
+};
+use tollgate_client::{
+    lookup_table_addresses,
+    pda::{get_policy_pda, get_progress_pda},
+    CuModel, InvestorStream, Page, PagePlanner,
+};
+
+use crate::{
+    instructions::ix_02_crank::{crank_builder, open_day},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{get_payer, TestContext},
+    },
+};
+
+/// Returns the investors of the policy in crank order.
+fn get_investors(ctx: &TestContext, key: &str) -> Vec<InvestorStream> {
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let investor_count = ctx
+        .get_program_account::<Policy>(&get_policy_pda(token.vault.pubkey()).0)
+        .investor_count;
+    token
+        .investors
+        .iter()
+        .take(investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect()
+}
+
+/// Writes active lookup tables holding the given addresses.
+fn set_lookup_tables(
+    ctx: &mut TestContext,
+    authority: Pubkey,
+    tables: Vec<Vec<Pubkey>>,
+) -> Vec<AddressLookupTableAccount> {
+    let slot = ctx.svm.get_sysvar::<Clock>().slot;
+    let lookup_tables = tables
+        .into_iter()
+        .map(|addresses| {
+            let key = Pubkey::new_unique();
+            let data = AddressLookupTable {
+                meta: LookupTableMeta {
+                    last_extended_slot: slot,
+                    ..LookupTableMeta::new(authority)
+                },
+                addresses: Cow::Borrowed(&addresses),
+            }
+            .serialize_for_tests()
+            .expect("Lookup table should serialize");
+            let account = Account {
+                lamports: ctx.svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: LOOKUP_TABLE_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            };
+            ctx.svm.set_account(key, account).unwrap();
+            AddressLookupTableAccount { key, addresses }
+        })
+        .collect();
+
+    // Addresses extended in the current slot are not active yet
+    ctx.svm.warp_to_slot(slot + 1);
+    lookup_tables
+}
+
+fn get_planner(
+    ctx: &mut TestContext,
+    key: &str,
+    pos_key: &str,
+    investors: &[InvestorStream],
+) -> PagePlanner {
+    let payer = get_payer();
+    let builder = crank_builder(ctx, key, pos_key, payer.pubkey());
+    let tables = lookup_table_addresses(&builder, investors, true);
+    let lookup_tables = set_lookup_tables(ctx, payer.pubkey(), tables);
+    PagePlanner::new(builder, lookup_tables)
+}
+
+/// Runs a planned pass in v0 transactions, returning the compute units of each page.
+fn crank_planned_pass(
+    ctx: &mut TestContext,
+    planner: &PagePlanner,
+    investors: &[InvestorStream],
+    phase: CrankPhase,
+    init_mode: bool,
+) -> Vec<(Page, u64)> {
+    let payer = get_payer();
+    let pages = planner
+        .plan_pass(&payer.pubkey(), investors, 0, phase.clone(), init_mode)
+        .expect("A single investor should fit a page");
+
+    let mut consumed = vec![];
+    for page in pages {
+        let message = planner
+            .compile_page(
+                &payer.pubkey(),
+                investors,
+                page,
+                phase.clone(),
+                init_mode,
+                ctx.svm.latest_blockhash(),
+            )
+            .expect("Page should compile");
+        let meta = ctx
+            .send_versioned_transaction(message, &[payer])
+            .expect("Planned page should succeed");
+        consumed.push((page, meta.compute_units_consumed));
+    }
+    consumed
+}
+
+#[test]
+fn test_01_planned_day_fits_the_cu_model() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let vault = ctx.tokens.get(key).expect("Token not found").vault.pubkey();
+    let investors = get_investors(&ctx, key);
+    let planner = get_planner(&mut ctx, key, pos_key, &investors);
+    let unpadded = planner.clone().cu_model(CuModel {
+        margin_bps: 0,
+        ..CuModel::default()
+    });
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+
+    for (phase, init_mode) in [(CrankPhase::Snapshot, false), (CrankPhase::Payout, true)] {
+        let pages = crank_planned_pass(&mut ctx, &planner, &investors, phase.clone(), init_mode);
+        for (page, consumed) in pages {
+            // The model without its margin bounds the measured cost
+            let estimate = unpadded.compute_unit_limit(&investors, page, phase.clone(), init_mode);
+            assert!(
+                consumed <= estimate as u64,
+                "{phase:?} page {page:?} consumed {consumed} over the estimate {estimate}"
+            );
+        }
+    }
+
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.day_state, DayState::Closed);
+    assert_eq!(progress.cursor, investors.len() as u32);
+}
+
+#[test]
+fn test_02_planned_pages_are_maximal() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    let investors = get_investors(&ctx, key);
+    let planner = get_planner(&mut ctx, key, pos_key, &investors);
+
+    for (phase, init_mode) in [
+        (CrankPhase::Snapshot, false),
+        (CrankPhase::Payout, false),
+        (CrankPhase::Payout, true),
+    ] {
+        let pages = planner
+            .plan_pass(&payer.pubkey(), &investors, 0, phase.clone(), init_mode)
+            .expect("A single investor should fit a page");
+        assert_eq!(pages.first().map(|page| page.cursor), Some(0));
+        assert_eq!(
+            pages.last().map(|page| page.end),
+            Some(investors.len() as u32)
+        );
+
+        for page in pages
+            .iter()
+            .filter(|page| page.end < investors.len() as u32)
+        {
+            let larger = Page {
+                cursor: page.cursor,
+                end: page.end + 1,
+            };
+            assert!(!planner.fits(
+                &payer.pubkey(),
+                &investors,
+                larger,
+                phase.clone(),
+                init_mode
+            ));
+        }
+    }
+
+    // With lookup tables the account lock limit bounds the page, not the packet size
+    let page = planner
+        .max_page(&payer.pubkey(), &investors, 0, CrankPhase::Snapshot, false)
+        .expect("A single investor should fit a page");
+    let unlocked = planner
+        .clone()
+        .max_account_locks(128)
+        .max_page(&payer.pubkey(), &investors, 0, CrankPhase::Snapshot, false)
+        .expect("A single investor should fit a page");
+    assert!(unlocked.len() > page.len());
+}
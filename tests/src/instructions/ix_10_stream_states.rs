@@ -0,0 +1,392 @@
+use anchor_client::{
+    anchor_lang::{AccountSerialize, AnchorDeserialize, AnchorSerialize},
+    solana_sdk::{
+        account::Account, clock::Clock, native_token::LAMPORTS_PER_SOL, program_pack::Pack,
+        pubkey::Pubkey, rent::Rent, signer::Signer,
+    },
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use streamflow_sdk::state::Contract;
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    error::TollgateError,
+    instructions::CrankParams,
+    state::{InvestorStats, Progress},
+};
+use tollgate_client::pda::{get_investor_stats_pda, get_progress_pda};
+use tollgate_indexer::{SkipReason, TollgateEvent};
+
+use crate::{
+    instructions::{
+        ix_02_crank::{claim_fees_ix, compute_crank_ix_accs, crank_ix},
+        ix_07_day_history::crank_day,
+    },
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{demand_instruction_error, get_ix_err, get_payer, TestContext, TransactionResult},
+    },
+};
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+/// Rewrites the contract of a stream account in place.
+pub fn update_stream(ctx: &mut TestContext, stream: Pubkey, update: impl FnOnce(&mut Contract)) {
+    let mut account = ctx.svm.get_account(&stream).expect("Stream not found");
+    let mut contract =
+        Contract::deserialize(&mut account.data.as_slice()).expect("Stream should decode");
+    update(&mut contract);
+    contract
+        .serialize(&mut &mut account.data[..])
+        .expect("Stream should serialize");
+    ctx.svm.set_account(stream, account).unwrap();
+}
+
+/// Writes the investor stats of a stream, as left behind by an earlier payout.
+fn record_stream(ctx: &mut TestContext, vault: Pubkey, stream: Pubkey, recipient: Pubkey) {
+    let (stats, bump) = get_investor_stats_pda(vault, stream);
+    let mut data = Vec::with_capacity(InvestorStats::SPACE);
+    InvestorStats::new(vault, stream, recipient, bump)
+        .try_serialize(&mut data)
+        .expect("Stats should serialize");
+    let account = Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: tollgate::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    ctx.svm.set_account(stats, account).unwrap();
+}
+
+/// Claims the day's fees and sends the first snapshot page of ten investors.
+fn crank_first_page(ctx: &mut TestContext, key: &str, pos_key: &str) -> TransactionResult {
+    let payer = get_payer();
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    ctx.send_transaction(
+        &[claim_fees_ix(ctx, key, pos_key, payer.pubkey())],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+    .expect("Claim fees should succeed");
+    let (_, accs) = compute_crank_ix_accs(ctx, key, pos_key, false, payer.pubkey(), 0, 10);
+    ctx.send_transaction(
+        &[crank_ix(
+            accs.0,
+            tollgate::instruction::Crank {
+                params: CrankParams { cursor: 0 },
+            },
+            accs.1,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+}
+
+#[test]
+fn test_01_inactive_streams_are_skipped() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let quote_mint = token.quote_mint;
+    let investors: Vec<(Pubkey, Pubkey)> = token
+        .investors
+        .iter()
+        .take(3)
+        .map(|investor| (investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+    let originals: Vec<Account> = investors
+        .iter()
+        .map(|(_, stream)| ctx.svm.get_account(stream).expect("Stream not found"))
+        .collect();
+    let balances: Vec<u64> = investors
+        .iter()
+        .map(|(recipient, _)| {
+            get_token_balance(&ctx, &get_associated_token_address(recipient, &quote_mint))
+        })
+        .collect();
+
+    // Cancel the first stream, pause the second and close the third, which
+    // the vault recorded on an earlier payout
+    let now = ctx.svm.get_sysvar::<Clock>().unix_timestamp as u64;
+    update_stream(&mut ctx, investors[0].1, |contract| {
+        contract.canceled_at = now;
+    });
+    update_stream(&mut ctx, investors[1].1, |contract| {
+        contract.current_pause_start = now;
+    });
+    ctx.svm
+        .set_account(investors[2].1, Account::default())
+        .unwrap();
+    record_stream(&mut ctx, vault, investors[2].1, investors[2].0);
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    let records = crank_day(&mut ctx, key, pos_key);
+
+    // The pages go through and the day closes
+    assert!(matches!(
+        records.last().map(|record| &record.event),
+        Some(TollgateEvent::CreatorPayoutDayClosed(_))
+    ));
+
+    let skipped: Vec<(Pubkey, u32, SkipReason)> = records
+        .iter()
+        .filter_map(|record| match &record.event {
+            // The other investors may be left unpaid for their own reasons
+            TollgateEvent::InvestorSkipped(e) if e.index < 3 => Some((e.stream, e.index, e.reason)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        skipped,
+        vec![
+            (investors[0].1, 0, SkipReason::Cancelled),
+            (investors[1].1, 1, SkipReason::Paused),
+            (investors[2].1, 2, SkipReason::Closed),
+        ]
+    );
+
+    // Zero-weight investors earn nothing, their stats record the skipped day
+    let day_ts = ctx
+        .get_program_account::<Progress>(&get_progress_pda(vault).0)
+        .last_distribution_ts;
+    for ((recipient, stream), balance) in investors.iter().zip(balances) {
+        let ata = get_associated_token_address(recipient, &quote_mint);
+        assert_eq!(get_token_balance(&ctx, &ata), balance);
+        let stats =
+            ctx.get_program_account::<InvestorStats>(&get_investor_stats_pda(vault, *stream).0);
+        assert_eq!(stats.last_seen_day, Some(day_ts));
+        assert!(stats.skipped_days > 0);
+    }
+
+    for ((_, stream), original) in investors.iter().zip(originals) {
+        ctx.svm.set_account(*stream, original).unwrap();
+    }
+}
+
+#[test]
+fn test_02_weights_are_averaged_over_the_day() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+    let amount = 8_000_000;
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let streams: Vec<Pubkey> = token
+        .investors
+        .iter()
+        .take(10)
+        .map(|investor| investor.stream.pubkey())
+        .collect();
+    let originals: Vec<Account> = streams
+        .iter()
+        .map(|stream| ctx.svm.get_account(stream).expect("Stream not found"))
+        .collect();
+
+    // Half of each stream unlocks at 18h into the day, the rest after the day
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    let day_start = ctx.svm.get_sysvar::<Clock>().unix_timestamp as u64;
+    let hour = 3_600;
+    for stream in &streams {
+        update_stream(&mut ctx, *stream, |contract| {
+            contract.ix.net_amount_deposited = amount;
+            contract.ix.start_time = day_start + 12 * hour;
+            contract.ix.cliff = 0;
+            contract.ix.cliff_amount = 0;
+            contract.ix.period = 6 * hour;
+            contract.ix.amount_per_period = amount / 2;
+            contract.end_time = day_start + 30 * hour;
+            contract.amount_withdrawn = 0;
+            contract.last_rate_change_time = 0;
+            contract.funds_unlocked_at_last_rate_change = 0;
+            contract.pause_cumulative = 0;
+        });
+    }
+
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    ctx.send_transaction(
+        &[claim_fees_ix(&ctx, key, pos_key, payer.pubkey())],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+    .expect("Claim fees should succeed");
+    let (_, accs) = compute_crank_ix_accs(&ctx, key, pos_key, false, payer.pubkey(), 0, 10);
+    ctx.send_transaction(
+        &[crank_ix(
+            accs.0,
+            tollgate::instruction::Crank {
+                params: CrankParams { cursor: 0 },
+            },
+            accs.1,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+    .expect("Crank should succeed");
+
+    // Fully locked for 18h then half locked for 6h, whenever the page lands
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.locked_total, 10 * (amount * 7 / 8));
+
+    crank_day(&mut ctx, key, pos_key);
+    for (stream, original) in streams.iter().zip(originals) {
+        ctx.svm.set_account(*stream, original).unwrap();
+    }
+}
+
+#[test]
+fn test_03_unrecorded_empty_stream_is_rejected() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let stream = token.investors[0].stream.pubkey();
+    let original = ctx.svm.get_account(&stream).expect("Stream not found");
+
+    // An empty account the vault never paid cannot stand in for a stream
+    ctx.svm.set_account(stream, Account::default()).unwrap();
+    let result = crank_first_page(&mut ctx, key, pos_key);
+    demand_instruction_error(get_ix_err(TollgateError::InvalidStreamAccount), &result);
+
+    ctx.svm.set_account(stream, original).unwrap();
+}
+
+#[test]
+fn test_04_stream_owned_by_another_program_is_rejected() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let stream = token.investors[0].stream.pubkey();
+    let original = ctx.svm.get_account(&stream).expect("Stream not found");
+
+    // Same contract bytes, but not written by Streamflow
+    let mut forged = original.clone();
+    forged.owner = Pubkey::new_unique();
+    ctx.svm.set_account(stream, forged).unwrap();
+    let result = crank_first_page(&mut ctx, key, pos_key);
+    demand_instruction_error(get_ix_err(TollgateError::InvalidStreamAccount), &result);
+
+    ctx.svm.set_account(stream, original).unwrap();
+}
+
+#[test]
+fn test_05_stream_cancelled_after_snapshot_is_carried() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let stream = token.investors[0].stream.pubkey();
+    let original = ctx.svm.get_account(&stream).expect("Stream not found");
+    let investor_count = token.investors.len() as u32;
+
+    // Snapshot pass with every stream vesting
+    crank_first_page(&mut ctx, key, pos_key).expect("Crank should succeed");
+    for cursor in (10..investor_count).step_by(10) {
+        let (_, accs) = compute_crank_ix_accs(
+            &ctx,
+            key,
+            pos_key,
+            false,
+            payer.pubkey(),
+            cursor,
+            (cursor + 10).min(investor_count),
+        );
+        ctx.send_transaction(
+            &[crank_ix(
+                accs.0,
+                tollgate::instruction::Crank {
+                    params: CrankParams { cursor },
+                },
+                accs.1,
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+        )
+        .expect("Crank should succeed");
+    }
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.cursor, 0);
+    let carry = progress.carry;
+
+    // The first stream is cancelled before the payout pass
+    let now = ctx.svm.get_sysvar::<Clock>().unix_timestamp as u64;
+    update_stream(&mut ctx, stream, |contract| {
+        contract.canceled_at = now;
+    });
+    let records = crank_day(&mut ctx, key, pos_key);
+
+    assert!(records.iter().any(|record| matches!(
+        &record.event,
+        TollgateEvent::InvestorSkipped(e) if e.stream == stream && e.reason == SkipReason::Cancelled
+    )));
+
+    // The day closes, with the share of the cancelled stream in the carry
+    let closed = records
+        .iter()
+        .find_map(|record| match &record.event {
+            TollgateEvent::CreatorPayoutDayClosed(e) => Some(e.clone()),
+            _ => None,
+        })
+        .expect("The day should close");
+    assert!(closed.investor_payouts < progress.investor_pool);
+    assert!(closed.carry > carry);
+    assert_eq!(
+        closed.investor_payouts + closed.creator_payout + closed.carry - carry,
+        closed.distributable
+    );
+
+    ctx.svm.set_account(stream, original).unwrap();
+}
+
+#[test]
+fn test_06_stream_closed_after_standard_crank_is_skipped() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let stream = token.investors[0].stream.pubkey();
+    let original = ctx.svm.get_account(&stream).expect("Stream not found");
+
+    // A day cranked without init mode binds the stream to the vault
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    crank_day(&mut ctx, key, pos_key);
+    let stats = ctx.get_program_account::<InvestorStats>(&get_investor_stats_pda(vault, stream).0);
+    assert_eq!(stats.stream, stream);
+
+    // The investor closes the stream without a redirect
+    ctx.svm.set_account(stream, Account::default()).unwrap();
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    let records = crank_day(&mut ctx, key, pos_key);
+
+    assert!(records.iter().any(|record| matches!(
+        &record.event,
+        TollgateEvent::InvestorSkipped(e) if e.stream == stream && e.reason == SkipReason::Closed
+    )));
+    assert!(matches!(
+        records.last().map(|record| &record.event),
+        Some(TollgateEvent::CreatorPayoutDayClosed(_))
+    ));
+
+    ctx.svm.set_account(stream, original).unwrap();
+}
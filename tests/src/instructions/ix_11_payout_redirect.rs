@@ -0,0 +1,201 @@
+use anchor_client::{
+    anchor_lang::error::ErrorCode,
+    solana_sdk::{
+        account::Account, compute_budget::ComputeBudgetInstruction, instruction::InstructionError,
+        native_token::LAMPORTS_PER_SOL, program_pack::Pack, pubkey::Pubkey, signature::Keypair,
+        signer::Signer,
+    },
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token::spl_token,
+};
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    error::TollgateError,
+    state::{CrankPhase, PayoutRedirect, Policy, Progress},
+};
+use tollgate_client::{
+    decode_investor_stats, decode_payout_redirect,
+    pda::{get_investor_stats_pda, get_payout_redirect_pda, get_policy_pda, get_progress_pda},
+    InvestorStream, SetPayoutDestinationBuilder,
+};
+use tollgate_indexer::TollgateEvent;
+
+use crate::{
+    instructions::ix_02_crank::{crank_builder, open_day},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{
+            demand_instruction_error, demand_logs_contain, get_ix_err, get_payer, TestContext,
+            TransactionResult,
+        },
+    },
+};
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+fn get_total_paid(ctx: &TestContext, vault: Pubkey, stream: Pubkey) -> u64 {
+    ctx.svm
+        .get_account(&get_investor_stats_pda(vault, stream).0)
+        .and_then(|account| decode_investor_stats(&account.data).ok())
+        .map_or(0, |stats| stats.total_paid)
+}
+
+/// Sends the crank of a page of `investors` in crank order.
+fn crank_page(
+    ctx: &mut TestContext,
+    key: &str,
+    pos_key: &str,
+    investors: &[InvestorStream],
+    cursor: u32,
+) -> TransactionResult {
+    let payer = get_payer();
+    let end = (cursor as usize + 10).min(investors.len());
+    let instruction = crank_builder(ctx, key, pos_key, payer.pubkey())
+        .cursor(cursor)
+        .investors(investors[cursor as usize..end].to_vec())
+        .crank();
+    ctx.send_transaction(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+}
+
+/// Runs a whole pass of the day in pages of 10.
+fn crank_pass(ctx: &mut TestContext, key: &str, pos_key: &str, investors: &[InvestorStream]) {
+    for cursor in (0..investors.len() as u32).step_by(10) {
+        crank_page(ctx, key, pos_key, investors, cursor).expect("Crank should succeed");
+    }
+}
+
+#[test]
+fn test_01_payouts_follow_the_redirect() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let quote_mint = token.quote_mint;
+    let investor_count = ctx
+        .get_program_account::<Policy>(&get_policy_pda(vault).0)
+        .investor_count;
+    let recipient = token.investors[0].key.clone();
+    let stream = token.investors[0].stream.pubkey();
+    let mut investors: Vec<InvestorStream> = token
+        .investors
+        .iter()
+        .take(investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+
+    // The destination is a quote account owned by someone else
+    let owner = Keypair::new();
+    let destination = get_associated_token_address(&owner.pubkey(), &quote_mint);
+    ctx.send_transaction(
+        &[create_associated_token_account_idempotent(
+            &payer.pubkey(),
+            &owner.pubkey(),
+            &quote_mint,
+            &spl_token::id(),
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+    .expect("Destination should be created");
+    ctx.airdrop(&recipient.pubkey(), 1).unwrap();
+
+    // Only a Streamflow account can stand for the stream
+    let not_a_stream = get_policy_pda(vault).0;
+    let instruction =
+        SetPayoutDestinationBuilder::new(vault, not_a_stream, destination, recipient.pubkey())
+            .instruction();
+    let result = ctx.send_transaction(&[instruction], Some(&recipient.pubkey()), &[&recipient]);
+    demand_instruction_error(
+        InstructionError::Custom(ErrorCode::ConstraintOwner as u32),
+        &result,
+    );
+
+    // Only the stream recipient can redirect its payouts
+    let instruction =
+        SetPayoutDestinationBuilder::new(vault, stream, destination, payer.pubkey()).instruction();
+    let result = ctx.send_transaction(&[instruction], Some(&payer.pubkey()), &[payer]);
+    demand_instruction_error(
+        get_ix_err(TollgateError::PayoutRedirectUnauthorized),
+        &result,
+    );
+
+    let instruction =
+        SetPayoutDestinationBuilder::new(vault, stream, destination, recipient.pubkey())
+            .instruction();
+    let result = ctx.send_transaction(&[instruction], Some(&recipient.pubkey()), &[&recipient]);
+    demand_logs_contain(
+        &format!("SetPayoutDestination::Payouts of stream {stream} redirected to {destination}"),
+        &result,
+    );
+    let set = ctx
+        .get_events(result.as_ref().expect("Destination should be set"))
+        .into_iter()
+        .find_map(|event| match event {
+            TollgateEvent::PayoutDestinationSet(e) => Some(e),
+            _ => None,
+        })
+        .expect("PayoutDestinationSet event not found");
+    assert_eq!(set.vault, vault);
+    assert_eq!(set.stream, stream);
+    assert_eq!(set.recipient, recipient.pubkey());
+    assert_eq!(set.destination, destination);
+
+    let redirect_pda = get_payout_redirect_pda(vault, stream).0;
+    let redirect = decode_payout_redirect(&ctx.svm.get_account(&redirect_pda).unwrap().data)
+        .expect("Payout redirect should decode");
+    assert_eq!(redirect.version, PayoutRedirect::VERSION);
+    assert_eq!(redirect.vault, vault);
+    assert_eq!(redirect.stream, stream);
+    assert_eq!(redirect.recipient, recipient.pubkey());
+    assert_eq!(redirect.destination, destination);
+    assert_eq!(redirect.destination_owner, owner.pubkey());
+
+    let ata = get_associated_token_address(&recipient.pubkey(), &quote_mint);
+    let ata_balance = get_token_balance(&ctx, &ata);
+    let total_paid = get_total_paid(&ctx, vault, stream);
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+    crank_pass(&mut ctx, key, pos_key, &investors);
+    assert_eq!(
+        ctx.get_program_account::<Progress>(&get_progress_pda(vault).0)
+            .phase,
+        CrankPhase::Payout
+    );
+
+    // Paying the ATA of a redirected investor is rejected
+    let result = crank_page(&mut ctx, key, pos_key, &investors, 0);
+    demand_instruction_error(get_ix_err(TollgateError::InvalidPayoutDestination), &result);
+
+    investors[0] = investors[0].with_redirect(Some(&redirect));
+    crank_pass(&mut ctx, key, pos_key, &investors);
+
+    let paid = get_total_paid(&ctx, vault, stream) - total_paid;
+    assert_eq!(get_token_balance(&ctx, &destination), paid);
+    assert_eq!(get_token_balance(&ctx, &ata), ata_balance);
+
+    ctx.svm
+        .set_account(redirect_pda, Account::default())
+        .unwrap();
+}
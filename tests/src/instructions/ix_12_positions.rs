@@ -0,0 +1,289 @@
+use anchor_client::solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Keypair, signer::Signer, system_instruction,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token::spl_token::{self, native_mint},
+};
+use tollgate::{
+    constants::{damm_v2_constants, TWENTY_FOUR_HOURS},
+    error::TollgateError,
+    state::{DayHistory, DayState, PositionRegistry, Progress},
+};
+use tollgate_client::{
+    decode_day_history, decode_position_registry,
+    pda::{get_day_history_pda, get_position_registry_pda, get_progress_pda},
+    AddPositionBuilder, ExtraPosition,
+};
+use tollgate_indexer::TollgateEvent;
+
+use crate::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    instructions::{ix_02_crank::crank_builder, ix_07_day_history::crank_day},
+    utils::{
+        damm_v2::{
+            get_initialize_customizable_pool_ix_accs, get_pool_pda, get_pool_with_config_pda,
+            get_position_pda, initialize_customizable_pool_ix, prepare_pool_creation_params,
+            set_damm_v2_position_fees, set_position_fees,
+        },
+        svm::{demand_instruction_error, demand_logs_contain, get_ix_err, get_payer, TestContext},
+    },
+};
+
+fn get_position_registry(ctx: &TestContext, vault: Pubkey) -> PositionRegistry {
+    let account = ctx
+        .svm
+        .get_account(&get_position_registry_pda(vault).0)
+        .expect("Position registry not found");
+    decode_position_registry(&account.data).expect("Position registry should decode")
+}
+
+fn get_day_history(ctx: &TestContext, vault: Pubkey) -> DayHistory {
+    let account = ctx
+        .svm
+        .get_account(&get_day_history_pda(vault).0)
+        .expect("Day history not found");
+    decode_day_history(&account.data).expect("Day history should decode")
+}
+
+/// Creates a customizable pool of the token pair collecting quote-only fees,
+/// returning the pool address.
+fn create_quote_only_pool(ctx: &mut TestContext, key: &str) -> Pubkey {
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let creator = token.creator.insecure_clone();
+    let base_mint = token.base_mint.pubkey();
+    let quote_mint = token.quote_mint;
+    let position_nft_mint = Keypair::new();
+    let wsol_account = get_associated_token_address(&creator.pubkey(), &native_mint::ID);
+
+    let pool_params = prepare_pool_creation_params(
+        10 * LAMPORTS_PER_SOL,
+        10 * LAMPORTS_PER_SOL,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
+    )
+    .expect("Failed to prepare pool creation parameters");
+    ctx.send_transaction(
+        &[
+            create_associated_token_account_idempotent(
+                &creator.pubkey(),
+                &creator.pubkey(),
+                &native_mint::id(),
+                &spl_token::ID,
+            ),
+            system_instruction::transfer(&creator.pubkey(), &wsol_account, 10 * LAMPORTS_PER_SOL),
+            spl_token::instruction::sync_native(&spl_token::ID, &wsol_account)
+                .expect("Failed to sync native token"),
+            initialize_customizable_pool_ix(
+                get_initialize_customizable_pool_ix_accs(
+                    ctx,
+                    creator.pubkey(),
+                    position_nft_mint.pubkey(),
+                    creator.pubkey(),
+                    damm_v2_constants::pool_authority::ID,
+                    base_mint,
+                    quote_mint,
+                ),
+                damm_v2::client::args::InitializeCustomizablePool {
+                    params: damm_v2::types::InitializeCustomizablePoolParameters {
+                        pool_fees: damm_v2::types::PoolFeeParameters {
+                            base_fee: damm_v2::types::BaseFeeParameters {
+                                cliff_fee_numerator: 2_500_000,
+                                number_of_period: 0,
+                                period_frequency: 0,
+                                reduction_factor: 0,
+                                fee_scheduler_mode: 0,
+                            },
+                            padding: [0; 3],
+                            dynamic_fee: None,
+                        },
+                        sqrt_min_price: MIN_SQRT_PRICE,
+                        sqrt_max_price: MAX_SQRT_PRICE,
+                        has_alpha_vault: false,
+                        liquidity: pool_params.liquidity_delta,
+                        sqrt_price: pool_params.init_sqrt_price,
+                        activation_type: 1,
+                        collect_fee_mode: 1,
+                        activation_point: None,
+                    },
+                },
+            ),
+        ],
+        Some(&creator.pubkey()),
+        &[&creator, &position_nft_mint],
+    )
+    .expect("Customizable pool should be created");
+
+    get_pool_pda(base_mint, quote_mint).0
+}
+
+#[test]
+fn test_01_initialize_registers_the_primary_position() {
+    let ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let pool = get_pool_with_config_pda(
+        token.pool_config,
+        token.base_mint.pubkey(),
+        token.quote_mint,
+    )
+    .0;
+    let position = get_position_pda(token.pos_mints.get(pos_key).unwrap().pubkey()).0;
+
+    let registry = get_position_registry(&ctx, vault);
+    assert_eq!(registry.version, PositionRegistry::VERSION);
+    assert_eq!(registry.vault, vault);
+    assert_eq!(registry.base_mint, token.base_mint.pubkey());
+    assert_eq!(registry.position_count, 1);
+    assert!(registry.is_primary(&pool, &position));
+}
+
+#[test]
+fn test_02_claim_fees_aggregates_every_position() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+    let primary_fee = LAMPORTS_PER_SOL / 4;
+    let extra_fee = LAMPORTS_PER_SOL / 8;
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.clone();
+    let quote_mint = token.quote_mint;
+    let pool_config = token.pool_config;
+
+    // Add a position on a second pool of the pair
+    let pool = create_quote_only_pool(&mut ctx, key);
+    let position_nft_mint = Keypair::new();
+    let position = get_position_pda(position_nft_mint.pubkey()).0;
+
+    // The pool config goes through the same checks as in initialize
+    let fee_both_config = ctx.tokens.get("coh").expect("Token not found").pool_config;
+    let instruction = AddPositionBuilder::new(
+        vault.pubkey(),
+        pool,
+        fee_both_config,
+        position_nft_mint.pubkey(),
+        payer.pubkey(),
+    )
+    .instruction();
+    let result = ctx.send_transaction(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer, &vault, &position_nft_mint],
+    );
+    demand_instruction_error(
+        get_ix_err(TollgateError::PoolConfigNotQuoteOnlyFees),
+        &result,
+    );
+    let instruction = AddPositionBuilder::new(
+        vault.pubkey(),
+        pool,
+        pool_config,
+        position_nft_mint.pubkey(),
+        payer.pubkey(),
+    )
+    .instruction();
+    let result = ctx.send_transaction(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer, &vault, &position_nft_mint],
+    );
+    demand_logs_contain(
+        &format!("AddPosition::Position {position} added at index 1"),
+        &result,
+    );
+
+    let registry = get_position_registry(&ctx, vault.pubkey());
+    assert_eq!(registry.position_count, 2);
+    assert_eq!(registry.positions()[1].pool, pool);
+    assert_eq!(registry.positions()[1].position, position);
+
+    // A pool holds a single honorary position
+    let other_nft_mint = Keypair::new();
+    let instruction = AddPositionBuilder::new(
+        vault.pubkey(),
+        pool,
+        pool_config,
+        other_nft_mint.pubkey(),
+        payer.pubkey(),
+    )
+    .instruction();
+    let result = ctx.send_transaction(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer, &vault, &other_nft_mint],
+    );
+    demand_instruction_error(get_ix_err(TollgateError::PoolAlreadyRegistered), &result);
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(primary_fee));
+    set_position_fees(
+        &mut ctx,
+        pool,
+        position,
+        quote_mint,
+        Some(0),
+        Some(extra_fee),
+    );
+
+    // A page cannot open the day
+    let builder = crank_builder(&ctx, key, pos_key, payer.pubkey());
+    let result = ctx.send_transaction(&[builder.crank()], Some(&payer.pubkey()), &[payer]);
+    demand_instruction_error(get_ix_err(TollgateError::DayNotOpened), &result);
+
+    // Every registered position must be claimed
+    let result = ctx.send_transaction(&[builder.claim_fees()], Some(&payer.pubkey()), &[payer]);
+    demand_instruction_error(get_ix_err(TollgateError::InvalidPositionAccounts), &result);
+
+    let extra = vec![ExtraPosition {
+        pool,
+        position_nft_mint: position_nft_mint.pubkey(),
+    }];
+    let result = ctx.send_transaction(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            builder.clone().positions(extra.clone()).claim_fees(),
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+    demand_logs_contain(
+        &format!(
+            "ClaimFees::Claimed quote fees of 2 positions: {}",
+            primary_fee + extra_fee
+        ),
+        &result,
+    );
+
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault.pubkey()).0);
+    assert_eq!(progress.day_state, DayState::New);
+    assert_eq!(progress.cursor, 0);
+    assert_eq!(
+        get_day_history(&ctx, vault.pubkey()).current.fees_claimed,
+        primary_fee + extra_fee
+    );
+
+    // The day is opened once
+    ctx.svm.expire_blockhash();
+    let result = ctx.send_transaction(
+        &[builder.positions(extra).claim_fees()],
+        Some(&payer.pubkey()),
+        &[payer],
+    );
+    demand_instruction_error(get_ix_err(TollgateError::CannotStartNewDay), &result);
+
+    // The pages then run as usual
+    let records = crank_day(&mut ctx, key, pos_key);
+    assert!(matches!(
+        records.last().map(|record| &record.event),
+        Some(TollgateEvent::CreatorPayoutDayClosed(_))
+    ));
+}
@@ -0,0 +1,188 @@
+use anchor_client::solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, program_pack::Pack, pubkey::Pubkey, signer::Signer,
+    system_instruction,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token::spl_token::{self, native_mint},
+};
+use tollgate::{
+    error::TollgateError,
+    utils::token::{get_token_order, TokenOrder},
+};
+use tollgate_client::{pda::get_owner_pda, AddLockedLiquidityBuilder};
+use tollgate_indexer::TollgateEvent;
+
+use crate::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    utils::{
+        damm_v2::{
+            get_liquidity_delta_from_amount_a, get_liquidity_delta_from_amount_b,
+            get_pool_with_config_pda, get_position_pda,
+        },
+        svm::{demand_instruction_error, get_ix_err, TestContext},
+    },
+};
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+fn get_damm_v2_account<T: bytemuck::Pod>(ctx: &TestContext, address: &Pubkey) -> T {
+    let account = ctx.svm.get_account(address).expect("Account not found");
+    bytemuck::try_pod_read_unaligned(account.data.as_slice().split_at(8).1)
+        .expect("Account should decode")
+}
+
+#[test]
+fn test_01_add_locked_liquidity_refunds_the_unused_deposit() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let max_base_amount = 1_000_000;
+    let max_quote_amount = LAMPORTS_PER_SOL / 10;
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let creator = token.creator.insecure_clone();
+    let vault = token.vault.pubkey();
+    let base_mint = token.base_mint.pubkey();
+    let quote_mint = token.quote_mint;
+    let pool = get_pool_with_config_pda(token.pool_config, base_mint, quote_mint).0;
+    let position_nft_mint = token.pos_mints.get(pos_key).unwrap().pubkey();
+    let position = get_position_pda(position_nft_mint).0;
+    let owner = get_owner_pda(vault).0;
+
+    // The creator deposits base tokens and wrapped SOL
+    let wsol_account = get_associated_token_address(&creator.pubkey(), &native_mint::ID);
+    ctx.send_transaction(
+        &[
+            create_associated_token_account_idempotent(
+                &creator.pubkey(),
+                &creator.pubkey(),
+                &native_mint::ID,
+                &spl_token::ID,
+            ),
+            system_instruction::transfer(&creator.pubkey(), &wsol_account, max_quote_amount),
+            spl_token::instruction::sync_native(&spl_token::ID, &wsol_account)
+                .expect("Failed to sync native token"),
+        ],
+        Some(&creator.pubkey()),
+        &[&creator],
+    )
+    .expect("Wrapped SOL should be funded");
+
+    // Half the liquidity the deposit could buy at the current price
+    let pool_state: damm_v2::accounts::Pool = get_damm_v2_account(&ctx, &pool);
+    let (amount_a, amount_b) = match get_token_order(&pool_state, &quote_mint).unwrap() {
+        TokenOrder::A => (max_quote_amount, max_base_amount),
+        TokenOrder::B => (max_base_amount, max_quote_amount),
+    };
+    let liquidity_delta = std::cmp::min(
+        get_liquidity_delta_from_amount_a(amount_a, pool_state.sqrt_price, MAX_SQRT_PRICE).unwrap(),
+        get_liquidity_delta_from_amount_b(amount_b, MIN_SQRT_PRICE, pool_state.sqrt_price).unwrap(),
+    ) / 2;
+
+    let owner_base = get_associated_token_address(&owner, &base_mint);
+    let owner_quote = get_associated_token_address(&owner, &quote_mint);
+    let creator_base = get_associated_token_address(&creator.pubkey(), &base_mint);
+    let owner_balances = (
+        get_token_balance(&ctx, &owner_base),
+        get_token_balance(&ctx, &owner_quote),
+    );
+    let creator_balances = (
+        get_token_balance(&ctx, &creator_base),
+        get_token_balance(&ctx, &wsol_account),
+    );
+    let locked_before = get_damm_v2_account::<damm_v2::accounts::Position>(&ctx, &position)
+        .permanent_locked_liquidity;
+
+    let instruction = AddLockedLiquidityBuilder::new(
+        vault,
+        pool,
+        position_nft_mint,
+        base_mint,
+        quote_mint,
+        creator.pubkey(),
+    )
+    .liquidity(liquidity_delta, max_base_amount, max_quote_amount)
+    .instruction();
+    let meta = ctx
+        .send_transaction(&[instruction], Some(&creator.pubkey()), &[&creator])
+        .expect("Liquidity should be added and locked");
+
+    let locked = ctx
+        .get_events(&meta)
+        .into_iter()
+        .find_map(|event| match event {
+            TollgateEvent::HonoraryLiquidityLocked(e) => Some(e),
+            _ => None,
+        })
+        .expect("HonoraryLiquidityLocked event not found");
+    assert_eq!(locked.vault, vault);
+    assert_eq!(locked.position, position);
+    assert_eq!(locked.depositor, creator.pubkey());
+    assert_eq!(locked.liquidity_delta, liquidity_delta);
+    assert_eq!(
+        locked.permanent_locked_liquidity,
+        locked_before + liquidity_delta
+    );
+    assert!(locked.base_amount > 0 && locked.base_amount < max_base_amount);
+    assert!(locked.quote_amount > 0 && locked.quote_amount < max_quote_amount);
+
+    let position_state: damm_v2::accounts::Position = get_damm_v2_account(&ctx, &position);
+    assert_eq!(
+        position_state.permanent_locked_liquidity,
+        locked_before + liquidity_delta
+    );
+
+    // Only the spent deposit left the creator, the owner balances are untouched
+    assert_eq!(
+        (
+            get_token_balance(&ctx, &owner_base),
+            get_token_balance(&ctx, &owner_quote),
+        ),
+        owner_balances
+    );
+    assert_eq!(
+        (
+            get_token_balance(&ctx, &creator_base),
+            get_token_balance(&ctx, &wsol_account),
+        ),
+        (
+            creator_balances.0 - locked.base_amount,
+            creator_balances.1 - locked.quote_amount,
+        )
+    );
+}
+
+#[test]
+fn test_02_add_locked_liquidity_rejects_zero_liquidity() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let creator = token.creator.insecure_clone();
+    let base_mint = token.base_mint.pubkey();
+    let quote_mint = token.quote_mint;
+    let pool = get_pool_with_config_pda(token.pool_config, base_mint, quote_mint).0;
+
+    let instruction = AddLockedLiquidityBuilder::new(
+        token.vault.pubkey(),
+        pool,
+        token.pos_mints.get(pos_key).unwrap().pubkey(),
+        base_mint,
+        quote_mint,
+        creator.pubkey(),
+    )
+    .liquidity(0, 1_000_000, LAMPORTS_PER_SOL / 10)
+    .instruction();
+    let result = ctx.send_transaction(&[instruction], Some(&creator.pubkey()), &[&creator]);
+    demand_instruction_error(get_ix_err(TollgateError::InvalidLiquidityDelta), &result);
+}
@@ -0,0 +1,690 @@
+use anchor_client::{
+    anchor_lang::Discriminator,
+    solana_sdk::{
+        account::Account, clock::Clock, compute_budget::ComputeBudgetInstruction,
+        native_token::LAMPORTS_PER_SOL, program_option::COption, program_pack::Pack,
+        pubkey::Pubkey, signature::Keypair, signer::Signer,
+    },
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token::spl_token,
+};
+use tollgate::{
+    constants::{damm_v2_constants, TWENTY_FOUR_HOURS},
+    error::TollgateError,
+    state::{Policy, RewardProgress},
+};
+use tollgate_client::{
+    decode_investor_stats, decode_payout_redirect, decode_reward_progress,
+    pda::{
+        get_investor_stats_pda, get_owner_pda, get_payout_redirect_pda, get_policy_pda,
+        get_reward_progress_pda, get_reward_vault_pda,
+    },
+    InvestorStream, RewardCrankBuilder, SetPayoutDestinationBuilder,
+};
+use tollgate_indexer::{RewardClaimed, RewardRoundClosed, SkipReason, TollgateEvent};
+
+use crate::{
+    instructions::{
+        ix_02_crank::{crank_builder, open_day},
+        ix_10_stream_states::update_stream,
+    },
+    utils::{
+        damm_v2::{get_pool_with_config_pda, get_position_pda, set_damm_v2_position_fees},
+        svm::{
+            demand_instruction_error, demand_logs_contain, get_ix_err, get_payer, TestContext,
+            TransactionResult,
+        },
+    },
+};
+
+const REWARD_INDEX: u8 = 0;
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+fn get_damm_v2_account<T: bytemuck::Pod>(ctx: &TestContext, address: &Pubkey) -> T {
+    let account = ctx.svm.get_account(address).expect("Account not found");
+    bytemuck::try_pod_read_unaligned(account.data.as_slice().split_at(8).1)
+        .expect("Account should decode")
+}
+
+fn set_damm_v2_account<T: bytemuck::Pod + Discriminator>(
+    ctx: &mut TestContext,
+    address: Pubkey,
+    state: &T,
+) {
+    let account = ctx.svm.get_account(&address).expect("Account not found");
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(state));
+    ctx.svm
+        .set_account(address, Account { data, ..account })
+        .unwrap();
+}
+
+/// Initializes a reward on the pool whose vault holds `amount`, all of it
+/// pending on `position`.
+fn set_pool_reward(ctx: &mut TestContext, pool: Pubkey, position: Pubkey, amount: u64) -> Pubkey {
+    let reward_mint = ctx.create_spl_token(None, None, amount).pubkey();
+    let reward_vault = get_reward_vault_pda(pool, REWARD_INDEX).0;
+
+    let mut vault_data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: reward_mint,
+        owner: damm_v2_constants::pool_authority::ID,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut vault_data);
+    ctx.svm
+        .set_account(
+            reward_vault,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: vault_data,
+                owner: spl_token::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let mut pool_state: damm_v2::accounts::Pool = get_damm_v2_account(ctx, &pool);
+    let reward_info = &mut pool_state.reward_infos[REWARD_INDEX as usize];
+    reward_info.initialized = 1;
+    reward_info.mint = reward_mint;
+    reward_info.vault = reward_vault;
+    set_damm_v2_account(ctx, pool, &pool_state);
+
+    let mut position_state: damm_v2::accounts::Position = get_damm_v2_account(ctx, &position);
+    position_state.reward_infos[REWARD_INDEX as usize].reward_pendings = amount;
+    set_damm_v2_account(ctx, position, &position_state);
+
+    reward_mint
+}
+
+/// Sends the reward crank of a page of `investors` in crank order.
+fn crank_reward_page(
+    ctx: &mut TestContext,
+    builder: &RewardCrankBuilder,
+    investors: &[InvestorStream],
+    cursor: u32,
+) -> TransactionResult {
+    let payer = get_payer();
+    let end = (cursor as usize + 10).min(investors.len());
+    let instruction = builder
+        .clone()
+        .cursor(cursor)
+        .investors(investors[cursor as usize..end].to_vec())
+        .crank_reward();
+    ctx.send_transaction(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+}
+
+/// Runs a whole pass of the day in pages of 10.
+fn crank_pass(ctx: &mut TestContext, key: &str, pos_key: &str, investors: &[InvestorStream]) {
+    let payer = get_payer();
+    for cursor in (0..investors.len() as u32).step_by(10) {
+        let end = (cursor as usize + 10).min(investors.len());
+        let instruction = crank_builder(ctx, key, pos_key, payer.pubkey())
+            .cursor(cursor)
+            .investors(investors[cursor as usize..end].to_vec())
+            .crank();
+        ctx.send_transaction(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                instruction,
+            ],
+            Some(&payer.pubkey()),
+            &[payer],
+        )
+        .expect("Crank should succeed");
+    }
+}
+
+/// Runs the reward crank of every page of the round, returning its close.
+fn crank_reward_round(
+    ctx: &mut TestContext,
+    builder: &RewardCrankBuilder,
+    investors: &[InvestorStream],
+) -> RewardRoundClosed {
+    let mut closed = None;
+    for cursor in (0..investors.len() as u32).step_by(10) {
+        let meta = crank_reward_page(ctx, builder, investors, cursor)
+            .expect("Reward crank should succeed");
+        closed = closed.or(ctx
+            .get_events(&meta)
+            .into_iter()
+            .find_map(|event| match event {
+                TollgateEvent::RewardRoundClosed(e) => Some(e),
+                _ => None,
+            }));
+    }
+    closed.expect("RewardRoundClosed event not found")
+}
+
+/// Sends `claim_reward`, returning its event.
+fn claim_reward(ctx: &mut TestContext, builder: &RewardCrankBuilder) -> RewardClaimed {
+    let payer = get_payer();
+    let meta = ctx
+        .send_transaction(&[builder.claim_reward()], Some(&payer.pubkey()), &[payer])
+        .expect("Reward should be claimed");
+    ctx.get_events(&meta)
+        .into_iter()
+        .find_map(|event| match event {
+            TollgateEvent::RewardClaimed(e) => Some(e),
+            _ => None,
+        })
+        .expect("RewardClaimed event not found")
+}
+
+/// A reward round opened over the snapshot of a new day.
+struct RewardRound {
+    vault: Pubkey,
+    reward_mint: Pubkey,
+    builder: RewardCrankBuilder,
+    investors: Vec<InvestorStream>,
+    claimed: RewardClaimed,
+}
+
+/// Pends `reward_amount` on the position, creates the reward accounts of the
+/// first page of investors, then opens a day, snapshots it and claims the reward.
+fn open_reward_round(
+    ctx: &mut TestContext,
+    key: &str,
+    pos_key: &str,
+    reward_amount: u64,
+) -> RewardRound {
+    let payer = get_payer();
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let pool = get_pool_with_config_pda(
+        token.pool_config,
+        token.base_mint.pubkey(),
+        token.quote_mint,
+    )
+    .0;
+    let position_nft_mint = token.pos_mints.get(pos_key).unwrap().pubkey();
+    let position = get_position_pda(position_nft_mint).0;
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(vault).0);
+    let investors: Vec<InvestorStream> = token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+
+    let reward_mint = set_pool_reward(ctx, pool, position, reward_amount);
+    let builder = RewardCrankBuilder::new(
+        vault,
+        pool,
+        position_nft_mint,
+        REWARD_INDEX,
+        reward_mint,
+        policy.creator,
+        payer.pubkey(),
+    );
+    let instructions: Vec<_> = investors
+        .iter()
+        .take(10)
+        .map(|investor| {
+            create_associated_token_account_idempotent(
+                &payer.pubkey(),
+                &investor.recipient,
+                &reward_mint,
+                &spl_token::ID,
+            )
+        })
+        .collect();
+    ctx.send_transaction(&instructions, Some(&payer.pubkey()), &[payer])
+        .expect("Reward accounts should be created");
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(ctx, key, pos_key);
+    crank_pass(ctx, key, pos_key, &investors);
+    let claimed = claim_reward(ctx, &builder);
+
+    RewardRound {
+        vault,
+        reward_mint,
+        builder,
+        investors,
+        claimed,
+    }
+}
+
+#[test]
+fn test_01_rewards_are_split_over_the_day_snapshot() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let reward_amount = 1_000_000_000;
+    let payer = get_payer();
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let pool = get_pool_with_config_pda(
+        token.pool_config,
+        token.base_mint.pubkey(),
+        token.quote_mint,
+    )
+    .0;
+    let position_nft_mint = token.pos_mints.get(pos_key).unwrap().pubkey();
+    let position = get_position_pda(position_nft_mint).0;
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(vault).0);
+    let investors: Vec<InvestorStream> = token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+
+    let reward_mint = set_pool_reward(&mut ctx, pool, position, reward_amount);
+    let builder = RewardCrankBuilder::new(
+        vault,
+        pool,
+        position_nft_mint,
+        REWARD_INDEX,
+        reward_mint,
+        policy.creator,
+        payer.pubkey(),
+    );
+
+    // Investors of the first page hold a reward account, the others do not
+    let funded: Vec<Pubkey> = investors
+        .iter()
+        .take(10)
+        .map(|investor| investor.recipient)
+        .collect();
+    let instructions: Vec<_> = funded
+        .iter()
+        .map(|recipient| {
+            create_associated_token_account_idempotent(
+                &payer.pubkey(),
+                recipient,
+                &reward_mint,
+                &spl_token::ID,
+            )
+        })
+        .collect();
+    ctx.send_transaction(&instructions, Some(&payer.pubkey()), &[payer])
+        .expect("Reward accounts should be created");
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+
+    // Rewards wait for the snapshot of the day
+    let result = ctx.send_transaction(&[builder.claim_reward()], Some(&payer.pubkey()), &[payer]);
+    demand_instruction_error(get_ix_err(TollgateError::RewardSnapshotNotReady), &result);
+
+    crank_pass(&mut ctx, key, pos_key, &investors);
+
+    let meta = ctx
+        .send_transaction(&[builder.claim_reward()], Some(&payer.pubkey()), &[payer])
+        .expect("Reward should be claimed");
+    let claimed = ctx
+        .get_events(&meta)
+        .into_iter()
+        .find_map(|event| match event {
+            TollgateEvent::RewardClaimed(e) => Some(e),
+            _ => None,
+        })
+        .expect("RewardClaimed event not found");
+    assert_eq!(claimed.vault, vault);
+    assert_eq!(claimed.reward_mint, reward_mint);
+    assert_eq!(claimed.reward_claimed, reward_amount);
+    assert_eq!(claimed.carry, 0);
+    assert_eq!(claimed.reward_pool, reward_amount);
+    assert!(claimed.investor_pool <= claimed.reward_pool);
+
+    // The round of the day opens once
+    let result = ctx.send_transaction(&[builder.claim_reward()], Some(&payer.pubkey()), &[payer]);
+    demand_instruction_error(get_ix_err(TollgateError::RewardAlreadyClaimed), &result);
+
+    let mut page_payouts = 0;
+    let mut closed = None;
+    for cursor in (0..investors.len() as u32).step_by(10) {
+        let meta = crank_reward_page(&mut ctx, &builder, &investors, cursor)
+            .expect("Reward crank should succeed");
+        for event in ctx.get_events(&meta) {
+            match event {
+                TollgateEvent::RewardPayoutPage(e) => page_payouts += e.payout,
+                TollgateEvent::RewardRoundClosed(e) => closed = Some(e),
+                _ => {}
+            }
+        }
+    }
+    let closed = closed.expect("RewardRoundClosed event not found");
+    assert_eq!(closed.investor_payouts, page_payouts);
+    assert_eq!(
+        closed.investor_payouts + closed.creator_payout + closed.carry,
+        reward_amount
+    );
+    assert_eq!(closed.creator_payout, reward_amount - claimed.investor_pool);
+
+    let paid: u64 = funded
+        .iter()
+        .map(|recipient| {
+            get_token_balance(&ctx, &get_associated_token_address(recipient, &reward_mint))
+        })
+        .sum();
+    assert_eq!(paid, closed.investor_payouts);
+    assert_eq!(
+        get_token_balance(
+            &ctx,
+            &get_associated_token_address(&policy.creator, &reward_mint)
+        ),
+        closed.creator_payout
+    );
+
+    let reward_progress = decode_reward_progress(
+        &ctx.svm
+            .get_account(&get_reward_progress_pda(vault, REWARD_INDEX).0)
+            .unwrap()
+            .data,
+    )
+    .expect("Reward progress should decode");
+    assert_eq!(reward_progress.version, RewardProgress::VERSION);
+    assert!(reward_progress.closed);
+    assert_eq!(reward_progress.total_claimed, reward_amount);
+    assert_eq!(
+        reward_progress.total_distributed,
+        closed.investor_payouts + closed.creator_payout
+    );
+
+    // Cranking a closed round is a no-op
+    crank_reward_page(&mut ctx, &builder, &investors, 0)
+        .expect("Cranking a closed round should succeed");
+
+    // The unpaid allocations stay with the owner for the next round
+    let owner_reward_account = get_associated_token_address(&get_owner_pda(vault).0, &reward_mint);
+    assert_eq!(get_token_balance(&ctx, &owner_reward_account), closed.carry);
+
+    // Finish the quote payouts of the day
+    crank_pass(&mut ctx, key, pos_key, &investors);
+}
+
+#[test]
+fn test_02_stream_stopped_after_the_snapshot_is_carried() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let reward_amount = 1_000_000_000;
+
+    let round = open_reward_round(&mut ctx, key, pos_key, reward_amount);
+    let cancelled = round.investors[0];
+    let original = ctx
+        .svm
+        .get_account(&cancelled.stream)
+        .expect("Stream not found");
+
+    // The stream is cancelled once the round split the snapshot
+    let now = ctx.svm.get_sysvar::<Clock>().unix_timestamp as u64;
+    update_stream(&mut ctx, cancelled.stream, |contract| {
+        contract.canceled_at = now;
+    });
+
+    // The round still closes, the stream share staying with the owner
+    let closed = crank_reward_round(&mut ctx, &round.builder, &round.investors);
+    assert_eq!(
+        closed.investor_payouts + closed.creator_payout + closed.carry,
+        reward_amount
+    );
+    assert_eq!(
+        closed.creator_payout,
+        reward_amount - round.claimed.investor_pool
+    );
+    let cancelled_account = get_associated_token_address(&cancelled.recipient, &round.reward_mint);
+    assert_eq!(get_token_balance(&ctx, &cancelled_account), 0);
+    let paid: u64 = round.investors[1..10]
+        .iter()
+        .map(|investor| {
+            get_token_balance(
+                &ctx,
+                &get_associated_token_address(&investor.recipient, &round.reward_mint),
+            )
+        })
+        .sum();
+    assert!(paid > 0);
+    assert_eq!(paid, closed.investor_payouts);
+    let owner_reward_account =
+        get_associated_token_address(&get_owner_pda(round.vault).0, &round.reward_mint);
+    assert_eq!(get_token_balance(&ctx, &owner_reward_account), closed.carry);
+
+    ctx.svm.set_account(cancelled.stream, original).unwrap();
+    crank_pass(&mut ctx, key, pos_key, &round.investors);
+}
+
+#[test]
+fn test_03_reward_pages_follow_the_round_cursor() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let round = open_reward_round(&mut ctx, key, pos_key, 1_000_000_000);
+    let reward_progress_pda = get_reward_progress_pda(round.vault, REWARD_INDEX).0;
+
+    // Pages cannot be skipped
+    let result = crank_reward_page(&mut ctx, &round.builder, &round.investors, 10);
+    demand_instruction_error(get_ix_err(TollgateError::PaginationCursorTooLarge), &result);
+
+    crank_reward_page(&mut ctx, &round.builder, &round.investors, 0)
+        .expect("Reward crank should succeed");
+
+    // A page sent twice is a no-op
+    let result = crank_reward_page(&mut ctx, &round.builder, &round.investors, 0);
+    demand_logs_contain("CrankReward::Cursor behind progress, skipping", &result);
+    let reward_progress =
+        decode_reward_progress(&ctx.svm.get_account(&reward_progress_pda).unwrap().data)
+            .expect("Reward progress should decode");
+    assert_eq!(reward_progress.cursor, 10);
+    assert!(!reward_progress.closed);
+
+    crank_pass(&mut ctx, key, pos_key, &round.investors);
+}
+
+#[test]
+fn test_04_round_left_open_is_carried_into_the_next_one() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let reward_amount = 1_000_000_000;
+
+    let round = open_reward_round(&mut ctx, key, pos_key, reward_amount);
+    let meta = crank_reward_page(&mut ctx, &round.builder, &round.investors, 0)
+        .expect("Reward crank should succeed");
+    let first_page = ctx
+        .get_events(&meta)
+        .into_iter()
+        .find_map(|event| match event {
+            TollgateEvent::RewardPayoutPage(e) => Some(e),
+            _ => None,
+        })
+        .expect("RewardPayoutPage event not found");
+
+    // The quote payouts close the day with the round still open
+    crank_pass(&mut ctx, key, pos_key, &round.investors);
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+    crank_pass(&mut ctx, key, pos_key, &round.investors);
+
+    // Nothing new is pending, the unpaid rewards of the open round are split again
+    let claimed = claim_reward(&mut ctx, &round.builder);
+    assert_eq!(claimed.reward_claimed, 0);
+    assert_eq!(claimed.carry, reward_amount - first_page.payout);
+    assert_eq!(claimed.reward_pool, claimed.carry);
+
+    let reward_progress = decode_reward_progress(
+        &ctx.svm
+            .get_account(&get_reward_progress_pda(round.vault, REWARD_INDEX).0)
+            .unwrap()
+            .data,
+    )
+    .expect("Reward progress should decode");
+    assert_eq!(reward_progress.total_claimed, reward_amount);
+    assert_eq!(reward_progress.total_distributed, first_page.payout);
+    assert!(!reward_progress.closed);
+
+    let closed = crank_reward_round(&mut ctx, &round.builder, &round.investors);
+    assert_eq!(
+        closed.investor_payouts + closed.creator_payout + closed.carry,
+        claimed.reward_pool
+    );
+
+    crank_pass(&mut ctx, key, pos_key, &round.investors);
+}
+
+#[test]
+fn test_05_reward_round_pays_each_stream_once() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let round = open_reward_round(&mut ctx, key, pos_key, 1_000_000_000);
+
+    // A stream repeated within a page is rejected
+    let mut duplicated = round.investors.clone();
+    duplicated[1] = duplicated[0];
+    let result = crank_reward_page(&mut ctx, &round.builder, &duplicated, 0);
+    demand_instruction_error(get_ix_err(TollgateError::DuplicateInvestorStream), &result);
+
+    // And so is a stream of an earlier page
+    crank_reward_page(&mut ctx, &round.builder, &round.investors, 0)
+        .expect("Reward crank should succeed");
+    let mut replayed = round.investors.clone();
+    replayed[10] = replayed[9];
+    let result = crank_reward_page(&mut ctx, &round.builder, &replayed, 10);
+    demand_instruction_error(get_ix_err(TollgateError::DuplicateInvestorStream), &result);
+
+    crank_reward_round(&mut ctx, &round.builder, &round.investors);
+    crank_pass(&mut ctx, key, pos_key, &round.investors);
+}
+
+#[test]
+fn test_06_rewards_follow_the_payout_redirect() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    let mut round = open_reward_round(&mut ctx, key, pos_key, 1_000_000_000);
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let quote_mint = token.quote_mint;
+    let recipient = token.investors[0].key.clone();
+    let stream = round.investors[0].stream;
+
+    // The recipient redirects its payouts to a quote account owned by someone else
+    let owner = Keypair::new();
+    let destination = get_associated_token_address(&owner.pubkey(), &quote_mint);
+    let owner_reward_account = get_associated_token_address(&owner.pubkey(), &round.reward_mint);
+    ctx.send_transaction(
+        &[
+            create_associated_token_account_idempotent(
+                &payer.pubkey(),
+                &owner.pubkey(),
+                &quote_mint,
+                &spl_token::ID,
+            ),
+            create_associated_token_account_idempotent(
+                &payer.pubkey(),
+                &owner.pubkey(),
+                &round.reward_mint,
+                &spl_token::ID,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+    .expect("Destination accounts should be created");
+    ctx.airdrop(&recipient.pubkey(), 1).unwrap();
+    let instruction =
+        SetPayoutDestinationBuilder::new(round.vault, stream, destination, recipient.pubkey())
+            .instruction();
+    ctx.send_transaction(&[instruction], Some(&recipient.pubkey()), &[&recipient])
+        .expect("Destination should be set");
+
+    // Paying the reward ATA of a redirected investor is rejected
+    let result = crank_reward_page(&mut ctx, &round.builder, &round.investors, 0);
+    demand_instruction_error(get_ix_err(TollgateError::InvalidPayoutDestination), &result);
+
+    let redirect = decode_payout_redirect(
+        &ctx.svm
+            .get_account(&get_payout_redirect_pda(round.vault, stream).0)
+            .unwrap()
+            .data,
+    )
+    .expect("Payout redirect should decode");
+    round.investors[0] = round.investors[0].with_redirect(Some(&redirect));
+
+    let mut paid = Vec::new();
+    let mut skipped = Vec::new();
+    for cursor in (0..round.investors.len() as u32).step_by(10) {
+        let meta = crank_reward_page(&mut ctx, &round.builder, &round.investors, cursor)
+            .expect("Reward crank should succeed");
+        for event in ctx.get_events(&meta) {
+            match event {
+                TollgateEvent::RewardInvestorPaid(e) => paid.push(e),
+                TollgateEvent::RewardInvestorSkipped(e) => skipped.push(e),
+                _ => {}
+            }
+        }
+    }
+
+    // The share of the redirected investor goes to the reward ATA of the destination owner
+    let redirected = paid
+        .iter()
+        .find(|e| e.stream == stream)
+        .expect("RewardInvestorPaid event not found");
+    assert_eq!(redirected.index, 0);
+    assert_eq!(redirected.recipient, recipient.pubkey());
+    assert_eq!(redirected.reward_account, owner_reward_account);
+    assert!(redirected.amount > 0);
+    assert_eq!(
+        get_token_balance(&ctx, &owner_reward_account),
+        redirected.amount
+    );
+    let recipient_reward_account =
+        get_associated_token_address(&recipient.pubkey(), &round.reward_mint);
+    assert_eq!(get_token_balance(&ctx, &recipient_reward_account), 0);
+
+    // Every payout is recorded in the investor stats
+    for e in &paid {
+        let stats = decode_investor_stats(
+            &ctx.svm
+                .get_account(&get_investor_stats_pda(round.vault, e.stream).0)
+                .unwrap()
+                .data,
+        )
+        .expect("Investor stats should decode");
+        assert_eq!(stats.reward_paid[REWARD_INDEX as usize], e.amount);
+    }
+
+    // Investors past the first page have no reward ATA, so their share is carried
+    assert_eq!(paid.len() + skipped.len(), round.investors.len());
+    assert!(skipped
+        .iter()
+        .all(|e| e.index >= 10 && e.reason == SkipReason::MissingAta));
+
+    crank_pass(&mut ctx, key, pos_key, &round.investors);
+}
@@ -0,0 +1,161 @@
+use anchor_client::{
+    anchor_lang::Discriminator,
+    solana_sdk::{
+        account::Account, compute_budget::ComputeBudgetInstruction, native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack, pubkey::Pubkey, signer::Signer,
+    },
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    state::{DayState, Policy, Progress},
+};
+use tollgate_client::{
+    pda::{get_owner_pda, get_policy_pda, get_progress_pda},
+    InvestorStream,
+};
+use tollgate_indexer::TollgateEvent;
+
+use crate::{
+    instructions::{
+        ix_02_crank::{claim_fees_ix, crank_builder},
+        ix_06_preview::simulate_preview,
+    },
+    utils::{
+        damm_v2::{get_pool_with_config_pda, get_position_pda, set_damm_v2_position_fees},
+        svm::{get_payer, TestContext},
+    },
+};
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+fn get_damm_v2_account<T: bytemuck::Pod>(ctx: &TestContext, address: &Pubkey) -> T {
+    let account = ctx.svm.get_account(address).expect("Account not found");
+    bytemuck::try_pod_read_unaligned(account.data.as_slice().split_at(8).1)
+        .expect("Account should decode")
+}
+
+/// Overwrites the status of a DAMM v2 pool, `0` being enabled.
+fn set_pool_status(ctx: &mut TestContext, pool: Pubkey, pool_status: u8) {
+    let account = ctx.svm.get_account(&pool).expect("Pool not found");
+    let mut pool_state: damm_v2::accounts::Pool = get_damm_v2_account(ctx, &pool);
+    pool_state.pool_status = pool_status;
+
+    let mut data = damm_v2::accounts::Pool::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&pool_state));
+    ctx.svm
+        .set_account(pool, Account { data, ..account })
+        .unwrap();
+}
+
+/// Runs the remaining passes of the day in pages of 10, until it closes.
+fn crank_day(ctx: &mut TestContext, key: &str, pos_key: &str, investors: &[InvestorStream]) {
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).unwrap().vault.pubkey();
+    for _ in 0..2 {
+        for cursor in (0..investors.len() as u32).step_by(10) {
+            if ctx
+                .get_program_account::<Progress>(&get_progress_pda(vault).0)
+                .day_state
+                == DayState::Closed
+            {
+                return;
+            }
+            let end = (cursor as usize + 10).min(investors.len());
+            let instruction = crank_builder(ctx, key, pos_key, payer.pubkey())
+                .cursor(cursor)
+                .investors(investors[cursor as usize..end].to_vec())
+                .crank();
+            ctx.send_transaction(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    instruction,
+                ],
+                Some(&payer.pubkey()),
+                &[payer],
+            )
+            .expect("Crank should succeed");
+        }
+    }
+}
+
+#[test]
+fn test_01_disabled_pool_distributes_the_held_balance() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let pending_fee = LAMPORTS_PER_SOL / 4;
+    let payer = get_payer();
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let quote_mint = token.quote_mint;
+    let pool = get_pool_with_config_pda(token.pool_config, token.base_mint.pubkey(), quote_mint).0;
+    let position = get_position_pda(token.pos_mints.get(pos_key).unwrap().pubkey()).0;
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(vault).0);
+    let investors: Vec<InvestorStream> = token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+    let owner_quote = get_associated_token_address(&get_owner_pda(vault).0, &quote_mint);
+
+    // Close the day in progress before the pool is disabled
+    crank_day(&mut ctx, key, pos_key, &investors);
+
+    set_pool_status(&mut ctx, pool, 1);
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(pending_fee));
+
+    // The preview does not count the fees of the disabled pool either
+    let preview = simulate_preview(&ctx, key, pos_key, 0);
+    assert_eq!(preview.claimable_quote_fee, 0);
+
+    let instruction = claim_fees_ix(&ctx, key, pos_key, payer.pubkey());
+    let meta = ctx
+        .send_transaction(&[instruction], Some(&payer.pubkey()), &[payer])
+        .expect("Claim fees should succeed on a disabled pool");
+    let events = ctx.get_events(&meta);
+
+    let disabled = events
+        .iter()
+        .find_map(|event| match event {
+            TollgateEvent::PoolDisabled(e) => Some(e),
+            _ => None,
+        })
+        .expect("PoolDisabled event not found");
+    assert_eq!(disabled.vault, vault);
+    assert_eq!(disabled.pool, pool);
+    assert_eq!(disabled.position, position);
+    assert_eq!(disabled.pool_status, 1);
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, TollgateEvent::QuoteFeesClaimed(_))));
+
+    // The fees stay pending, the day distributes the balance already held
+    let position_state: damm_v2::accounts::Position = get_damm_v2_account(&ctx, &position);
+    assert_eq!(
+        position_state.fee_a_pending + position_state.fee_b_pending,
+        pending_fee
+    );
+    let held = get_token_balance(&ctx, &owner_quote);
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(
+        progress.distributable,
+        policy.daily_cap.map_or(held, |cap| held.min(cap))
+    );
+    assert_eq!(progress.distributable, preview.distributable);
+
+    crank_day(&mut ctx, key, pos_key, &investors);
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.day_state, DayState::Closed);
+    assert!(get_token_balance(&ctx, &owner_quote) <= held);
+
+    set_pool_status(&mut ctx, pool, 0);
+}
@@ -0,0 +1,154 @@
+use anchor_client::solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, native_token::LAMPORTS_PER_SOL, program_pack::Pack,
+    pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    error::TollgateError,
+    state::{DayState, Policy, Progress},
+};
+use tollgate_client::{
+    pda::{get_policy_pda, get_progress_pda},
+    InvestorStream, SetCreatorBuilder,
+};
+use tollgate_indexer::{CreatorPayoutDayClosed, TollgateEvent};
+
+use crate::{
+    instructions::ix_02_crank::{crank_builder, open_day},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{demand_instruction_error, get_ix_err, get_payer, TestContext},
+    },
+};
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+/// Runs the remaining passes of the day in pages of 10, returning its close
+/// event once it closes.
+fn crank_day(
+    ctx: &mut TestContext,
+    key: &str,
+    pos_key: &str,
+    investors: &[InvestorStream],
+) -> Option<CreatorPayoutDayClosed> {
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).unwrap().vault.pubkey();
+    for _ in 0..2 {
+        for cursor in (0..investors.len() as u32).step_by(10) {
+            if ctx
+                .get_program_account::<Progress>(&get_progress_pda(vault).0)
+                .day_state
+                == DayState::Closed
+            {
+                return None;
+            }
+            let end = (cursor as usize + 10).min(investors.len());
+            let instruction = crank_builder(ctx, key, pos_key, payer.pubkey())
+                .cursor(cursor)
+                .investors(investors[cursor as usize..end].to_vec())
+                .crank();
+            let meta = ctx
+                .send_transaction(
+                    &[
+                        ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                        instruction,
+                    ],
+                    Some(&payer.pubkey()),
+                    &[payer],
+                )
+                .expect("Crank should succeed");
+            let closed = ctx
+                .get_events(&meta)
+                .into_iter()
+                .find_map(|event| match event {
+                    TollgateEvent::CreatorPayoutDayClosed(e) => Some(e),
+                    _ => None,
+                });
+            if closed.is_some() {
+                return closed;
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn test_01_set_creator_rejects_the_default_pubkey() {
+    let mut ctx = TestContext::default();
+    let payer = get_payer();
+    let vault = ctx.tokens.get("tollgate").unwrap().vault.insecure_clone();
+
+    let instruction = SetCreatorBuilder::new(vault.pubkey(), Pubkey::default()).instruction();
+    let result = ctx.send_transaction(&[instruction], Some(&payer.pubkey()), &[payer, &vault]);
+    demand_instruction_error(get_ix_err(TollgateError::InvalidCreator), &result);
+}
+
+#[test]
+fn test_02_creator_payouts_follow_the_new_creator() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let payer = get_payer();
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.insecure_clone();
+    let quote_mint = token.quote_mint;
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(vault.pubkey()).0);
+    let investors: Vec<InvestorStream> = token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+
+    // Close the day in progress before the creator changes
+    crank_day(&mut ctx, key, pos_key, &investors);
+
+    // The treasury holds no quote account yet
+    let treasury = Keypair::new().pubkey();
+    let instruction = SetCreatorBuilder::new(vault.pubkey(), treasury).instruction();
+    let meta = ctx
+        .send_transaction(&[instruction], Some(&payer.pubkey()), &[payer, &vault])
+        .expect("Creator should be set");
+    let updated = ctx
+        .get_events(&meta)
+        .into_iter()
+        .find_map(|event| match event {
+            TollgateEvent::CreatorUpdated(e) => Some(e),
+            _ => None,
+        })
+        .expect("CreatorUpdated event not found");
+    assert_eq!(updated.vault, vault.pubkey());
+    assert_eq!(updated.previous_creator, policy.creator);
+    assert_eq!(updated.creator, treasury);
+    assert_eq!(
+        ctx.get_program_account::<Policy>(&get_policy_pda(vault.pubkey()).0)
+            .creator,
+        treasury
+    );
+
+    let previous_account = get_associated_token_address(&policy.creator, &quote_mint);
+    let previous_balance = get_token_balance(&ctx, &previous_account);
+    let treasury_account = get_associated_token_address(&treasury, &quote_mint);
+
+    // Opening the day creates the treasury quote account, paid by the cranker
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+    assert!(ctx.svm.get_account(&treasury_account).is_some());
+
+    let closed = crank_day(&mut ctx, key, pos_key, &investors);
+    let creator_payout = closed.map_or(0, |closed| closed.creator_payout);
+    assert!(get_token_balance(&ctx, &treasury_account) >= creator_payout);
+    assert_eq!(get_token_balance(&ctx, &previous_account), previous_balance);
+
+    let instruction = SetCreatorBuilder::new(vault.pubkey(), policy.creator).instruction();
+    ctx.send_transaction(&[instruction], Some(&payer.pubkey()), &[payer, &vault])
+        .expect("Creator should be restored");
+}
@@ -0,0 +1,232 @@
+use anchor_client::{
+    anchor_lang::{AccountDeserialize, AccountSerialize},
+    solana_sdk::{
+        account::Account, compute_budget::ComputeBudgetInstruction, native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack, pubkey::Pubkey, signer::Signer,
+    },
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    state::{CarryPolicy, DayState, Policy, Progress},
+};
+use tollgate_client::{
+    pda::{get_owner_pda, get_policy_pda, get_progress_pda},
+    InvestorStream,
+};
+use tollgate_indexer::{RemainderSettled, TollgateEvent};
+
+use crate::{
+    instructions::ix_02_crank::{claim_fees_ix, crank_builder},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{get_payer, TestContext},
+    },
+};
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+/// Overwrites a program account after applying `update`.
+fn update_account<T: AccountSerialize + AccountDeserialize>(
+    ctx: &mut TestContext,
+    address: Pubkey,
+    update: impl FnOnce(&mut T),
+) {
+    let account = ctx.svm.get_account(&address).expect("Account not found");
+    let mut state = ctx.get_program_account::<T>(&address);
+    update(&mut state);
+
+    let mut data = Vec::with_capacity(account.data.len());
+    state.try_serialize(&mut data).unwrap();
+    data.resize(account.data.len(), 0);
+    ctx.svm
+        .set_account(address, Account { data, ..account })
+        .unwrap();
+}
+
+/// Runs the remaining passes of the day in pages of 10, until it closes.
+fn crank_day(ctx: &mut TestContext, key: &str, pos_key: &str, investors: &[InvestorStream]) {
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).unwrap().vault.pubkey();
+    for _ in 0..2 {
+        for cursor in (0..investors.len() as u32).step_by(10) {
+            if ctx
+                .get_program_account::<Progress>(&get_progress_pda(vault).0)
+                .day_state
+                == DayState::Closed
+            {
+                return;
+            }
+            let end = (cursor as usize + 10).min(investors.len());
+            let instruction = crank_builder(ctx, key, pos_key, payer.pubkey())
+                .cursor(cursor)
+                .investors(investors[cursor as usize..end].to_vec())
+                .crank();
+            ctx.send_transaction(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    instruction,
+                ],
+                Some(&payer.pubkey()),
+                &[payer],
+            )
+            .expect("Crank should succeed");
+        }
+    }
+}
+
+/// Opens the next day with `quote_fee` pending, returning the settlement of
+/// the previous day remainder.
+fn open_next_day(
+    ctx: &mut TestContext,
+    key: &str,
+    pos_key: &str,
+    quote_fee: u64,
+) -> Option<RemainderSettled> {
+    let payer = get_payer();
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(ctx, key, pos_key, Some(0), Some(quote_fee));
+
+    let instruction = claim_fees_ix(ctx, key, pos_key, payer.pubkey());
+    let meta = ctx
+        .send_transaction(&[instruction], Some(&payer.pubkey()), &[payer])
+        .expect("Claim fees should succeed");
+    ctx.get_events(&meta)
+        .into_iter()
+        .find_map(|event| match event {
+            TollgateEvent::RemainderSettled(e) => Some(e),
+            _ => None,
+        })
+}
+
+#[test]
+fn test_01_excess_over_the_cap_rolls_over() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let quote_fee = LAMPORTS_PER_SOL / 4;
+    let daily_cap = quote_fee / 2;
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let quote_mint = token.quote_mint;
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(vault).0);
+    let investors: Vec<InvestorStream> = token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+    let owner_quote = get_associated_token_address(&get_owner_pda(vault).0, &quote_mint);
+    let creator_quote = get_associated_token_address(&policy.creator, &quote_mint);
+
+    // Close the day in progress before the policy changes
+    crank_day(&mut ctx, key, pos_key, &investors);
+    update_account(&mut ctx, get_policy_pda(vault).0, |policy: &mut Policy| {
+        policy.daily_cap = Some(daily_cap);
+        policy.carry_policy = CarryPolicy::RollOver;
+    });
+
+    // The day is capped, the excess stays with the owner
+    open_next_day(&mut ctx, key, pos_key, quote_fee);
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.distributable, daily_cap);
+    crank_day(&mut ctx, key, pos_key, &investors);
+
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    let remainder = get_token_balance(&ctx, &owner_quote) - progress.carry;
+    assert!(remainder > 0);
+    let creator_balance = get_token_balance(&ctx, &creator_quote);
+
+    // The excess is rolled into the next day instead of the creator
+    let settled =
+        open_next_day(&mut ctx, key, pos_key, quote_fee).expect("RemainderSettled event not found");
+    assert_eq!(settled.vault, vault);
+    assert_eq!(settled.remainder, remainder);
+    assert_eq!(settled.swept, 0);
+    assert_eq!(settled.rolled_over, remainder);
+    assert_eq!(settled.reserved, 0);
+    assert_eq!(get_token_balance(&ctx, &creator_quote), creator_balance);
+
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.distributable, daily_cap);
+    crank_day(&mut ctx, key, pos_key, &investors);
+}
+
+#[test]
+fn test_02_remainder_is_released_from_the_reserve() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let quote_fee = LAMPORTS_PER_SOL / 4;
+    let release_days = 4;
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let quote_mint = token.quote_mint;
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(vault).0);
+    let investors: Vec<InvestorStream> = token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect();
+    let owner_quote = get_associated_token_address(&get_owner_pda(vault).0, &quote_mint);
+
+    crank_day(&mut ctx, key, pos_key, &investors);
+    update_account(&mut ctx, get_policy_pda(vault).0, |policy: &mut Policy| {
+        policy.daily_cap = None;
+        policy.carry_policy = CarryPolicy::Reserve { release_days };
+    });
+
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    let carry = progress.carry;
+    let remainder = get_token_balance(&ctx, &owner_quote) - carry - progress.reserve;
+    assert!(remainder > 0);
+
+    // The remainder is held back, a share of the reserve joins the day
+    let settled =
+        open_next_day(&mut ctx, key, pos_key, quote_fee).expect("RemainderSettled event not found");
+    let released = remainder.div_ceil(release_days as u64);
+    assert_eq!(settled.remainder, remainder);
+    assert_eq!(settled.swept, 0);
+    assert_eq!(settled.reserved, remainder);
+    assert_eq!(settled.released, released);
+    assert_eq!(settled.reserve, remainder - released);
+
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.reserve, remainder - released);
+    assert_eq!(progress.reserve_release, released);
+    assert_eq!(progress.distributable, quote_fee + carry + released);
+    crank_day(&mut ctx, key, pos_key, &investors);
+
+    // The next day releases the next share of the reserve
+    let settled =
+        open_next_day(&mut ctx, key, pos_key, 0).expect("RemainderSettled event not found");
+    assert_eq!(settled.released, released);
+    assert_eq!(settled.reserve, remainder - 2 * released);
+    crank_day(&mut ctx, key, pos_key, &investors);
+
+    // Restore the policy, the rest of the reserve is swept with the next remainder
+    update_account(
+        &mut ctx,
+        get_policy_pda(vault).0,
+        |restored: &mut Policy| {
+            restored.daily_cap = policy.daily_cap;
+            restored.carry_policy = policy.carry_policy.clone();
+        },
+    );
+    update_account(
+        &mut ctx,
+        get_progress_pda(vault).0,
+        |progress: &mut Progress| {
+            progress.reserve = 0;
+            progress.reserve_release = 0;
+        },
+    );
+}
@@ -0,0 +1,178 @@
+use anchor_client::{
+    anchor_lang::{AccountDeserialize, AccountSerialize},
+    solana_sdk::{
+        account::Account, compute_budget::ComputeBudgetInstruction, native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey, signer::Signer,
+    },
+};
+use tollgate::{
+    constants::{MAX_BPS, TWENTY_FOUR_HOURS},
+    state::{CapExcess, CapLimit, CrankPhase, DayState, InvestorCap, Policy, Progress},
+};
+use tollgate_client::{
+    pda::{get_policy_pda, get_progress_pda},
+    InvestorStream,
+};
+use tollgate_indexer::{InvestorCapped, TollgateEvent};
+
+use crate::{
+    instructions::ix_02_crank::{crank_builder, open_day},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{get_payer, TestContext},
+    },
+};
+
+/// Overwrites a program account after applying `update`.
+fn update_account<T: AccountSerialize + AccountDeserialize>(
+    ctx: &mut TestContext,
+    address: Pubkey,
+    update: impl FnOnce(&mut T),
+) {
+    let account = ctx.svm.get_account(&address).expect("Account not found");
+    let mut state = ctx.get_program_account::<T>(&address);
+    update(&mut state);
+
+    let mut data = Vec::with_capacity(account.data.len());
+    state.try_serialize(&mut data).unwrap();
+    data.resize(account.data.len(), 0);
+    ctx.svm
+        .set_account(address, Account { data, ..account })
+        .unwrap();
+}
+
+/// Cranks the day from the progress cursor in pages of 10 until it closes,
+/// returning the capped investors reported along the way.
+fn crank_day(
+    ctx: &mut TestContext,
+    key: &str,
+    pos_key: &str,
+    investors: &[InvestorStream],
+) -> Vec<InvestorCapped> {
+    let payer = get_payer();
+    let vault = ctx.tokens.get(key).unwrap().vault.pubkey();
+    let mut capped = vec![];
+    // A snapshot pass, up to one cap pass per investor and a payout pass
+    for _ in 0..(investors.len() + 2) * investors.len().div_ceil(10) {
+        let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+        if progress.day_state == DayState::Closed {
+            return capped;
+        }
+        let cursor = progress.cursor as usize;
+        let end = (cursor + 10).min(investors.len());
+        let instruction = crank_builder(ctx, key, pos_key, payer.pubkey())
+            .cursor(cursor as u32)
+            .investors(investors[cursor..end].to_vec())
+            .crank();
+        let meta = ctx
+            .send_transaction(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    instruction,
+                ],
+                Some(&payer.pubkey()),
+                &[payer],
+            )
+            .expect("Crank should succeed");
+        capped.extend(
+            ctx.get_events(&meta)
+                .into_iter()
+                .filter_map(|event| match event {
+                    TollgateEvent::InvestorCapped(e) => Some(e),
+                    _ => None,
+                }),
+        );
+    }
+    panic!("The day should close");
+}
+
+/// Sets the investor cap to about an even share of the investor pool, so
+/// that the investors locking more than the average are capped.
+fn set_investor_cap(ctx: &mut TestContext, vault: Pubkey, excess: CapExcess) {
+    update_account(ctx, get_policy_pda(vault).0, |policy: &mut Policy| {
+        let bps = (MAX_BPS as u32 / policy.investor_count).max(1) as u16;
+        policy.investor_cap = Some(InvestorCap {
+            limit: CapLimit::Bps { bps },
+            excess,
+        });
+    });
+}
+
+fn investor_streams(ctx: &TestContext, key: &str) -> Vec<InvestorStream> {
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(token.vault.pubkey()).0);
+    token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect()
+}
+
+#[test]
+fn test_01_cap_excess_is_redistributed() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let vault = ctx.tokens.get(key).unwrap().vault.pubkey();
+    let investors = investor_streams(&ctx, key);
+
+    // Close the day in progress before the policy changes
+    crank_day(&mut ctx, key, pos_key, &investors);
+    set_investor_cap(&mut ctx, vault, CapExcess::Redistribute);
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+    let capped = crank_day(&mut ctx, key, pos_key, &investors);
+
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.phase, CrankPhase::Payout);
+    assert_eq!(capped.len(), progress.capped_count as usize);
+    for event in &capped {
+        assert_eq!(event.vault, vault);
+        assert_eq!(Some(event.cap), progress.investor_cap);
+        assert!(event.share >= event.cap);
+    }
+    if !capped.is_empty() && progress.investor_cap < Some(progress.investor_pool) {
+        // The level settles once a pass caps no new investor
+        assert!(progress.cap_passes >= 2);
+    }
+
+    // The excess of the capped investors goes to the uncapped ones
+    if progress.capped_locked < progress.locked_total {
+        assert_eq!(progress.allocated, progress.investor_pool);
+    }
+
+    update_account(&mut ctx, get_policy_pda(vault).0, |policy: &mut Policy| {
+        policy.investor_cap = None
+    });
+}
+
+#[test]
+fn test_02_cap_excess_is_carried_over() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let vault = ctx.tokens.get(key).unwrap().vault.pubkey();
+    let investors = investor_streams(&ctx, key);
+
+    crank_day(&mut ctx, key, pos_key, &investors);
+    set_investor_cap(&mut ctx, vault, CapExcess::Carry);
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+    let capped = crank_day(&mut ctx, key, pos_key, &investors);
+
+    // No cap pass runs, the capped investors keep the excess out of the day
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.cap_passes, 0);
+    let excess: u64 = capped.iter().map(|event| event.share - event.cap).sum();
+    assert!(progress.investor_pool - progress.allocated >= excess);
+    assert!(progress.carry >= progress.investor_pool - progress.allocated);
+
+    update_account(&mut ctx, get_policy_pda(vault).0, |policy: &mut Policy| {
+        policy.investor_cap = None
+    });
+}
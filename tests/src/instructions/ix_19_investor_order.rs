@@ -0,0 +1,138 @@
+use anchor_client::solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, native_token::LAMPORTS_PER_SOL, signer::Signer,
+};
+use tollgate::{
+    constants::TWENTY_FOUR_HOURS,
+    error::TollgateError,
+    state::{CrankPhase, DayState, Policy, Progress},
+};
+use tollgate_client::{
+    pda::{get_policy_pda, get_progress_pda},
+    sort_investors, InvestorStream,
+};
+
+use crate::{
+    instructions::ix_02_crank::{crank_builder, open_day},
+    utils::{
+        damm_v2::set_damm_v2_position_fees,
+        svm::{demand_instruction_error, get_ix_err, get_payer, TestContext, TransactionResult},
+    },
+};
+
+/// Sends the crank of `investors` as a page at `cursor`.
+fn crank_page(
+    ctx: &mut TestContext,
+    key: &str,
+    pos_key: &str,
+    cursor: u32,
+    investors: Vec<InvestorStream>,
+) -> TransactionResult {
+    let payer = get_payer();
+    let instruction = crank_builder(ctx, key, pos_key, payer.pubkey())
+        .cursor(cursor)
+        .investors(investors)
+        .crank();
+    ctx.send_transaction(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+    )
+}
+
+/// Cranks the day from the progress cursor in pages of 10 until it closes.
+fn crank_day(ctx: &mut TestContext, key: &str, pos_key: &str, investors: &[InvestorStream]) {
+    let vault = ctx.tokens.get(key).unwrap().vault.pubkey();
+    loop {
+        let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+        if progress.day_state == DayState::Closed {
+            return;
+        }
+        let cursor = progress.cursor as usize;
+        let end = (cursor + 10).min(investors.len());
+        crank_page(
+            ctx,
+            key,
+            pos_key,
+            cursor as u32,
+            investors[cursor..end].to_vec(),
+        )
+        .expect("Crank should succeed");
+    }
+}
+
+fn investor_streams(ctx: &TestContext, key: &str) -> Vec<InvestorStream> {
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(token.vault.pubkey()).0);
+    token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| InvestorStream::new(investor.key.pubkey(), investor.stream.pubkey()))
+        .collect()
+}
+
+#[test]
+fn test_01_investors_are_sorted_by_stream() {
+    let ctx = TestContext::default();
+    let investors = investor_streams(&ctx, "tollgate");
+
+    let mut sorted: Vec<InvestorStream> = investors.iter().rev().copied().collect();
+    sort_investors(&mut sorted).expect("Distinct streams should sort");
+    assert_eq!(sorted, investors);
+
+    let mut duplicated = vec![investors[1], investors[0], investors[1]];
+    assert!(sort_investors(&mut duplicated).is_err());
+}
+
+#[test]
+fn test_02_streams_out_of_order_are_rejected() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+    let vault = ctx.tokens.get(key).unwrap().vault.pubkey();
+    let investors = investor_streams(&ctx, key);
+
+    crank_day(&mut ctx, key, pos_key, &investors);
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    open_day(&mut ctx, key, pos_key);
+
+    // A stream listed twice within a page
+    let mut page = investors[..10].to_vec();
+    page[5] = page[4];
+    let result = crank_page(&mut ctx, key, pos_key, 0, page);
+    demand_instruction_error(get_ix_err(TollgateError::DuplicateInvestorStream), &result);
+
+    // Streams in decreasing order within a page
+    let mut page = investors[..10].to_vec();
+    page.swap(3, 4);
+    let result = crank_page(&mut ctx, key, pos_key, 0, page);
+    demand_instruction_error(
+        get_ix_err(TollgateError::InvestorStreamsOutOfOrder),
+        &result,
+    );
+
+    crank_page(&mut ctx, key, pos_key, 0, investors[..10].to_vec()).expect("Crank should succeed");
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.last_stream, investors[9].stream);
+
+    // The next page repeats the last stream of the previous one
+    let result = crank_page(&mut ctx, key, pos_key, 10, investors[9..19].to_vec());
+    demand_instruction_error(get_ix_err(TollgateError::DuplicateInvestorStream), &result);
+
+    // The next page goes back to streams of the previous one
+    let result = crank_page(&mut ctx, key, pos_key, 10, investors[5..15].to_vec());
+    demand_instruction_error(
+        get_ix_err(TollgateError::InvestorStreamsOutOfOrder),
+        &result,
+    );
+
+    // Each pass starts over from the first stream
+    crank_day(&mut ctx, key, pos_key, &investors);
+    let progress = ctx.get_program_account::<Progress>(&get_progress_pda(vault).0);
+    assert_eq!(progress.phase, CrankPhase::Payout);
+    assert_eq!(progress.last_stream, investors.last().unwrap().stream);
+}
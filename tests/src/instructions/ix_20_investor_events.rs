@@ -0,0 +1,127 @@
+use anchor_client::solana_sdk::{
+    account::Account, native_token::LAMPORTS_PER_SOL, program_pack::Pack, pubkey::Pubkey,
+    signer::Signer,
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use tollgate::{constants::TWENTY_FOUR_HOURS, state::Policy};
+use tollgate_client::pda::get_policy_pda;
+use tollgate_indexer::{EventRecord, InvestorPaid, InvestorSkipped, SkipReason, TollgateEvent};
+
+use crate::{
+    instructions::ix_07_day_history::crank_day,
+    utils::{damm_v2::set_damm_v2_position_fees, svm::TestContext},
+};
+
+fn get_token_balance(ctx: &TestContext, address: &Pubkey) -> u64 {
+    ctx.svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+/// Splits the investor events of the records into (paid, skipped).
+fn investor_events(records: &[EventRecord]) -> (Vec<InvestorPaid>, Vec<InvestorSkipped>) {
+    let mut paid = vec![];
+    let mut skipped = vec![];
+    for record in records {
+        match &record.event {
+            TollgateEvent::InvestorPaid(e) => paid.push(e.clone()),
+            TollgateEvent::InvestorSkipped(e) => skipped.push(e.clone()),
+            _ => {}
+        }
+    }
+    (paid, skipped)
+}
+
+#[test]
+fn test_01_every_investor_is_reported_once() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let quote_mint = token.quote_mint;
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(vault).0);
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    let records = crank_day(&mut ctx, key, pos_key);
+    let (paid, skipped) = investor_events(&records);
+
+    // The payout pass reports each investor as either paid or skipped
+    let mut indexes: Vec<u32> = paid
+        .iter()
+        .map(|e| e.index)
+        .chain(skipped.iter().map(|e| e.index))
+        .collect();
+    indexes.sort_unstable();
+    assert_eq!(indexes, (0..policy.investor_count).collect::<Vec<_>>());
+
+    let page_payouts: u64 = records
+        .iter()
+        .filter_map(|record| match &record.event {
+            TollgateEvent::InvestorPayoutPage(e) => Some(e.payout),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(paid.iter().map(|e| e.amount).sum::<u64>(), page_payouts);
+
+    // The weights of the paid investors are part of the whole day
+    assert!(paid.iter().map(|e| e.share_bps).sum::<u64>() <= 10_000);
+    for event in &paid {
+        assert_eq!(event.vault, vault);
+        assert!(event.locked > 0);
+        assert!(event.amount >= policy.min_payout_lamports);
+        assert_eq!(
+            event.ata,
+            get_associated_token_address(&event.recipient, &quote_mint)
+        );
+        assert!(get_token_balance(&ctx, &event.ata) >= event.amount);
+    }
+}
+
+#[test]
+fn test_02_missing_atas_are_reported() {
+    let mut ctx = TestContext::default();
+    let key = "tollgate";
+    let pos_key = "initialize";
+
+    let token = ctx.tokens.get(key).expect("Token not found");
+    let vault = token.vault.pubkey();
+    let quote_mint = token.quote_mint;
+    let policy = ctx.get_program_account::<Policy>(&get_policy_pda(vault).0);
+    let atas: Vec<Pubkey> = token
+        .investors
+        .iter()
+        .take(policy.investor_count as usize)
+        .map(|investor| get_associated_token_address(&investor.key.pubkey(), &quote_mint))
+        .collect();
+
+    // Close every investor ATA
+    let originals: Vec<Option<Account>> = atas.iter().map(|ata| ctx.svm.get_account(ata)).collect();
+    for ata in &atas {
+        ctx.svm.set_account(*ata, Account::default()).unwrap();
+    }
+
+    ctx.time_travel_by_secs(TWENTY_FOUR_HOURS as u64);
+    set_damm_v2_position_fees(&mut ctx, key, pos_key, Some(0), Some(LAMPORTS_PER_SOL / 4));
+    let records = crank_day(&mut ctx, key, pos_key);
+    let (paid, skipped) = investor_events(&records);
+
+    // Investors with a weight are skipped for their missing ATA
+    assert!(paid.is_empty());
+    assert_eq!(skipped.len(), policy.investor_count as usize);
+    assert!(skipped
+        .iter()
+        .any(|event| event.reason == SkipReason::MissingAta));
+    assert!(skipped
+        .iter()
+        .all(|event| event.reason != SkipReason::BelowDust));
+
+    for (ata, original) in atas.iter().zip(originals) {
+        ctx.svm
+            .set_account(*ata, original.unwrap_or_default())
+            .unwrap();
+    }
+}
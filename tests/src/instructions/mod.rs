@@ -1,3 +1,21 @@
 mod ix_00_setup;
 mod ix_01_initialize;
 mod ix_02_crank;
+mod ix_03_migrate;
+mod ix_04_keeper;
+mod ix_05_indexer;
+mod ix_06_preview;
+mod ix_07_day_history;
+mod ix_08_investor_stats;
+mod ix_09_page_planner;
+mod ix_10_stream_states;
+mod ix_11_payout_redirect;
+mod ix_12_positions;
+mod ix_13_locked_liquidity;
+mod ix_14_rewards;
+mod ix_15_disabled_pool;
+mod ix_16_creator;
+mod ix_17_carry_policy;
+mod ix_18_investor_cap;
+mod ix_19_investor_order;
+mod ix_20_investor_events;
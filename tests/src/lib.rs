@@ -1,5 +1,9 @@
 pub mod constants;
 pub mod utils;
 
+#[cfg(test)]
+mod cli;
 #[cfg(test)]
 mod instructions;
+#[cfg(test)]
+mod math;
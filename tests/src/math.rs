@@ -0,0 +1,271 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use proptest::{collection::vec, prelude::*};
+use tollgate::{
+    constants::{ACCOUNT_RESERVED_SPACE, MAX_BPS},
+    error::TollgateError,
+    math::{
+        average_locked, bps_share, checked_sum, locked_fraction_bps, mul_div, pro_rata_share,
+        Rounding, UnlockSchedule,
+    },
+    state::{CapExcess, CapLimit, CrankPhase, DayState, InvestorCap, Progress},
+};
+
+fn is_error(result: anchor_client::anchor_lang::Result<u64>, expected: TollgateError) -> bool {
+    matches!(result, Err(err) if err == expected.into())
+}
+
+/// Returns the progress of a day whose snapshot pass is about to complete.
+fn snapshot_progress(locked_total: u64) -> Progress {
+    Progress {
+        version: Progress::VERSION,
+        vault: Pubkey::default(),
+        last_distribution_ts: 0,
+        daily_spent: 0,
+        carry: 0,
+        cursor: 0,
+        day_state: DayState::Same,
+        bump: 0,
+        phase: CrankPhase::Snapshot,
+        distributable: 0,
+        locked_total,
+        investor_pool: 0,
+        allocated_locked: 0,
+        allocated: 0,
+        reserve: 0,
+        reserve_release: 0,
+        investor_cap: None,
+        cap_passes: 0,
+        capped_count: 0,
+        capped_locked: 0,
+        pass_capped_count: 0,
+        pass_capped_locked: 0,
+        uncapped_allocated_locked: 0,
+        uncapped_allocated: 0,
+        last_stream: Pubkey::default(),
+        reserved: [0; ACCOUNT_RESERVED_SPACE],
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_sum_of_shares_never_exceeds_pool(
+        pool in any::<u64>(),
+        lockeds in vec(0..=u64::MAX / 64, 1..64),
+    ) {
+        let locked_total = checked_sum(&lockeds).unwrap();
+        let mut paid = 0u128;
+        for locked in &lockeds {
+            paid += pro_rata_share(pool, *locked, locked_total).unwrap() as u128;
+        }
+        prop_assert!(paid <= pool as u128);
+        // Each floor loses less than one unit
+        prop_assert!(pool as u128 - paid < lockeds.len() as u128 || locked_total == 0);
+    }
+
+    #[test]
+    fn test_cumulative_floor_allocates_the_whole_pool(
+        pool in any::<u64>(),
+        lockeds in vec(0..=u64::MAX / 64, 1..64),
+    ) {
+        let locked_total = checked_sum(&lockeds).unwrap();
+        let (mut allocated_locked, mut allocated, mut sum) = (0u64, 0u64, 0u128);
+        for locked in &lockeds {
+            allocated_locked += locked;
+            let cumulative = pro_rata_share(pool, allocated_locked, locked_total).unwrap();
+            let allocation = cumulative - allocated;
+            // Each allocation is within one unit of the exact pro rata share
+            let exact = pool as u128 * *locked as u128;
+            prop_assert!(allocation as u128 * locked_total as u128 <= exact + locked_total as u128);
+            prop_assert!(exact < (allocation as u128 + 1) * locked_total as u128);
+            allocated = cumulative;
+            sum += allocation as u128;
+        }
+        if locked_total > 0 {
+            prop_assert_eq!(sum, pool as u128);
+        } else {
+            prop_assert_eq!(sum, 0);
+        }
+    }
+
+    #[test]
+    fn test_bps_share_never_exceeds_amount(amount in any::<u64>(), bps in any::<u64>()) {
+        let share = bps_share(amount, bps).unwrap();
+        prop_assert!(share <= amount);
+        if bps >= MAX_BPS as u64 {
+            prop_assert_eq!(share, amount);
+        }
+    }
+
+    #[test]
+    fn test_locked_fraction_is_capped(locked_total in any::<u64>(), y0 in 1..=u64::MAX) {
+        let f_locked = locked_fraction_bps(locked_total, y0).unwrap();
+        prop_assert!(f_locked <= MAX_BPS as u64);
+        if locked_total >= y0 {
+            prop_assert_eq!(f_locked, MAX_BPS as u64);
+        }
+    }
+
+    #[test]
+    fn test_rounding_up_is_at_most_one_above_down(
+        a in any::<u64>(),
+        b in any::<u64>(),
+        denominator in 1..=u64::MAX,
+    ) {
+        let down = mul_div(a, b, denominator, Rounding::Down);
+        let up = mul_div(a, b, denominator, Rounding::Up);
+        match (down, up) {
+            (Ok(down), Ok(up)) => {
+                prop_assert!(up >= down && up - down <= 1);
+                let exact = (a as u128 * b as u128).is_multiple_of(denominator as u128);
+                prop_assert_eq!(up == down, exact);
+            }
+            (Ok(down), Err(_)) => prop_assert_eq!(down, u64::MAX),
+            (Err(_), up) => prop_assert!(up.is_err()),
+        }
+    }
+
+    #[test]
+    fn test_average_locked_integrates_the_unlock_schedule(
+        amount in 0..100_000u64,
+        start in 0..400u64,
+        vesting in 0..600u64,
+        base in 0..20_000u64,
+        steps_delay in 0..100u64,
+        period in 0..60u64,
+        amount_per_period in 0..5_000u64,
+        epoch_start in 0..1_000u64,
+        epoch_len in 1..500u64,
+    ) {
+        let schedule = UnlockSchedule {
+            amount,
+            start,
+            end: start + vesting,
+            base,
+            steps_start: start + steps_delay,
+            period,
+            amount_per_period,
+        };
+        // Every second of the epoch weighs the same
+        let locked: u64 = (epoch_start..epoch_start + epoch_len)
+            .map(|t| amount - schedule.unlocked_at(t))
+            .sum();
+        prop_assert_eq!(
+            average_locked(&schedule, epoch_start, epoch_start + epoch_len).unwrap(),
+            locked / epoch_len
+        );
+    }
+
+    #[test]
+    fn test_investor_cap_water_filling(
+        pool in any::<u64>(),
+        bps in 1..=MAX_BPS,
+        redistribute in any::<bool>(),
+        lockeds in vec(0..=u64::MAX / 64, 1..64),
+    ) {
+        let locked_total = checked_sum(&lockeds).unwrap();
+        let investor_cap = InvestorCap {
+            limit: CapLimit::Bps { bps },
+            excess: if redistribute { CapExcess::Redistribute } else { CapExcess::Carry },
+        };
+        let cap = bps_share(pool, bps as u64).unwrap();
+        let mut progress = snapshot_progress(locked_total);
+        progress.complete_snapshot(pool, Some(&investor_cap)).unwrap();
+
+        // Each pass caps at least one more investor, or settles the level
+        while progress.phase == CrankPhase::Cap {
+            progress.record_cap_page(&lockeds).unwrap();
+            progress.complete_cap_pass().unwrap();
+            prop_assert!(progress.cap_passes as usize <= lockeds.len() + 1);
+        }
+
+        let mut sum = 0u128;
+        let mut uncapped_locked = 0u64;
+        for locked in &lockeds {
+            let allocation = progress.allocate(*locked).unwrap();
+            // Nobody is allocated more than the cap, capped investors exactly the cap
+            prop_assert!(allocation.amount <= cap);
+            if allocation.capped {
+                prop_assert_eq!(allocation.amount, cap);
+            } else {
+                uncapped_locked += locked;
+            }
+            sum += allocation.amount as u128;
+        }
+        prop_assert!(sum <= pool as u128);
+        prop_assert_eq!(progress.allocated as u128, sum);
+        // A redistributed excess is water-filled over the uncapped investors
+        if redistribute && uncapped_locked > 0 {
+            prop_assert_eq!(sum, pool as u128);
+        }
+    }
+}
+
+#[test]
+fn test_mul_div_does_not_overflow_u64_intermediates() {
+    // investor_fee_quote * locked overflows u64 for high supply mints
+    let pool = u64::MAX / 2;
+    let locked = u64::MAX / 3;
+    let locked_total = u64::MAX / 3 * 2;
+    assert_eq!(
+        pro_rata_share(pool, locked, locked_total).unwrap(),
+        pool / 2
+    );
+    assert_eq!(
+        bps_share(u64::MAX, 7_000).unwrap(),
+        12_912_720_851_596_686_130
+    );
+    assert_eq!(
+        locked_fraction_bps(u64::MAX, u64::MAX / 2).unwrap(),
+        MAX_BPS as u64
+    );
+}
+
+#[test]
+fn test_mul_div_errors() {
+    assert!(is_error(
+        mul_div(1, 1, 0, Rounding::Down),
+        TollgateError::MathDivisionByZero
+    ));
+    assert!(is_error(
+        mul_div(u64::MAX, 2, 1, Rounding::Down),
+        TollgateError::MathOverflow
+    ));
+    assert!(is_error(
+        checked_sum(&[u64::MAX, 1]),
+        TollgateError::MathOverflow
+    ));
+    assert!(is_error(
+        locked_fraction_bps(1, 0),
+        TollgateError::MathDivisionByZero
+    ));
+    assert_eq!(pro_rata_share(100, 1, 0).unwrap(), 0);
+}
+
+#[test]
+fn test_average_locked_weighs_the_whole_epoch() {
+    let schedule = UnlockSchedule {
+        amount: 1_000,
+        start: 100,
+        end: 500,
+        base: 0,
+        steps_start: 100,
+        period: 100,
+        amount_per_period: 250,
+    };
+    // Locked over the whole epoch, then unlocked over the whole epoch
+    assert_eq!(average_locked(&schedule, 0, 100).unwrap(), 1_000);
+    assert_eq!(average_locked(&schedule, 500, 600).unwrap(), 0);
+    // A quarter unlocks every period: 1000, 750, 500 then 250 for 100s each
+    assert_eq!(average_locked(&schedule, 100, 500).unwrap(), 625);
+    assert!(is_error(
+        average_locked(&schedule, 100, 100),
+        TollgateError::MathDivisionByZero
+    ));
+}
+
+#[test]
+fn test_rounding_modes() {
+    assert_eq!(mul_div(10, 1, 3, Rounding::Down).unwrap(), 3);
+    assert_eq!(mul_div(10, 1, 3, Rounding::Up).unwrap(), 4);
+    assert_eq!(mul_div(9, 1, 3, Rounding::Up).unwrap(), 3);
+}
@@ -7,80 +7,12 @@ use anchor_client::{
 use anchor_spl::{associated_token::get_associated_token_address_with_program_id, token_2022};
 use anyhow::{ensure, Result};
 use ruint::aliases::U256;
-use tollgate::constants::damm_v2_constants;
-
-use crate::utils::{find_program_address, find_program_event_authority, svm::TestContext};
-
-pub fn max_key(left: &Pubkey, right: &Pubkey) -> [u8; 32] {
-    std::cmp::max(left, right).to_bytes()
-}
-
-pub fn min_key(left: &Pubkey, right: &Pubkey) -> [u8; 32] {
-    std::cmp::min(left, right).to_bytes()
-}
-
-/// Calculates the address of the Position NFT account PDA.
-pub fn get_position_nft_account_pda(position_nft_mint: Pubkey) -> (Pubkey, u8) {
-    find_program_address(
-        &[
-            damm_v2_constants::seeds::POSITION_NFT_ACCOUNT_PREFIX,
-            position_nft_mint.as_ref(),
-        ],
-        Some(&damm_v2::ID),
-    )
-}
-
-/// Calculates the address of the Pool PDA with config.
-pub fn get_pool_with_config_pda(
-    config: Pubkey,
-    base_mint: Pubkey,
-    quote_mint: Pubkey,
-) -> (Pubkey, u8) {
-    find_program_address(
-        &[
-            damm_v2_constants::seeds::POOL_PREFIX,
-            config.as_ref(),
-            &max_key(&base_mint, &quote_mint),
-            &min_key(&base_mint, &quote_mint),
-        ],
-        Some(&damm_v2::ID),
-    )
-}
-
-/// Calculates the address of the Pool PDA.
-pub fn get_pool_pda(base_mint: Pubkey, quote_mint: Pubkey) -> (Pubkey, u8) {
-    find_program_address(
-        &[
-            damm_v2_constants::seeds::CUSTOMIZABLE_POOL_PREFIX,
-            &max_key(&base_mint, &quote_mint),
-            &min_key(&base_mint, &quote_mint),
-        ],
-        Some(&damm_v2::ID),
-    )
-}
-
-/// Calculates the address of the Position PDA.
-pub fn get_position_pda(position_nft_mint: Pubkey) -> (Pubkey, u8) {
-    find_program_address(
-        &[
-            damm_v2_constants::seeds::POSITION_PREFIX,
-            position_nft_mint.as_ref(),
-        ],
-        Some(&damm_v2::ID),
-    )
-}
+pub use tollgate_client::pda::{
+    get_pool_pda, get_pool_with_config_pda, get_position_nft_account_pda, get_position_pda,
+    get_token_vault_pda,
+};
 
-/// Calculates the address of the Token Vault PDA.
-pub fn get_token_vault_pda(token_mint: Pubkey, pool: Pubkey) -> (Pubkey, u8) {
-    find_program_address(
-        &[
-            damm_v2_constants::seeds::TOKEN_VAULT_PREFIX,
-            token_mint.as_ref(),
-            pool.as_ref(),
-        ],
-        Some(&damm_v2::ID),
-    )
-}
+use crate::utils::{find_program_event_authority, svm::TestContext};
 
 pub fn set_damm_v2_position_fees(
     ctx: &mut TestContext,
@@ -96,6 +28,18 @@ pub fn set_damm_v2_position_fees(
     let pos_mint = token.pos_mints.get(pos_key).unwrap();
     let (position, _) = get_position_pda(pos_mint.pubkey());
 
+    set_position_fees(ctx, pool, position, quote_mint, base_fee, quote_fee);
+}
+
+/// Overwrites the pending fees of any DAMM v2 position.
+pub fn set_position_fees(
+    ctx: &mut TestContext,
+    pool: Pubkey,
+    position: Pubkey,
+    quote_mint: Pubkey,
+    base_fee: Option<u64>,
+    quote_fee: Option<u64>,
+) {
     let pool_acc = ctx.svm.get_account(&pool).unwrap();
     let pos_acc = ctx.svm.get_account(&position).unwrap();
 
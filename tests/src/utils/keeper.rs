@@ -0,0 +1,64 @@
+use anchor_client::solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use solana_clock::Clock;
+use tollgate_keeper::{KeeperRpc, SendError, SendOutcome};
+
+use crate::utils::svm::TestContext;
+
+/// Runs the keeper against the LiteSVM bank of the test context.
+pub struct SvmKeeperRpc<'a> {
+    pub ctx: &'a mut TestContext,
+}
+
+impl KeeperRpc for SvmKeeperRpc<'_> {
+    fn get_account(&self, address: &Pubkey) -> anyhow::Result<Option<Account>> {
+        Ok(self.ctx.svm.get_account(address))
+    }
+
+    fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> anyhow::Result<Vec<Option<Account>>> {
+        Ok(addresses
+            .iter()
+            .map(|address| self.ctx.svm.get_account(address))
+            .collect())
+    }
+
+    fn get_unix_timestamp(&self) -> anyhow::Result<i64> {
+        Ok(self.ctx.svm.get_sysvar::<Clock>().unix_timestamp)
+    }
+
+    fn send(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+    ) -> Result<SendOutcome, SendError> {
+        // Resending the same page must not be rejected as an already processed transaction
+        self.ctx.svm.expire_blockhash();
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            self.ctx.svm.latest_blockhash(),
+        );
+
+        match self.ctx.svm.send_transaction(transaction) {
+            Ok(meta) => Ok(SendOutcome {
+                signature: meta.signature.to_string(),
+                logs: meta.logs,
+            }),
+            Err(failed) => match failed.err {
+                TransactionError::InstructionError(index, error) => Err(SendError::Instruction {
+                    index,
+                    error,
+                    logs: failed.meta.logs,
+                }),
+                err => Err(SendError::Transport(err.to_string())),
+            },
+        }
+    }
+}
@@ -7,6 +7,7 @@ use tollgate::constants::{POLICY_SEED, PROGRESS_SEED};
 use crate::utils::svm::TestContext;
 
 pub mod damm_v2;
+pub mod keeper;
 pub mod streamflow;
 pub mod svm;
 
@@ -22,7 +23,7 @@ pub fn find_program_address(seeds: &[&[u8]], program_id: Option<&Pubkey>) -> (Pu
 }
 
 pub fn find_program_event_authority(program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"__event_authority"], program_id)
+    tollgate_client::pda::get_event_authority_pda(program_id)
 }
 
 pub fn rand_investors_num(num_rng: Range<u32>) -> u32 {
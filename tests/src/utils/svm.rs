@@ -10,16 +10,17 @@ use anchor_client::{
     anchor_lang::AccountDeserialize,
     solana_sdk::{
         account::Account,
-        instruction::{Instruction, InstructionError},
+        instruction::{CompiledInstruction, Instruction, InstructionError},
+        message::{v0, VersionedMessage},
         native_token::LAMPORTS_PER_SOL,
         program_option::COption,
         program_pack::Pack,
         pubkey::Pubkey,
-        signature::{read_keypair_file, Keypair},
+        signature::{read_keypair_file, Keypair, Signature},
         signer::Signer,
         system_instruction::{self, SystemError},
         system_program,
-        transaction::{Transaction, TransactionError},
+        transaction::{Transaction, TransactionError, VersionedTransaction},
     },
 };
 use anchor_spl::{
@@ -40,8 +41,12 @@ use litesvm::{
 use num_traits::FromPrimitive;
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
 use solana_clock::Clock;
 use tollgate::error::TollgateError;
+use tollgate_indexer::{
+    parse_inner_instructions, records_from_inner_instructions, EventRecord, TollgateEvent,
+};
 
 use crate::constants::{SOL_MINT, USDC_MINT};
 
@@ -226,6 +231,7 @@ pub struct TestContext {
     pub payer: Arc<Keypair>,
     pub svm: MutexGuard<'static, LiteSVM>,
     pub tokens: MutexGuard<'static, HashMap<String, Token>>,
+    account_keys: HashMap<Signature, Vec<Pubkey>>, // Keys of the sent transactions, by signature
 }
 
 impl Default for TestContext {
@@ -237,6 +243,7 @@ impl Default for TestContext {
             },
             svm: SVM.lock().unwrap(),
             tokens: TOKENS.lock().unwrap(),
+            account_keys: HashMap::new(),
         }
     }
 }
@@ -290,7 +297,80 @@ impl TestContext {
         transaction.sign(signers, self.svm.latest_blockhash());
 
         // Process transaction
-        self.svm.send_transaction(transaction).map_err(Box::new)
+        let account_keys = transaction.message.account_keys.clone();
+        let meta = self.svm.send_transaction(transaction).map_err(Box::new)?;
+        self.account_keys.insert(meta.signature, account_keys);
+        Ok(meta)
+    }
+
+    pub fn send_versioned_transaction(
+        &mut self,
+        message: v0::Message,
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        let account_keys = self.get_loaded_account_keys(&message);
+        let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), signers)
+            .expect("Transaction should be signed");
+        let meta = self.svm.send_transaction(transaction).map_err(Box::new)?;
+        self.account_keys.insert(meta.signature, account_keys);
+        Ok(meta)
+    }
+
+    /// Returns the static keys of a v0 message followed by the addresses it
+    /// loads from lookup tables, writable then readonly.
+    fn get_loaded_account_keys(&self, message: &v0::Message) -> Vec<Pubkey> {
+        let tables: Vec<(Vec<Pubkey>, &v0::MessageAddressTableLookup)> = message
+            .address_table_lookups
+            .iter()
+            .map(|lookup| {
+                let account = self
+                    .svm
+                    .get_account(&lookup.account_key)
+                    .expect("Lookup table not found");
+                let table = AddressLookupTable::deserialize(&account.data)
+                    .expect("Lookup table should decode");
+                (table.addresses.to_vec(), lookup)
+            })
+            .collect();
+
+        let mut account_keys = message.account_keys.clone();
+        for (addresses, lookup) in &tables {
+            account_keys.extend(
+                lookup
+                    .writable_indexes
+                    .iter()
+                    .map(|i| addresses[*i as usize]),
+            );
+        }
+        for (addresses, lookup) in &tables {
+            account_keys.extend(
+                lookup
+                    .readonly_indexes
+                    .iter()
+                    .map(|i| addresses[*i as usize]),
+            );
+        }
+        account_keys
+    }
+
+    /// Decodes the Tollgate events of a transaction sent through the context.
+    pub fn get_events(&self, meta: &TransactionMetadata) -> Vec<TollgateEvent> {
+        parse_inner_instructions(
+            &self.account_keys[&meta.signature],
+            &get_inner_instructions(meta),
+        )
+    }
+
+    /// Decodes the Tollgate events of a transaction sent through the context
+    /// into records at `slot`.
+    pub fn get_records(&self, meta: &TransactionMetadata, slot: u64) -> Vec<EventRecord> {
+        records_from_inner_instructions(
+            &meta.signature.to_string(),
+            slot,
+            None,
+            &self.account_keys[&meta.signature],
+            &get_inner_instructions(meta),
+        )
     }
 
     pub fn create_spl_token(
@@ -354,6 +434,15 @@ impl TestContext {
     }
 }
 
+/// Returns the inner instructions of a transaction, in execution order.
+pub fn get_inner_instructions(meta: &TransactionMetadata) -> Vec<CompiledInstruction> {
+    meta.inner_instructions
+        .iter()
+        .flatten()
+        .map(|inner| inner.instruction.clone())
+        .collect()
+}
+
 pub fn get_ix_err(err: TollgateError) -> InstructionError {
     InstructionError::Custom(6000 + err as u32)
 }